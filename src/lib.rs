@@ -1,7 +1,7 @@
 pub mod modules;
-pub use modules::audio_control::AudioModule;
+pub use modules::audio_control::{AudioModule, AudioModuleConfig};
 pub use modules::core::{
     create_error_response, create_response, get_timestamp, handle_message,
     models::{ModuleType, ServerEvent},
-    Broadcaster, ModuleRegistry,
+    Broadcaster, Clock, ModuleRegistry, SystemClock,
 };
@@ -1,3 +1,5 @@
+use std::net::SocketAddr;
+
 use crate::modules::app_router::app;
 
 pub mod modules;
@@ -12,7 +14,11 @@ async fn main() {
 
     println!("Server running on http://0.0.0.0:{}", PORT_SERVER);
 
-    axum::serve(listener, app())
-        .await
-        .expect("Failed to start server");
+    // O endereço de origem de cada conexão vai para o log de auditoria
+    axum::serve(
+        listener,
+        app().into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .expect("Failed to start server");
 }
@@ -1,11 +1,15 @@
 use std::sync::Arc;
 
-use axum::{response::Response, Router};
+use axum::{middleware, response::Response, routing::get, Router};
 
 use crate::modules::audio_control::AudioModule;
+use crate::modules::core::audit::{audit_requests, handle_audit_query, AuditLog, AuditRotation};
 use crate::modules::core::errors::error_codes;
+use crate::modules::core::persistence::default_data_dir;
 use crate::modules::core::response::create_error_response;
-use crate::modules::core::ModuleRegistry;
+use crate::modules::core::{ModuleRegistry, SystemClock};
+
+const AUDIT_LOG_FILE: &str = "audit.log";
 
 fn default_registry() -> ModuleRegistry {
     let mut registry = ModuleRegistry::new();
@@ -13,12 +17,31 @@ fn default_registry() -> ModuleRegistry {
     registry
 }
 
+fn default_audit_log() -> AuditLog {
+    AuditLog::open(
+        default_data_dir().join(AUDIT_LOG_FILE),
+        AuditRotation::default(),
+        Arc::new(SystemClock),
+    )
+}
+
 pub fn app() -> Router {
-    app_with_registry(default_registry())
+    app_with_registry(default_registry(), Arc::new(default_audit_log()))
 }
 
-pub fn app_with_registry(registry: ModuleRegistry) -> Router {
-    registry.http_routes().fallback(not_found)
+/// Monta o router com os módulos registrados e o log de auditoria
+///
+/// Todas as rotas, inclusive o fallback, passam pelo log de auditoria.
+pub fn app_with_registry(registry: ModuleRegistry, audit: Arc<AuditLog>) -> Router {
+    registry.start_background_tasks();
+    registry
+        .http_routes()
+        .route(
+            "/api/v1/audit",
+            get(handle_audit_query).with_state(Arc::clone(&audit)),
+        )
+        .fallback(not_found)
+        .layer(middleware::from_fn_with_state(audit, audit_requests))
 }
 
 async fn not_found() -> Response {
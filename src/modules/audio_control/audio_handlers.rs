@@ -1,41 +1,779 @@
-use crate::modules::audio_control::models::audio_requests::Volume;
+use crate::modules::audio_control::models::audio_requests::{
+    ForgetVolumeMemoryRequest, LinkNameRequest, MoveGroupRequest, ProfileNameRequest,
+    RemoveRoutingPreferenceRequest, RuleDryRunRequest, RuleNameRequest, ScheduleTaskRequest,
+    ScheduledTaskIdRequest, SetApplicationVolumeRequest, SetDeviceChannelsRequest,
+    SetDeviceVolumeRequest, SetGroupChannelsRequest, SetGroupVolumeRequest,
+    SetRoutingPreferenceRequest, SetSessionMuteRequest, SetSessionVolumeRequest,
+    SetVolumeMemoryRequest, StartFocusModeRequest, StartSleepTimerRequest,
+};
+use crate::modules::audio_control::models::{
+    normalize_process_name, AutomationRule, ChannelAdjustment, ChannelVolumes, DuckingConfig,
+    HistoryTarget, HistoryValue, LinkTarget, SessionGroup, SessionMember, SessionVolume,
+    SoundProfile, VolumeLimits, VolumeLink,
+};
 use crate::modules::audio_control::{
-    platform::audio_system_interface::AudioSystemInterface, types::GroupId,
+    errors::AudioError,
+    platform::{async_audio_system::AsyncAudioSystem, cached_audio_system::CachedAudioSystem},
+    services::{
+        self, AppRoutingService, DuckingService, FocusModeService, HistoryService, ProfileService,
+        RulesService, SchedulerService, SleepTimerService, StateService, VolumeLimitService,
+        VolumeLinkService, VolumeMemoryService,
+    },
+    types::{AudioResult, GroupId},
 };
 use crate::modules::core::response::create_response;
 use crate::modules::core::traits::module_handler::ModuleResponse;
+use crate::modules::core::ClientId;
 use anyhow::anyhow;
+use axum::{
+    body::Body,
+    http::{
+        header::{CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_TYPE, ETAG},
+        StatusCode,
+    },
+    response::Response,
+};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Ícones mudam apenas quando o executável é atualizado
+const ICON_CACHE_CONTROL: &str = "public, max-age=86400";
 
-pub fn handle_get_volume(audio_system: &dyn AudioSystemInterface) -> ModuleResponse {
-    let volume = audio_system.get_actual_volume().map_err(|e| anyhow!(e))?;
+pub async fn handle_get_volume(audio_system: &dyn AsyncAudioSystem) -> ModuleResponse {
+    let volume = audio_system
+        .get_actual_volume()
+        .await
+        .map_err(|e| anyhow!(e))?;
     Ok(create_response(volume, None))
 }
 
-pub fn handle_list_sessions(
-    audio_system: &dyn AudioSystemInterface,
+pub async fn handle_list_sessions(
+    audio_system: &dyn AsyncAudioSystem,
     device_id: String,
+    expand: bool,
 ) -> ModuleResponse {
     let sessions = audio_system
-        .get_sessions_for_device(&device_id)
+        .get_sessions_for_device(&device_id, expand)
+        .await
         .map_err(|e| anyhow!(e))?;
     let size = sessions.len();
     Ok(create_response(sessions, Some(size)))
 }
 
-pub fn handle_set_group_volume(
-    audio_system: &dyn AudioSystemInterface,
-    device_id: String,
-    group_id: GroupId,
-    volume: Volume,
+pub async fn handle_list_all_sessions(
+    audio_system: &dyn AsyncAudioSystem,
+    expand: bool,
+) -> ModuleResponse {
+    let devices = services::list_all_sessions(audio_system, expand)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let size = devices.len();
+    Ok(create_response(devices, Some(size)))
+}
+
+pub async fn handle_list_applications(audio_system: &dyn AsyncAudioSystem) -> ModuleResponse {
+    let applications = services::list_applications(audio_system)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let size = applications.len();
+    Ok(create_response(applications, Some(size)))
+}
+
+/// Define o volume master de um dispositivo, respeitando os limites
+///
+/// Retorna o volume aplicado, que pode ser menor que o pedido.
+pub async fn handle_set_device_volume(
+    audio_system: &dyn AsyncAudioSystem,
+    limits: &VolumeLimitService,
+    links: &VolumeLinkService,
+    history: &HistoryService,
+    client: &ClientId,
+    request: SetDeviceVolumeRequest,
 ) -> ModuleResponse {
+    let device_id = request.device_id;
+    let current = audio_system
+        .get_device_volume(&device_id)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let volume = limits
+        .limit_master(current, request.volume.into())
+        .map_err(|e| anyhow!(e))?;
     audio_system
-        .set_group_volume(&group_id, &device_id, volume.into())
+        .set_device_volume(&device_id, volume)
+        .await
+        .map_err(|e| anyhow!(e))?;
+
+    history.record(
+        client,
+        HistoryTarget::DeviceVolume {
+            device_id: device_id.clone(),
+        },
+        HistoryValue::Volume(current),
+        HistoryValue::Volume(volume),
+    );
+    links
+        .propagate(&LinkTarget::Device { device_id }, volume)
+        .await;
+    Ok(create_response(volume, None))
+}
+
+pub async fn handle_set_application_volume(
+    audio_system: &dyn AsyncAudioSystem,
+    limits: &VolumeLimitService,
+    links: &VolumeLinkService,
+    history: &HistoryService,
+    client: &ClientId,
+    request: SetApplicationVolumeRequest,
+) -> ModuleResponse {
+    let process_name = request.process_name;
+    let devices = services::list_all_sessions(audio_system, true)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let normalized = normalize_process_name(&process_name);
+    let groups: Vec<(&str, &SessionGroup)> = devices
+        .iter()
+        .flat_map(|device| {
+            device
+                .sessions
+                .iter()
+                .filter(|group| normalize_process_name(&group.app.process_name) == normalized)
+                .map(|group| (device.device_id.as_str(), group))
+        })
+        .collect();
+
+    let resolved: Vec<&SessionGroup> = groups.iter().map(|(_, group)| *group).collect();
+    let volume = limits
+        .limit_application(&resolved, request.volume.into(), request.mode)
         .map_err(|e| anyhow!(e))?;
+    let instances = services::set_application_volume_in(
+        audio_system,
+        &devices,
+        &process_name,
+        volume,
+        request.mode,
+    )
+    .await
+    .map_err(|e| anyhow!(e))?;
+
+    let sessions = |after: bool| -> Vec<SessionVolume> {
+        groups
+            .iter()
+            .flat_map(|(device_id, group)| match after {
+                true => SessionVolume::after_group_volume(device_id, group, volume, request.mode),
+                false => SessionVolume::of_group(device_id, group),
+            })
+            .collect()
+    };
+    history.record(
+        client,
+        HistoryTarget::ApplicationVolume {
+            process_name: process_name.clone(),
+        },
+        HistoryValue::Sessions(sessions(false)),
+        HistoryValue::Sessions(sessions(true)),
+    );
+    links
+        .propagate(&LinkTarget::Application { process_name }, volume)
+        .await;
+    let size = instances.len();
+    Ok(create_response(instances, Some(size)))
+}
+
+/// Resposta aos pedidos de volume que serão aplicados junto com um mais novo
+pub fn handle_coalesced_volume_write() -> ModuleResponse {
+    Ok(create_response("Volume update coalesced", None))
+}
+
+pub async fn handle_set_group_volume(
+    audio_system: &dyn AsyncAudioSystem,
+    limits: &VolumeLimitService,
+    links: &VolumeLinkService,
+    history: &HistoryService,
+    client: &ClientId,
+    request: SetGroupVolumeRequest,
+) -> ModuleResponse {
+    let device_id = request.device_id;
+    let group_id = GroupId::new(request.group_id);
+    let group = find_group(audio_system, &device_id, &group_id)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let volume = limits
+        .limit_group(&group, request.volume.into(), request.mode)
+        .map_err(|e| anyhow!(e))?;
+    audio_system
+        .set_group_volume(&group_id, &device_id, volume, request.mode)
+        .await
+        .map_err(|e| anyhow!(e))?;
+
+    let target = HistoryTarget::GroupVolume {
+        device_id: device_id.clone(),
+        group_id: group_id.to_string(),
+    };
+    history.record(
+        client,
+        target,
+        HistoryValue::Sessions(SessionVolume::of_group(&device_id, &group)),
+        HistoryValue::Sessions(SessionVolume::after_group_volume(
+            &device_id,
+            &group,
+            volume,
+            request.mode,
+        )),
+    );
+    links
+        .propagate(
+            &LinkTarget::Group {
+                device_id,
+                group_id: group_id.to_string(),
+            },
+            volume,
+        )
+        .await;
     Ok(create_response("Group volume set successfully", None))
 }
 
-pub fn handle_list_devices(audio_system: &dyn AudioSystemInterface) -> ModuleResponse {
-    let devices = audio_system.list_output_devices().map_err(|e| anyhow!(e))?;
+pub async fn handle_set_session_volume(
+    audio_system: &dyn AsyncAudioSystem,
+    limits: &VolumeLimitService,
+    history: &HistoryService,
+    client: &ClientId,
+    request: SetSessionVolumeRequest,
+) -> ModuleResponse {
+    let (device_id, session_id) = (request.device_id, request.session_id);
+    let member = find_session(audio_system, &device_id, &session_id)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let volume = limits
+        .limit_session(&member, request.volume.into())
+        .map_err(|e| anyhow!(e))?;
+    audio_system
+        .set_session_volume(&device_id, &session_id, volume)
+        .await
+        .map_err(|e| anyhow!(e))?;
+
+    history.record(
+        client,
+        HistoryTarget::SessionVolume {
+            device_id,
+            session_id,
+        },
+        HistoryValue::Volume(member.volume_level),
+        HistoryValue::Volume(volume),
+    );
+    Ok(create_response("Session volume set successfully", None))
+}
+
+pub async fn handle_set_session_mute(
+    audio_system: &dyn AsyncAudioSystem,
+    history: &HistoryService,
+    client: &ClientId,
+    request: SetSessionMuteRequest,
+) -> ModuleResponse {
+    let (device_id, session_id) = (request.device_id, request.session_id);
+    let member = find_session(audio_system, &device_id, &session_id)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    audio_system
+        .set_session_mute(&device_id, &session_id, request.muted)
+        .await
+        .map_err(|e| anyhow!(e))?;
+
+    history.record(
+        client,
+        HistoryTarget::SessionMute {
+            device_id,
+            session_id,
+        },
+        HistoryValue::Muted(member.muted),
+        HistoryValue::Muted(request.muted),
+    );
+    Ok(create_response("Session mute set successfully", None))
+}
+
+/// Grupo de um dispositivo, com as sessões
+async fn find_group(
+    audio_system: &dyn AsyncAudioSystem,
+    device_id: &str,
+    group_id: &GroupId,
+) -> AudioResult<SessionGroup> {
+    audio_system
+        .get_sessions_for_device(device_id, true)
+        .await?
+        .into_iter()
+        .find(|group| group.id == *group_id)
+        .ok_or(AudioError::NoSessionsFound)
+}
+
+async fn find_session(
+    audio_system: &dyn AsyncAudioSystem,
+    device_id: &str,
+    session_id: &str,
+) -> AudioResult<SessionMember> {
+    audio_system
+        .get_sessions_for_device(device_id, true)
+        .await?
+        .into_iter()
+        .flat_map(|group| group.sessions.unwrap_or_default())
+        .find(|member| member.session_id == session_id)
+        .ok_or_else(|| AudioError::SessionNotFound {
+            session_id: session_id.to_string(),
+        })
+}
+
+/// Retorna o ícone de um grupo em PNG, com `ETag` para revalidação
+///
+/// Quando `if_none_match` corresponde ao ícone atual a resposta é `304`
+/// sem corpo.
+pub async fn handle_get_group_icon(
+    audio_system: &dyn AsyncAudioSystem,
+    group_id: GroupId,
+    if_none_match: Option<&str>,
+) -> ModuleResponse {
+    let png = audio_system
+        .get_group_icon(&group_id)
+        .await
+        .map_err(|e| anyhow!(e))?;
+
+    let mut hasher = DefaultHasher::new();
+    png.hash(&mut hasher);
+    let etag = format!("\"{:016x}\"", hasher.finish());
+
+    let builder = Response::builder()
+        .header(ETAG, &etag)
+        .header(CACHE_CONTROL, ICON_CACHE_CONTROL);
+
+    let response = if if_none_match == Some(etag.as_str()) {
+        builder.status(StatusCode::NOT_MODIFIED).body(Body::empty())
+    } else {
+        builder
+            .status(StatusCode::OK)
+            .header(CONTENT_TYPE, "image/png")
+            .body(Body::from(png))
+    };
+    response.map_err(|e| anyhow!(e))
+}
+
+pub async fn handle_list_devices(
+    audio_system: &dyn AsyncAudioSystem,
+    include_inactive: bool,
+) -> ModuleResponse {
+    let devices = audio_system
+        .list_output_devices(include_inactive)
+        .await
+        .map_err(|e| anyhow!(e))?;
     let size = devices.len();
     Ok(create_response(devices, Some(size)))
 }
+
+pub async fn handle_get_device_channels(
+    audio_system: &dyn AsyncAudioSystem,
+    device_id: String,
+) -> ModuleResponse {
+    let levels = audio_system
+        .get_device_channel_volumes(&device_id)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    Ok(create_response(ChannelVolumes::new(levels), None))
+}
+
+/// Define os canais de um dispositivo, respeitando os limites
+///
+/// Retorna os níveis aplicados, que podem ser menores que os pedidos.
+pub async fn handle_set_device_channels(
+    audio_system: &dyn AsyncAudioSystem,
+    limits: &VolumeLimitService,
+    history: &HistoryService,
+    client: &ClientId,
+    request: SetDeviceChannelsRequest,
+) -> ModuleResponse {
+    let adjustment = ChannelAdjustment::try_from(request.levels).map_err(|e| anyhow!(e))?;
+    let current = audio_system
+        .get_device_channel_volumes(&request.device_id)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let levels = adjustment.resolve(&current).map_err(|e| anyhow!(e))?;
+    let levels = limits
+        .limit_device_channels(&current, levels)
+        .map_err(|e| anyhow!(e))?;
+    audio_system
+        .set_device_channel_volumes(&request.device_id, &levels)
+        .await
+        .map_err(|e| anyhow!(e))?;
+
+    history.record(
+        client,
+        HistoryTarget::DeviceChannels {
+            device_id: request.device_id,
+        },
+        HistoryValue::Levels(current),
+        HistoryValue::Levels(levels.clone()),
+    );
+    Ok(create_response(ChannelVolumes::new(levels), None))
+}
+
+pub async fn handle_get_group_channels(
+    audio_system: &dyn AsyncAudioSystem,
+    device_id: String,
+    group_id: GroupId,
+) -> ModuleResponse {
+    let levels = audio_system
+        .get_group_channel_volumes(&group_id, &device_id)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    Ok(create_response(ChannelVolumes::new(levels), None))
+}
+
+/// Define os canais de um grupo, respeitando os limites
+///
+/// Retorna os níveis aplicados, que podem ser menores que os pedidos.
+pub async fn handle_set_group_channels(
+    audio_system: &dyn AsyncAudioSystem,
+    limits: &VolumeLimitService,
+    history: &HistoryService,
+    client: &ClientId,
+    request: SetGroupChannelsRequest,
+) -> ModuleResponse {
+    let group_id = GroupId::new(request.group_id);
+    let adjustment = ChannelAdjustment::try_from(request.levels).map_err(|e| anyhow!(e))?;
+    let current = audio_system
+        .get_group_channel_volumes(&group_id, &request.device_id)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let levels = adjustment.resolve(&current).map_err(|e| anyhow!(e))?;
+    let group = find_group(audio_system, &request.device_id, &group_id)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let levels = limits
+        .limit_group_channels(&group, &current, levels)
+        .map_err(|e| anyhow!(e))?;
+    audio_system
+        .set_group_channel_volumes(&group_id, &request.device_id, &levels)
+        .await
+        .map_err(|e| anyhow!(e))?;
+
+    history.record(
+        client,
+        HistoryTarget::GroupChannels {
+            device_id: request.device_id,
+            group_id: group_id.to_string(),
+        },
+        HistoryValue::Levels(current),
+        HistoryValue::Levels(levels.clone()),
+    );
+    Ok(create_response(ChannelVolumes::new(levels), None))
+}
+
+pub async fn handle_move_group(
+    app_routing: &AppRoutingService,
+    history: &HistoryService,
+    client: &ClientId,
+    request: MoveGroupRequest,
+) -> ModuleResponse {
+    app_routing
+        .move_group(
+            &GroupId::new(request.group_id),
+            &request.device_id,
+            &request.target_device_id,
+        )
+        .await
+        .map_err(|e| anyhow!(e))?;
+    history.record_irreversible(client, "move_group");
+    Ok(create_response("Group moved successfully", None))
+}
+
+pub async fn handle_list_routing_preferences(app_routing: &AppRoutingService) -> ModuleResponse {
+    let preferences = app_routing.preferences();
+    let size = preferences.len();
+    Ok(create_response(preferences, Some(size)))
+}
+
+pub async fn handle_set_routing_preference(
+    app_routing: &AppRoutingService,
+    history: &HistoryService,
+    client: &ClientId,
+    request: SetRoutingPreferenceRequest,
+) -> ModuleResponse {
+    let preference = app_routing
+        .set_preference(&request.process_name, &request.device_id)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    history.record_irreversible(client, "set_routing_preference");
+    Ok(create_response(preference, None))
+}
+
+pub async fn handle_remove_routing_preference(
+    app_routing: &AppRoutingService,
+    history: &HistoryService,
+    client: &ClientId,
+    request: RemoveRoutingPreferenceRequest,
+) -> ModuleResponse {
+    let removed = app_routing
+        .remove_preference(&request.process_name)
+        .map_err(|e| anyhow!(e))?;
+    history.record_irreversible(client, "remove_routing_preference");
+    Ok(create_response(removed, None))
+}
+
+pub async fn handle_list_profiles(profiles: &ProfileService) -> ModuleResponse {
+    let list = profiles.list();
+    let size = list.len();
+    Ok(create_response(list, Some(size)))
+}
+
+pub async fn handle_get_profile(profiles: &ProfileService, name: String) -> ModuleResponse {
+    let profile = profiles.get(&name).map_err(|e| anyhow!(e))?;
+    Ok(create_response(profile, None))
+}
+
+pub async fn handle_capture_profile(
+    profiles: &ProfileService,
+    request: ProfileNameRequest,
+) -> ModuleResponse {
+    let profile = profiles
+        .capture(&request.name)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    Ok(create_response(profile, None))
+}
+
+pub async fn handle_delete_profile(
+    profiles: &ProfileService,
+    request: ProfileNameRequest,
+) -> ModuleResponse {
+    profiles.delete(&request.name).map_err(|e| anyhow!(e))?;
+    Ok(create_response("Profile deleted successfully", None))
+}
+
+pub async fn handle_apply_profile(
+    profiles: &ProfileService,
+    history: &HistoryService,
+    client: &ClientId,
+    request: ProfileNameRequest,
+) -> ModuleResponse {
+    let report = profiles
+        .apply(&request.name)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    history.record_irreversible(client, "apply_profile");
+    Ok(create_response(report, None))
+}
+
+/// Exporta todos os perfis como um array JSON simples, no mesmo formato
+/// aceito por `import_profiles`
+pub async fn handle_export_profiles(profiles: &ProfileService) -> ModuleResponse {
+    let json = serde_json::to_string_pretty(&profiles.list())?;
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "application/json")
+        .header(
+            CONTENT_DISPOSITION,
+            "attachment; filename=\"sound_profiles.json\"",
+        )
+        .body(Body::from(json))
+        .map_err(|e| anyhow!(e))
+}
+
+pub async fn handle_import_profiles(
+    profiles: &ProfileService,
+    request: Vec<SoundProfile>,
+) -> ModuleResponse {
+    let count = profiles.import(request).map_err(|e| anyhow!(e))?;
+    Ok(create_response(
+        "Profiles imported successfully",
+        Some(count),
+    ))
+}
+
+pub async fn handle_list_volume_memory(volume_memory: &VolumeMemoryService) -> ModuleResponse {
+    let entries = volume_memory.entries();
+    let size = entries.len();
+    Ok(create_response(entries, Some(size)))
+}
+
+pub async fn handle_set_volume_memory(
+    volume_memory: &VolumeMemoryService,
+    request: SetVolumeMemoryRequest,
+) -> ModuleResponse {
+    let entry = volume_memory
+        .set_enabled(&request.process_name, request.enabled)
+        .map_err(|e| anyhow!(e))?;
+    Ok(create_response(entry, None))
+}
+
+pub async fn handle_forget_volume_memory(
+    volume_memory: &VolumeMemoryService,
+    request: ForgetVolumeMemoryRequest,
+) -> ModuleResponse {
+    let removed = volume_memory
+        .forget(&request.process_name)
+        .map_err(|e| anyhow!(e))?;
+    Ok(create_response(removed, None))
+}
+
+pub async fn handle_list_rules(rules: &RulesService) -> ModuleResponse {
+    let list = rules.list();
+    let size = list.len();
+    Ok(create_response(list, Some(size)))
+}
+
+pub async fn handle_get_rule(rules: &RulesService, name: String) -> ModuleResponse {
+    let rule = rules.get(&name).map_err(|e| anyhow!(e))?;
+    Ok(create_response(rule, None))
+}
+
+pub async fn handle_save_rule(rules: &RulesService, request: AutomationRule) -> ModuleResponse {
+    let rule = rules.save(request).map_err(|e| anyhow!(e))?;
+    Ok(create_response(rule, None))
+}
+
+pub async fn handle_delete_rule(rules: &RulesService, request: RuleNameRequest) -> ModuleResponse {
+    rules.delete(&request.name).map_err(|e| anyhow!(e))?;
+    Ok(create_response("Rule deleted successfully", None))
+}
+
+pub async fn handle_dry_run_rules(
+    rules: &RulesService,
+    request: RuleDryRunRequest,
+) -> ModuleResponse {
+    let evaluations = rules.dry_run(request).await.map_err(|e| anyhow!(e))?;
+    let size = evaluations.len();
+    Ok(create_response(evaluations, Some(size)))
+}
+
+pub async fn handle_list_scheduled_tasks(scheduler: &SchedulerService) -> ModuleResponse {
+    let tasks = scheduler.list();
+    let size = tasks.len();
+    Ok(create_response(tasks, Some(size)))
+}
+
+pub async fn handle_schedule_task(
+    scheduler: &SchedulerService,
+    request: ScheduleTaskRequest,
+) -> ModuleResponse {
+    let task = scheduler.create(request).map_err(|e| anyhow!(e))?;
+    Ok(create_response(task, None))
+}
+
+pub async fn handle_cancel_scheduled_task(
+    scheduler: &SchedulerService,
+    request: ScheduledTaskIdRequest,
+) -> ModuleResponse {
+    scheduler.cancel(request.id).map_err(|e| anyhow!(e))?;
+    Ok(create_response(
+        "Scheduled task cancelled successfully",
+        None,
+    ))
+}
+
+pub async fn handle_get_sleep_timer(sleep_timer: &SleepTimerService) -> ModuleResponse {
+    Ok(create_response(sleep_timer.status(), None))
+}
+
+pub async fn handle_start_sleep_timer(
+    sleep_timer: &SleepTimerService,
+    request: StartSleepTimerRequest,
+) -> ModuleResponse {
+    let status = sleep_timer.start(request).await.map_err(|e| anyhow!(e))?;
+    Ok(create_response(status, None))
+}
+
+pub async fn handle_cancel_sleep_timer(sleep_timer: &SleepTimerService) -> ModuleResponse {
+    let status = sleep_timer.cancel().await.map_err(|e| anyhow!(e))?;
+    Ok(create_response(status, None))
+}
+
+pub async fn handle_get_ducking(ducking: &DuckingService) -> ModuleResponse {
+    Ok(create_response(ducking.status(), None))
+}
+
+pub async fn handle_set_ducking(
+    ducking: &DuckingService,
+    request: DuckingConfig,
+) -> ModuleResponse {
+    let status = ducking.set_config(request).map_err(|e| anyhow!(e))?;
+    Ok(create_response(status, None))
+}
+
+pub async fn handle_get_focus_mode(focus_mode: &FocusModeService) -> ModuleResponse {
+    Ok(create_response(focus_mode.status(), None))
+}
+
+pub async fn handle_start_focus_mode(
+    focus_mode: &FocusModeService,
+    request: StartFocusModeRequest,
+) -> ModuleResponse {
+    let status = focus_mode.start(request).await.map_err(|e| anyhow!(e))?;
+    Ok(create_response(status, None))
+}
+
+pub async fn handle_stop_focus_mode(focus_mode: &FocusModeService) -> ModuleResponse {
+    let status = focus_mode.stop().await.map_err(|e| anyhow!(e))?;
+    Ok(create_response(status, None))
+}
+
+pub async fn handle_get_volume_limits(limits: &VolumeLimitService) -> ModuleResponse {
+    Ok(create_response(limits.limits(), None))
+}
+
+pub async fn handle_set_volume_limits(
+    limits: &VolumeLimitService,
+    request: VolumeLimits,
+) -> ModuleResponse {
+    let limits = limits.set_limits(request).map_err(|e| anyhow!(e))?;
+    Ok(create_response(limits, None))
+}
+
+pub async fn handle_list_volume_links(links: &VolumeLinkService) -> ModuleResponse {
+    let list = links.list();
+    let size = list.len();
+    Ok(create_response(list, Some(size)))
+}
+
+pub async fn handle_save_volume_link(
+    links: &VolumeLinkService,
+    request: VolumeLink,
+) -> ModuleResponse {
+    let link = links.save(request).await.map_err(|e| anyhow!(e))?;
+    Ok(create_response(link, None))
+}
+
+pub async fn handle_delete_volume_link(
+    links: &VolumeLinkService,
+    request: LinkNameRequest,
+) -> ModuleResponse {
+    links.delete(&request.name).await.map_err(|e| anyhow!(e))?;
+    Ok(create_response("Volume link deleted successfully", None))
+}
+
+pub async fn handle_list_history(history: &HistoryService) -> ModuleResponse {
+    let entries = history.list();
+    let size = entries.len();
+    Ok(create_response(entries, Some(size)))
+}
+
+pub async fn handle_undo(history: &HistoryService, client: &ClientId) -> ModuleResponse {
+    let entry = history.undo(client).await.map_err(|e| anyhow!(e))?;
+    Ok(create_response(entry, None))
+}
+
+pub async fn handle_redo(history: &HistoryService, client: &ClientId) -> ModuleResponse {
+    let entry = history.redo(client).await.map_err(|e| anyhow!(e))?;
+    Ok(create_response(entry, None))
+}
+
+pub async fn handle_lookup_cache_stats(lookup_cache: &CachedAudioSystem) -> ModuleResponse {
+    Ok(create_response(lookup_cache.stats(), None))
+}
+
+pub async fn handle_get_state(state: &StateService) -> ModuleResponse {
+    state.refresh().await.map_err(|e| anyhow!(e))?;
+    Ok(create_response(state.snapshot(), None))
+}
+
+pub async fn handle_state_changes(
+    state: &StateService,
+    since: u64,
+    epoch: Option<u64>,
+) -> ModuleResponse {
+    state.refresh().await.map_err(|e| anyhow!(e))?;
+    Ok(create_response(state.changes_since(since, epoch), None))
+}
@@ -0,0 +1,136 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::ws::{Message, WebSocket};
+use futures::{SinkExt, StreamExt};
+use serde::Serialize;
+use tokio::time::{interval, Interval, MissedTickBehavior};
+
+use crate::modules::audio_control::{
+    models::{MeterCommand, MeterError, MeterFrame},
+    platform::async_audio_system::AsyncAudioSystem,
+};
+use crate::modules::core::get_timestamp_millis;
+
+const MIN_RATE_HZ: u32 = 1;
+const MAX_RATE_HZ: u32 = 60;
+
+struct MeterSubscription {
+    device_id: String,
+    include_groups: bool,
+    ticker: Interval,
+}
+
+/// Atende uma conexão WebSocket de medição de picos
+///
+/// Nenhuma leitura é feita até o cliente enviar `subscribe`; a partir daí
+/// quadros são enviados na taxa pedida até `unsubscribe` ou o fim da conexão.
+/// As leituras passam pelo backend assíncrono, sem bloquear o runtime.
+pub async fn handle_meter_socket(socket: WebSocket, audio_system: Arc<dyn AsyncAudioSystem>) {
+    let (mut sender, mut receiver) = socket.split();
+    let mut subscription: Option<MeterSubscription> = None;
+
+    loop {
+        tokio::select! {
+            message = receiver.next() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+
+                match serde_json::from_str::<MeterCommand>(&text) {
+                    Ok(MeterCommand::Subscribe { device_id, rate_hz, include_groups }) => {
+                        subscription = Some(new_subscription(device_id, rate_hz, include_groups));
+                    }
+                    Ok(MeterCommand::Unsubscribe) => subscription = None,
+                    Err(error) => {
+                        let frame = MeterError { error: format!("Invalid meter command: {error}") };
+                        if send_frame(&mut sender, &frame).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+            _ = tick(&mut subscription) => {
+                let Some(active) = subscription.as_ref() else {
+                    continue;
+                };
+
+                let sent = match read_frame(audio_system.as_ref(), active).await {
+                    Ok(frame) => send_frame(&mut sender, &frame).await,
+                    Err(error) => {
+                        // Leitura falhou (ex: dispositivo removido): encerra a inscrição
+                        subscription = None;
+                        send_frame(&mut sender, &MeterError { error }).await
+                    }
+                };
+                if sent.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn new_subscription(device_id: String, rate_hz: u32, include_groups: bool) -> MeterSubscription {
+    let rate_hz = rate_hz.clamp(MIN_RATE_HZ, MAX_RATE_HZ);
+    let mut ticker = interval(Duration::from_secs(1) / rate_hz);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    MeterSubscription {
+        device_id,
+        include_groups,
+        ticker,
+    }
+}
+
+/// Aguarda o próximo tick da inscrição ativa; sem inscrição nunca completa
+async fn tick(subscription: &mut Option<MeterSubscription>) {
+    match subscription {
+        Some(active) => {
+            active.ticker.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+async fn read_frame(
+    audio_system: &dyn AsyncAudioSystem,
+    subscription: &MeterSubscription,
+) -> Result<MeterFrame, String> {
+    let device_peak = audio_system
+        .get_device_peak(&subscription.device_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let group_peaks = if subscription.include_groups {
+        audio_system
+            .get_group_peaks(&subscription.device_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|group| (group.group_id, group.peak))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(MeterFrame {
+        t: get_timestamp_millis(),
+        d: device_peak,
+        g: group_peaks,
+    })
+}
+
+async fn send_frame<S, T>(sender: &mut S, frame: &T) -> Result<(), ()>
+where
+    S: SinkExt<Message> + Unpin,
+    T: Serialize,
+{
+    let json = serde_json::to_string(frame).map_err(|_| ())?;
+    sender
+        .send(Message::Text(json.into()))
+        .await
+        .map_err(|_| ())
+}
@@ -1,37 +1,328 @@
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::modules::audio_control::audio_handlers;
 use crate::modules::audio_control::errors::AudioError;
-use crate::modules::audio_control::models::audio_requests::SetGroupVolumeRequest;
+use crate::modules::audio_control::models::audio_requests::{
+    ForgetVolumeMemoryRequest, LinkNameRequest, ListDevicesQuery, ListSessionsQuery,
+    MoveGroupRequest, ProfileNameRequest, RemoveRoutingPreferenceRequest, RuleDryRunRequest,
+    RuleNameRequest, ScheduleTaskRequest, ScheduledTaskIdRequest, SetApplicationVolumeRequest,
+    SetDeviceChannelsRequest, SetDeviceVolumeRequest, SetGroupChannelsRequest,
+    SetGroupVolumeRequest, SetRoutingPreferenceRequest, SetSessionMuteRequest,
+    SetSessionVolumeRequest, SetVolumeMemoryRequest, StartFocusModeRequest, StartSleepTimerRequest,
+    StateChangesQuery,
+};
+use crate::modules::audio_control::models::{
+    AutomationRule, DuckingConfig, SoundProfile, VolumeLimits, VolumeLink,
+};
 use crate::modules::audio_control::platform::{
-    audio_system_interface::AudioSystemInterface, windows_audio_adapter::WindowsAudioAdapter,
+    async_audio_system::AsyncAudioSystem,
+    audio_system_interface::AudioSystemInterface,
+    blocking_audio_adapter::BlockingAudioAdapter,
+    cached_audio_system::{CachedAudioSystem, LookupCacheConfig},
+    limited_audio_system::LimitedAudioSystem,
+    windows_audio_adapter::WindowsAudioAdapter,
+};
+use crate::modules::audio_control::services::{
+    ActionExecutor, AppRoutingService, DuckingService, FocusModeService, HistoryService, Notifier,
+    ProfileService, RulesService, SchedulerService, SessionMonitor, SleepTimerService,
+    StateService, VolumeCoalescer, VolumeLimitService, VolumeLinkService, VolumeMemoryService,
+    WriteOutcome,
 };
 use crate::modules::audio_control::types::GroupId;
+use crate::modules::audio_control::{audio_handlers, audio_meter_socket, audio_state_socket};
+use crate::modules::core::broadcasting::DEFAULT_REPLAY_CAPACITY;
+use crate::modules::core::executor::{BackendError, BackendExecutorConfig};
+use crate::modules::core::handlers::{handle_event_socket, EventStreamQuery};
+use crate::modules::core::persistence::{default_data_dir, JsonStore};
 use crate::modules::core::response::create_error_response;
 use crate::modules::core::traits::module_handler::{ModuleHandler, ModuleResponse};
+use crate::modules::core::{Broadcaster, ClientId, Clock, SystemClock};
 use anyhow::Error as AnyhowError;
 use async_trait::async_trait;
-use axum::extract::Path;
+use axum::extract::{Path, Query, WebSocketUpgrade};
+use axum::http::{header::IF_NONE_MATCH, HeaderMap};
 use axum::{
-    extract::rejection::JsonRejection,
+    extract::rejection::{JsonRejection, QueryRejection},
     response::Response,
     routing::{get, post},
     Json, Router,
 };
 
+const ROUTING_PREFERENCES_FILE: &str = "routing_preferences.json";
+const PROFILES_FILE: &str = "profiles.json";
+const VOLUME_MEMORY_FILE: &str = "volume_memory.json";
+const RULES_FILE: &str = "rules.json";
+const SCHEDULED_TASKS_FILE: &str = "scheduled_tasks.json";
+const DUCKING_FILE: &str = "ducking.json";
+const VOLUME_LIMITS_FILE: &str = "volume_limits.json";
+const VOLUME_LINKS_FILE: &str = "volume_links.json";
+
+/// Configuração do módulo de áudio
+#[derive(Debug, Clone)]
+pub struct AudioModuleConfig {
+    /// Diretório dos arquivos persistidos; `None` mantém tudo em memória
+    pub data_dir: Option<PathBuf>,
+    /// Intervalo de amostragem do monitor de sessões; `None` desativa o monitor
+    pub monitor_interval: Option<Duration>,
+    /// Horário usado pelas automações
+    pub clock: Arc<dyn Clock>,
+    /// Intervalo mínimo entre duas escritas de volume no mesmo alvo
+    pub volume_write_interval: Duration,
+    /// Threads que atendem as chamadas a um backend síncrono e o tempo
+    /// máximo de espera por cada uma; usado só por `AudioModule::blocking`
+    pub backend: BackendExecutorConfig,
+    /// Validade das listas de dispositivos e dos nomes de processos em cache
+    pub lookup_cache: LookupCacheConfig,
+    /// Tempo sem novas mudanças antes de gravar os volumes lembrados por
+    /// aplicação
+    pub volume_memory_write_delay: Duration,
+    /// Eventos guardados para repetir a clientes que reconectam com
+    /// `last_event_id`
+    pub event_replay: usize,
+}
+
+impl Default for AudioModuleConfig {
+    fn default() -> Self {
+        Self {
+            data_dir: None,
+            monitor_interval: Some(Duration::from_secs(1)),
+            clock: Arc::new(SystemClock),
+            volume_write_interval: Duration::from_millis(50),
+            backend: BackendExecutorConfig::default(),
+            lookup_cache: LookupCacheConfig::default(),
+            volume_memory_write_delay: Duration::from_secs(2),
+            event_replay: DEFAULT_REPLAY_CAPACITY,
+        }
+    }
+}
+
 pub struct AudioModule {
-    audio_system: Arc<dyn AudioSystemInterface>,
+    lookup_cache: Arc<CachedAudioSystem>,
+    backend: Arc<dyn AsyncAudioSystem>,
+    broadcaster: Broadcaster,
+    session_monitor: Arc<SessionMonitor>,
+    app_routing: Arc<AppRoutingService>,
+    profiles: Arc<ProfileService>,
+    volume_memory: Arc<VolumeMemoryService>,
+    executor: Arc<ActionExecutor>,
+    rules: Arc<RulesService>,
+    scheduler: Arc<SchedulerService>,
+    sleep_timer: Arc<SleepTimerService>,
+    ducking: Arc<DuckingService>,
+    focus_mode: Arc<FocusModeService>,
+    history: Arc<HistoryService>,
+    coalescer: Arc<VolumeCoalescer>,
+    volume_limits: Arc<VolumeLimitService>,
+    volume_links: Arc<VolumeLinkService>,
+    state: Arc<StateService>,
+    monitor_interval: Option<Duration>,
 }
 
 impl AudioModule {
-    pub fn new(audio_system: Arc<dyn AudioSystemInterface>) -> Self {
-        Self { audio_system }
+    pub fn new(audio_system: Arc<dyn AsyncAudioSystem>) -> Self {
+        Self::with_config(audio_system, AudioModuleConfig::default())
+    }
+
+    /// Usa um backend síncrono, com as chamadas feitas nas threads descritas
+    /// por `config.backend`
+    pub fn blocking(
+        audio_system: Arc<dyn AudioSystemInterface>,
+        config: AudioModuleConfig,
+    ) -> Self {
+        let audio_system = Arc::new(BlockingAudioAdapter::new(audio_system, config.backend));
+        Self::with_config(audio_system, config)
+    }
+
+    pub fn with_config(audio_system: Arc<dyn AsyncAudioSystem>, config: AudioModuleConfig) -> Self {
+        let lookup_cache = Arc::new(CachedAudioSystem::new(
+            audio_system,
+            config.lookup_cache,
+            Arc::clone(&config.clock),
+        ));
+        let broadcaster = Broadcaster::with_replay(100, config.event_replay);
+        let notifier = Notifier::new(broadcaster.clone());
+        // Os limites leem o backend direto; os serviços escrevem pelos tetos
+        let volume_limits = Arc::new(VolumeLimitService::new(
+            lookup_cache.clone(),
+            notifier.clone(),
+            open_store(&config, VOLUME_LIMITS_FILE),
+            Arc::clone(&config.clock),
+        ));
+        let audio_system: Arc<dyn AsyncAudioSystem> = Arc::new(LimitedAudioSystem::new(
+            lookup_cache.clone(),
+            Arc::clone(&volume_limits),
+        ));
+
+        // O monitor é quem percebe mudanças de dispositivos, então os lê sempre do backend
+        let session_monitor = SessionMonitor::new(
+            Arc::new(lookup_cache.with_live_devices()),
+            broadcaster.clone(),
+        );
+        let app_routing = AppRoutingService::new(
+            Arc::clone(&audio_system),
+            broadcaster.clone(),
+            open_store(&config, ROUTING_PREFERENCES_FILE),
+        );
+        let profiles = Arc::new(ProfileService::new(
+            Arc::clone(&audio_system),
+            open_store(&config, PROFILES_FILE),
+        ));
+        let volume_memory = VolumeMemoryService::new(
+            Arc::clone(&audio_system),
+            open_store(&config, VOLUME_MEMORY_FILE),
+            config.volume_memory_write_delay,
+        );
+        let executor = Arc::new(ActionExecutor::new(
+            Arc::clone(&audio_system),
+            notifier.clone(),
+            Arc::clone(&profiles),
+            Arc::clone(&config.clock),
+        ));
+        let rules = RulesService::new(
+            Arc::clone(&audio_system),
+            Arc::clone(&executor),
+            open_store(&config, RULES_FILE),
+            Arc::clone(&config.clock),
+        );
+        let scheduler = SchedulerService::new(
+            Arc::clone(&executor),
+            broadcaster.clone(),
+            open_store(&config, SCHEDULED_TASKS_FILE),
+            Arc::clone(&config.clock),
+        );
+        let sleep_timer = SleepTimerService::new(
+            Arc::clone(&audio_system),
+            Arc::clone(&executor),
+            broadcaster.clone(),
+            Arc::clone(&config.clock),
+        );
+        let ducking = DuckingService::new(
+            Arc::clone(&audio_system),
+            broadcaster.clone(),
+            open_store(&config, DUCKING_FILE),
+            Arc::clone(&config.clock),
+        );
+        let focus_mode = FocusModeService::new(
+            Arc::clone(&audio_system),
+            broadcaster.clone(),
+            notifier,
+            Arc::clone(&config.clock),
+        );
+        let history = HistoryService::new(Arc::clone(&audio_system), Arc::clone(&config.clock));
+        let volume_links = VolumeLinkService::new(
+            Arc::clone(&audio_system),
+            open_store(&config, VOLUME_LINKS_FILE),
+        );
+
+        let state = StateService::new(Arc::clone(&audio_system));
+
+        // Os handlers já aplicam os limites em cada pedido, então escrevem
+        // direto no backend, sem passar de novo pelos tetos
+        let backend: Arc<dyn AsyncAudioSystem> = lookup_cache.clone();
+
+        Self {
+            lookup_cache,
+            backend,
+            broadcaster,
+            session_monitor: Arc::new(session_monitor),
+            app_routing: Arc::new(app_routing),
+            profiles,
+            volume_memory: Arc::new(volume_memory),
+            executor,
+            rules: Arc::new(rules),
+            scheduler: Arc::new(scheduler),
+            sleep_timer: Arc::new(sleep_timer),
+            ducking: Arc::new(ducking),
+            focus_mode: Arc::new(focus_mode),
+            history: Arc::new(history),
+            coalescer: Arc::new(VolumeCoalescer::new(config.volume_write_interval)),
+            volume_limits,
+            volume_links: Arc::new(volume_links),
+            state: Arc::new(state),
+            monitor_interval: config.monitor_interval,
+        }
+    }
+
+    /// Monitor de sessões, exposto para que os testes disparem leituras
+    #[cfg(test)]
+    pub(crate) fn session_monitor(&self) -> &SessionMonitor {
+        &self.session_monitor
+    }
+
+    /// Motor de regras, exposto para que os testes avaliem um evento sem
+    /// esperar pela tarefa em segundo plano
+    #[cfg(test)]
+    pub(crate) fn rules(&self) -> &RulesService {
+        &self.rules
+    }
+
+    /// Agendador, exposto para que os testes rodem as tarefas vencidas depois
+    /// de avançar o `ManualClock`
+    #[cfg(test)]
+    pub(crate) fn scheduler(&self) -> &SchedulerService {
+        &self.scheduler
+    }
+
+    /// Timer de sono, exposto para que os testes apliquem o fade e o fim do
+    /// timer no horário do `ManualClock`
+    #[cfg(test)]
+    pub(crate) fn sleep_timer(&self) -> &SleepTimerService {
+        &self.sleep_timer
+    }
+
+    /// Ducking, exposto para que os testes releiam as sessões de comunicação
+    /// sem esperar pela amostragem
+    #[cfg(test)]
+    pub(crate) fn ducking(&self) -> &DuckingService {
+        &self.ducking
+    }
+
+    /// Modo foco, exposto para que os testes verifiquem o timer de desligamento
+    /// depois de avançar o `ManualClock`
+    #[cfg(test)]
+    pub(crate) fn focus_mode(&self) -> &FocusModeService {
+        &self.focus_mode
+    }
+
+    /// Limites de volume, expostos para que os testes corrijam na hora volumes
+    /// alterados fora do servidor
+    #[cfg(test)]
+    pub(crate) fn volume_limits(&self) -> &VolumeLimitService {
+        &self.volume_limits
+    }
+
+    /// Links de volume, expostos para que os testes propaguem uma mudança
+    /// externa sem esperar pela amostragem
+    #[cfg(test)]
+    pub(crate) fn volume_links(&self) -> &VolumeLinkService {
+        &self.volume_links
+    }
+
+    /// Executor das automações, exposto para que os testes avancem os fades
+    #[cfg(test)]
+    pub(crate) fn executor(&self) -> &ActionExecutor {
+        &self.executor
     }
 }
 
 impl Default for AudioModule {
     fn default() -> Self {
-        Self::new(Arc::new(WindowsAudioAdapter::new()))
+        let config = AudioModuleConfig {
+            data_dir: Some(default_data_dir()),
+            ..AudioModuleConfig::default()
+        };
+        Self::blocking(Arc::new(WindowsAudioAdapter::new()), config)
+    }
+}
+
+fn open_store<T>(config: &AudioModuleConfig, file_name: &str) -> JsonStore<T>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + Default,
+{
+    match &config.data_dir {
+        Some(data_dir) => JsonStore::open(data_dir.join(file_name)),
+        None => JsonStore::in_memory(),
     }
 }
 
@@ -42,6 +333,61 @@ impl ModuleHandler for AudioModule {
         let for_list_device = Arc::clone(&self);
         let for_list_session = Arc::clone(&self);
         let for_set_group_volume = Arc::clone(&self);
+        let for_set_session_volume = Arc::clone(&self);
+        let for_set_session_mute = Arc::clone(&self);
+        let for_get_device_channels = Arc::clone(&self);
+        let for_set_device_channels = Arc::clone(&self);
+        let for_get_group_channels = Arc::clone(&self);
+        let for_set_group_channels = Arc::clone(&self);
+        let for_meters = Arc::clone(&self);
+        let for_move_group = Arc::clone(&self);
+        let for_routing_preferences = Arc::clone(&self);
+        let for_set_routing_preference = Arc::clone(&self);
+        let for_remove_routing_preference = Arc::clone(&self);
+        let for_events = Arc::clone(&self);
+        let for_group_icon = Arc::clone(&self);
+        let for_list_all_sessions = Arc::clone(&self);
+        let for_list_applications = Arc::clone(&self);
+        let for_set_application_volume = Arc::clone(&self);
+        let for_list_profiles = Arc::clone(&self);
+        let for_get_profile = Arc::clone(&self);
+        let for_capture_profile = Arc::clone(&self);
+        let for_delete_profile = Arc::clone(&self);
+        let for_apply_profile = Arc::clone(&self);
+        let for_export_profiles = Arc::clone(&self);
+        let for_import_profiles = Arc::clone(&self);
+        let for_volume_memory = Arc::clone(&self);
+        let for_set_volume_memory = Arc::clone(&self);
+        let for_forget_volume_memory = Arc::clone(&self);
+        let for_list_rules = Arc::clone(&self);
+        let for_get_rule = Arc::clone(&self);
+        let for_save_rule = Arc::clone(&self);
+        let for_delete_rule = Arc::clone(&self);
+        let for_dry_run_rules = Arc::clone(&self);
+        let for_scheduled_tasks = Arc::clone(&self);
+        let for_schedule_task = Arc::clone(&self);
+        let for_cancel_scheduled_task = Arc::clone(&self);
+        let for_sleep_timer = Arc::clone(&self);
+        let for_start_sleep_timer = Arc::clone(&self);
+        let for_cancel_sleep_timer = Arc::clone(&self);
+        let for_ducking = Arc::clone(&self);
+        let for_set_ducking = Arc::clone(&self);
+        let for_focus_mode = Arc::clone(&self);
+        let for_start_focus_mode = Arc::clone(&self);
+        let for_stop_focus_mode = Arc::clone(&self);
+        let for_set_device_volume = Arc::clone(&self);
+        let for_volume_limits = Arc::clone(&self);
+        let for_set_volume_limits = Arc::clone(&self);
+        let for_volume_links = Arc::clone(&self);
+        let for_save_volume_link = Arc::clone(&self);
+        let for_delete_volume_link = Arc::clone(&self);
+        let for_history = Arc::clone(&self);
+        let for_undo = Arc::clone(&self);
+        let for_redo = Arc::clone(&self);
+        let for_lookup_cache = Arc::clone(&self);
+        let for_state = Arc::clone(&self);
+        let for_state_changes = Arc::clone(&self);
+        let for_state_stream = Arc::clone(&self);
 
         Router::new()
             .route(
@@ -51,56 +397,266 @@ impl ModuleHandler for AudioModule {
                     async move {
                         handle_audio_response(
                             "get_volume",
-                            audio_handlers::handle_get_volume(module.audio_system.as_ref()),
+                            audio_handlers::handle_get_volume(module.backend.as_ref()).await,
                         )
                     }
                 }),
             )
             .route(
                 "/list_devices",
+                get(
+                    move |query: Result<Query<ListDevicesQuery>, QueryRejection>| {
+                        let module = Arc::clone(&for_list_device);
+                        async move {
+                            match query {
+                                Ok(Query(query)) => handle_audio_response(
+                                    "list_devices",
+                                    audio_handlers::handle_list_devices(
+                                        module.backend.as_ref(),
+                                        query.include_inactive,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "list_devices",
+                                    Err(query_rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/list_session/{device_id}",
+                get(
+                    move |Path(device_id): Path<String>,
+                          query: Result<Query<ListSessionsQuery>, QueryRejection>| {
+                        let module = Arc::clone(&for_list_session);
+                        async move {
+                            match query {
+                                Ok(Query(query)) => handle_audio_response(
+                                    "list_session",
+                                    audio_handlers::handle_list_sessions(
+                                        module.backend.as_ref(),
+                                        device_id,
+                                        query.expand,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "list_session",
+                                    Err(query_rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/list_sessions",
+                get(
+                    move |query: Result<Query<ListSessionsQuery>, QueryRejection>| {
+                        let module = Arc::clone(&for_list_all_sessions);
+                        async move {
+                            match query {
+                                Ok(Query(query)) => handle_audio_response(
+                                    "list_sessions",
+                                    audio_handlers::handle_list_all_sessions(
+                                        module.backend.as_ref(),
+                                        query.expand,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "list_sessions",
+                                    Err(query_rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/list_applications",
                 get(move || {
-                    let module = Arc::clone(&for_list_device);
+                    let module = Arc::clone(&for_list_applications);
                     async move {
                         handle_audio_response(
-                            "list_devices",
-                            audio_handlers::handle_list_devices(module.audio_system.as_ref()),
+                            "list_applications",
+                            audio_handlers::handle_list_applications(module.backend.as_ref()).await,
                         )
                     }
                 }),
             )
             .route(
-                "/list_session/{device_id}",
-                get(move |Path(device_id): Path<String>| {
-                    let module = Arc::clone(&for_list_session);
+                "/set_application_volume",
+                post(
+                    move |client: ClientId, request: Result<Json<SetApplicationVolumeRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_set_application_volume);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => {
+                                    let key = format!("application:{}", request.process_name);
+                                    let writer = Arc::clone(&module);
+                                    let outcome = module
+                                        .coalescer
+                                        .submit(key, move || async move {
+                                            audio_handlers::handle_set_application_volume(
+                                                writer.backend.as_ref(),
+                                                &writer.volume_limits,
+                                                &writer.volume_links,
+                                                &writer.history,
+                                                &client,
+                                                request,
+                                            )
+                                            .await
+                                        })
+                                        .await;
+                                    handle_audio_response(
+                                        "set_application_volume",
+                                        coalesced_response(outcome),
+                                    )
+                                }
+                                Err(rejection) => handle_audio_response(
+                                    "set_application_volume",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/sessions/{group_id}/icon",
+                get(move |Path(group_id): Path<String>, headers: HeaderMap| {
+                    let module = Arc::clone(&for_group_icon);
                     async move {
+                        let if_none_match = headers
+                            .get(IF_NONE_MATCH)
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_string);
                         handle_audio_response(
-                            "list_session",
-                            audio_handlers::handle_list_sessions(
-                                module.audio_system.as_ref(),
-                                device_id,
-                            ),
+                            "group_icon",
+                            audio_handlers::handle_get_group_icon(
+                                module.backend.as_ref(),
+                                GroupId::new(group_id),
+                                if_none_match.as_deref(),
+                            )
+                            .await,
                         )
                     }
                 }),
             )
+            .route(
+                "/set_device_volume",
+                post(
+                    move |client: ClientId, request: Result<Json<SetDeviceVolumeRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_set_device_volume);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => {
+                                    let key = format!("device:{}", request.device_id);
+                                    let writer = Arc::clone(&module);
+                                    let outcome = module
+                                        .coalescer
+                                        .submit(key, move || async move {
+                                            audio_handlers::handle_set_device_volume(
+                                                writer.backend.as_ref(),
+                                                &writer.volume_limits,
+                                                &writer.volume_links,
+                                                &writer.history,
+                                                &client,
+                                                request,
+                                            )
+                                            .await
+                                        })
+                                        .await;
+                                    handle_audio_response(
+                                        "set_device_volume",
+                                        coalesced_response(outcome),
+                                    )
+                                }
+                                Err(rejection) => handle_audio_response(
+                                    "set_device_volume",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
             .route(
                 "/set_group_volume",
                 post(
-                    move |request: Result<Json<SetGroupVolumeRequest>, JsonRejection>| {
+                    move |client: ClientId, request: Result<Json<SetGroupVolumeRequest>, JsonRejection>| {
                         let module = Arc::clone(&for_set_group_volume);
                         async move {
                             match request {
-                                Ok(Json(request)) => handle_audio_response(
+                                Ok(Json(request)) => {
+                                    let key = format!(
+                                        "group:{}/{}", request.device_id, request.group_id
+                                    );
+                                    let writer = Arc::clone(&module);
+                                    let outcome = module
+                                        .coalescer
+                                        .submit(key, move || async move {
+                                            audio_handlers::handle_set_group_volume(
+                                                writer.backend.as_ref(),
+                                                &writer.volume_limits,
+                                                &writer.volume_links,
+                                                &writer.history,
+                                                &client,
+                                                request,
+                                            )
+                                            .await
+                                        })
+                                        .await;
+                                    handle_audio_response(
+                                        "set_group_volume",
+                                        coalesced_response(outcome),
+                                    )
+                                }
+                                Err(rejection) => handle_audio_response(
                                     "set_group_volume",
-                                    audio_handlers::handle_set_group_volume(
-                                        module.audio_system.as_ref(),
-                                        request.device_id,
-                                        GroupId::new(request.group_id),
-                                        request.volume.into(),
-                                    ),
+                                    Err(rejection_to_anyhow(rejection)),
                                 ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/set_session_volume",
+                post(
+                    move |client: ClientId, request: Result<Json<SetSessionVolumeRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_set_session_volume);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => {
+                                    let key = format!(
+                                        "session:{}/{}", request.device_id, request.session_id
+                                    );
+                                    let writer = Arc::clone(&module);
+                                    let outcome = module
+                                        .coalescer
+                                        .submit(key, move || async move {
+                                            audio_handlers::handle_set_session_volume(
+                                                writer.backend.as_ref(),
+                                                &writer.volume_limits,
+                                                &writer.history,
+                                                &client,
+                                                request,
+                                            )
+                                            .await
+                                        })
+                                        .await;
+                                    handle_audio_response(
+                                        "set_session_volume",
+                                        coalesced_response(outcome),
+                                    )
+                                }
                                 Err(rejection) => handle_audio_response(
-                                    "set_group_volume",
+                                    "set_session_volume",
                                     Err(rejection_to_anyhow(rejection)),
                                 ),
                             }
@@ -108,35 +664,1037 @@ impl ModuleHandler for AudioModule {
                     },
                 ),
             )
-    }
-}
-
-fn handle_audio_response(operation: &str, result: ModuleResponse) -> Response {
-    match result {
-        Ok(response) => response,
-        Err(error) => {
-            let (status_code, details) = AudioError::error_response_from_anyhow(&error);
-            let message = match error.downcast_ref::<AudioError>() {
-                Some(AudioError::DeviceNotFound { .. }) => "Device not found".to_string(),
-                Some(AudioError::InvalidRequestBody { message }) => message.clone(),
-                Some(AudioError::InvalidDeviceId) => "Invalid device ID".to_string(),
-                Some(AudioError::NoSessionsFound) => "No sessions found".to_string(),
-                _ => format!("Failed to handle audio operation '{operation}'"),
-            };
-
-            tracing::error!("Audio operation '{}' failed: {}", operation, error);
-            create_error_response(status_code, &message, details)
-        }
-    }
-}
-
-fn rejection_to_anyhow(rejection: JsonRejection) -> AnyhowError {
-    let body_text = rejection.body_text();
-    let message = if body_text.contains("Volume must be between 0.0 and 100.0") {
-        "Volume must be between 0.0 and 100.0".to_string()
-    } else {
-        format!("Invalid request body: {}", body_text)
-    };
-
-    AnyhowError::new(AudioError::InvalidRequestBody { message })
+            .route(
+                "/set_session_mute",
+                post(
+                    move |client: ClientId, request: Result<Json<SetSessionMuteRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_set_session_mute);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "set_session_mute",
+                                    audio_handlers::handle_set_session_mute(
+                                        module.backend.as_ref(),
+                                        &module.history,
+                                        &client,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "set_session_mute",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/get_device_channels/{device_id}",
+                get(move |Path(device_id): Path<String>| {
+                    let module = Arc::clone(&for_get_device_channels);
+                    async move {
+                        handle_audio_response(
+                            "get_device_channels",
+                            audio_handlers::handle_get_device_channels(
+                                module.backend.as_ref(),
+                                device_id,
+                            )
+                            .await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/set_device_channels",
+                post(
+                    move |client: ClientId, request: Result<Json<SetDeviceChannelsRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_set_device_channels);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "set_device_channels",
+                                    audio_handlers::handle_set_device_channels(
+                                        module.backend.as_ref(),
+                                        &module.volume_limits,
+                                        &module.history,
+                                        &client,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "set_device_channels",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/get_group_channels/{device_id}/{group_id}",
+                get(move |Path((device_id, group_id)): Path<(String, String)>| {
+                    let module = Arc::clone(&for_get_group_channels);
+                    async move {
+                        handle_audio_response(
+                            "get_group_channels",
+                            audio_handlers::handle_get_group_channels(
+                                module.backend.as_ref(),
+                                device_id,
+                                GroupId::new(group_id),
+                            )
+                            .await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/set_group_channels",
+                post(
+                    move |client: ClientId, request: Result<Json<SetGroupChannelsRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_set_group_channels);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "set_group_channels",
+                                    audio_handlers::handle_set_group_channels(
+                                        module.backend.as_ref(),
+                                        &module.volume_limits,
+                                        &module.history,
+                                        &client,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "set_group_channels",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/meters",
+                get(move |ws: WebSocketUpgrade| {
+                    let module = Arc::clone(&for_meters);
+                    async move {
+                        let backend = module.backend.clone();
+                        ws.on_upgrade(move |socket| {
+                            audio_meter_socket::handle_meter_socket(socket, backend)
+                        })
+                    }
+                }),
+            )
+            .route(
+                "/move_group",
+                post(
+                    move |client: ClientId, request: Result<Json<MoveGroupRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_move_group);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "move_group",
+                                    audio_handlers::handle_move_group(
+                                        &module.app_routing,
+                                        &module.history,
+                                        &client,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "move_group",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/routing_preferences",
+                get(move || {
+                    let module = Arc::clone(&for_routing_preferences);
+                    async move {
+                        handle_audio_response(
+                            "routing_preferences",
+                            audio_handlers::handle_list_routing_preferences(
+                                &module.app_routing,
+                            )
+                            .await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/set_routing_preference",
+                post(
+                    move |client: ClientId, request: Result<Json<SetRoutingPreferenceRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_set_routing_preference);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "set_routing_preference",
+                                    audio_handlers::handle_set_routing_preference(
+                                        &module.app_routing,
+                                        &module.history,
+                                        &client,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "set_routing_preference",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/remove_routing_preference",
+                post(
+                    move |client: ClientId, request: Result<Json<RemoveRoutingPreferenceRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_remove_routing_preference);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "remove_routing_preference",
+                                    audio_handlers::handle_remove_routing_preference(
+                                        &module.app_routing,
+                                        &module.history,
+                                        &client,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "remove_routing_preference",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/profiles",
+                get(move || {
+                    let module = Arc::clone(&for_list_profiles);
+                    async move {
+                        handle_audio_response(
+                            "profiles",
+                            audio_handlers::handle_list_profiles(&module.profiles).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/profiles/{name}",
+                get(move |Path(name): Path<String>| {
+                    let module = Arc::clone(&for_get_profile);
+                    async move {
+                        handle_audio_response(
+                            "get_profile",
+                            audio_handlers::handle_get_profile(&module.profiles, name).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/capture_profile",
+                post(
+                    move |request: Result<Json<ProfileNameRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_capture_profile);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "capture_profile",
+                                    audio_handlers::handle_capture_profile(
+                                        &module.profiles,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "capture_profile",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/delete_profile",
+                post(
+                    move |request: Result<Json<ProfileNameRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_delete_profile);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "delete_profile",
+                                    audio_handlers::handle_delete_profile(
+                                        &module.profiles,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "delete_profile",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/apply_profile",
+                post(
+                    move |client: ClientId, request: Result<Json<ProfileNameRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_apply_profile);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "apply_profile",
+                                    audio_handlers::handle_apply_profile(
+                                        &module.profiles,
+                                        &module.history,
+                                        &client,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "apply_profile",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/export_profiles",
+                get(move || {
+                    let module = Arc::clone(&for_export_profiles);
+                    async move {
+                        handle_audio_response(
+                            "export_profiles",
+                            audio_handlers::handle_export_profiles(&module.profiles).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/import_profiles",
+                post(
+                    move |request: Result<Json<Vec<SoundProfile>>, JsonRejection>| {
+                        let module = Arc::clone(&for_import_profiles);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "import_profiles",
+                                    audio_handlers::handle_import_profiles(
+                                        &module.profiles,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "import_profiles",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/volume_memory",
+                get(move || {
+                    let module = Arc::clone(&for_volume_memory);
+                    async move {
+                        handle_audio_response(
+                            "volume_memory",
+                            audio_handlers::handle_list_volume_memory(&module.volume_memory).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/set_volume_memory",
+                post(
+                    move |request: Result<Json<SetVolumeMemoryRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_set_volume_memory);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "set_volume_memory",
+                                    audio_handlers::handle_set_volume_memory(
+                                        &module.volume_memory,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "set_volume_memory",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/forget_volume_memory",
+                post(
+                    move |request: Result<Json<ForgetVolumeMemoryRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_forget_volume_memory);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "forget_volume_memory",
+                                    audio_handlers::handle_forget_volume_memory(
+                                        &module.volume_memory,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "forget_volume_memory",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/rules",
+                get(move || {
+                    let module = Arc::clone(&for_list_rules);
+                    async move {
+                        handle_audio_response(
+                            "rules",
+                            audio_handlers::handle_list_rules(&module.rules).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/rules/{name}",
+                get(move |Path(name): Path<String>| {
+                    let module = Arc::clone(&for_get_rule);
+                    async move {
+                        handle_audio_response(
+                            "get_rule",
+                            audio_handlers::handle_get_rule(&module.rules, name).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/save_rule",
+                post(
+                    move |request: Result<Json<AutomationRule>, JsonRejection>| {
+                        let module = Arc::clone(&for_save_rule);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "save_rule",
+                                    audio_handlers::handle_save_rule(
+                                        &module.rules,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "save_rule",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/delete_rule",
+                post(
+                    move |request: Result<Json<RuleNameRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_delete_rule);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "delete_rule",
+                                    audio_handlers::handle_delete_rule(
+                                        &module.rules,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "delete_rule",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/dry_run_rules",
+                post(
+                    move |request: Result<Json<RuleDryRunRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_dry_run_rules);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "dry_run_rules",
+                                    audio_handlers::handle_dry_run_rules(
+                                        &module.rules,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "dry_run_rules",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/scheduled_tasks",
+                get(move || {
+                    let module = Arc::clone(&for_scheduled_tasks);
+                    async move {
+                        handle_audio_response(
+                            "scheduled_tasks",
+                            audio_handlers::handle_list_scheduled_tasks(&module.scheduler).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/schedule_task",
+                post(
+                    move |request: Result<Json<ScheduleTaskRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_schedule_task);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "schedule_task",
+                                    audio_handlers::handle_schedule_task(
+                                        &module.scheduler,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "schedule_task",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/cancel_scheduled_task",
+                post(
+                    move |request: Result<Json<ScheduledTaskIdRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_cancel_scheduled_task);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "cancel_scheduled_task",
+                                    audio_handlers::handle_cancel_scheduled_task(
+                                        &module.scheduler,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "cancel_scheduled_task",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/sleep_timer",
+                get(move || {
+                    let module = Arc::clone(&for_sleep_timer);
+                    async move {
+                        handle_audio_response(
+                            "sleep_timer",
+                            audio_handlers::handle_get_sleep_timer(&module.sleep_timer).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/start_sleep_timer",
+                post(
+                    move |request: Result<Json<StartSleepTimerRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_start_sleep_timer);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "start_sleep_timer",
+                                    audio_handlers::handle_start_sleep_timer(
+                                        &module.sleep_timer,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "start_sleep_timer",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/cancel_sleep_timer",
+                post(move || {
+                    let module = Arc::clone(&for_cancel_sleep_timer);
+                    async move {
+                        handle_audio_response(
+                            "cancel_sleep_timer",
+                            audio_handlers::handle_cancel_sleep_timer(
+                                &module.sleep_timer,
+                            )
+                            .await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/ducking",
+                get(move || {
+                    let module = Arc::clone(&for_ducking);
+                    async move {
+                        handle_audio_response(
+                            "ducking",
+                            audio_handlers::handle_get_ducking(&module.ducking).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/set_ducking",
+                post(
+                    move |request: Result<Json<DuckingConfig>, JsonRejection>| {
+                        let module = Arc::clone(&for_set_ducking);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "set_ducking",
+                                    audio_handlers::handle_set_ducking(
+                                        &module.ducking,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "set_ducking",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/focus_mode",
+                get(move || {
+                    let module = Arc::clone(&for_focus_mode);
+                    async move {
+                        handle_audio_response(
+                            "focus_mode",
+                            audio_handlers::handle_get_focus_mode(&module.focus_mode).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/start_focus_mode",
+                post(
+                    move |request: Result<Json<StartFocusModeRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_start_focus_mode);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "start_focus_mode",
+                                    audio_handlers::handle_start_focus_mode(
+                                        &module.focus_mode,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "start_focus_mode",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/stop_focus_mode",
+                post(move || {
+                    let module = Arc::clone(&for_stop_focus_mode);
+                    async move {
+                        handle_audio_response(
+                            "stop_focus_mode",
+                            audio_handlers::handle_stop_focus_mode(
+                                &module.focus_mode,
+                            )
+                            .await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/volume_limits",
+                get(move || {
+                    let module = Arc::clone(&for_volume_limits);
+                    async move {
+                        handle_audio_response(
+                            "volume_limits",
+                            audio_handlers::handle_get_volume_limits(&module.volume_limits).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/set_volume_limits",
+                post(
+                    move |request: Result<Json<VolumeLimits>, JsonRejection>| {
+                        let module = Arc::clone(&for_set_volume_limits);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "set_volume_limits",
+                                    audio_handlers::handle_set_volume_limits(
+                                        &module.volume_limits,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "set_volume_limits",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/volume_links",
+                get(move || {
+                    let module = Arc::clone(&for_volume_links);
+                    async move {
+                        handle_audio_response(
+                            "volume_links",
+                            audio_handlers::handle_list_volume_links(&module.volume_links).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/save_volume_link",
+                post(
+                    move |request: Result<Json<VolumeLink>, JsonRejection>| {
+                        let module = Arc::clone(&for_save_volume_link);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "save_volume_link",
+                                    audio_handlers::handle_save_volume_link(
+                                        &module.volume_links,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "save_volume_link",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/delete_volume_link",
+                post(
+                    move |request: Result<Json<LinkNameRequest>, JsonRejection>| {
+                        let module = Arc::clone(&for_delete_volume_link);
+                        async move {
+                            match request {
+                                Ok(Json(request)) => handle_audio_response(
+                                    "delete_volume_link",
+                                    audio_handlers::handle_delete_volume_link(
+                                        &module.volume_links,
+                                        request,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "delete_volume_link",
+                                    Err(rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/history",
+                get(move || {
+                    let module = Arc::clone(&for_history);
+                    async move {
+                        handle_audio_response(
+                            "history",
+                            audio_handlers::handle_list_history(&module.history).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/undo",
+                post(move |client: ClientId| {
+                    let module = Arc::clone(&for_undo);
+                    async move {
+                        handle_audio_response(
+                            "undo",
+                            audio_handlers::handle_undo(
+                                &module.history,
+                                &client,
+                            )
+                            .await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/redo",
+                post(move |client: ClientId| {
+                    let module = Arc::clone(&for_redo);
+                    async move {
+                        handle_audio_response(
+                            "redo",
+                            audio_handlers::handle_redo(
+                                &module.history,
+                                &client,
+                            )
+                            .await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/lookup_cache",
+                get(move || {
+                    let module = Arc::clone(&for_lookup_cache);
+                    async move {
+                        handle_audio_response(
+                            "lookup_cache",
+                            audio_handlers::handle_lookup_cache_stats(&module.lookup_cache).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/state",
+                get(move || {
+                    let module = Arc::clone(&for_state);
+                    async move {
+                        handle_audio_response(
+                            "state",
+                            audio_handlers::handle_get_state(&module.state).await,
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/state/changes",
+                get(
+                    move |query: Result<Query<StateChangesQuery>, QueryRejection>| {
+                        let module = Arc::clone(&for_state_changes);
+                        async move {
+                            match query {
+                                Ok(Query(query)) => handle_audio_response(
+                                    "state_changes",
+                                    audio_handlers::handle_state_changes(
+                                        &module.state,
+                                        query.since,
+                                        query.epoch,
+                                    )
+                                    .await,
+                                ),
+                                Err(rejection) => handle_audio_response(
+                                    "state_changes",
+                                    Err(query_rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/state/stream",
+                get(move |ws: WebSocketUpgrade| {
+                    let module = Arc::clone(&for_state_stream);
+                    async move {
+                        let state = Arc::clone(&module.state);
+                        ws.on_upgrade(move |socket| {
+                            audio_state_socket::handle_state_socket(socket, state)
+                        })
+                    }
+                }),
+            )
+            .route(
+                "/events",
+                get(
+                    move |ws: WebSocketUpgrade,
+                          query: Result<Query<EventStreamQuery>, QueryRejection>| {
+                        let module = Arc::clone(&for_events);
+                        async move {
+                            match query {
+                                Ok(Query(query)) => {
+                                    let broadcaster = module.broadcaster.clone();
+                                    ws.on_upgrade(move |socket| {
+                                        handle_event_socket(
+                                            socket,
+                                            broadcaster,
+                                            query.last_event_id,
+                                        )
+                                    })
+                                }
+                                Err(rejection) => handle_audio_response(
+                                    "events",
+                                    Err(query_rejection_to_anyhow(rejection)),
+                                ),
+                            }
+                        }
+                    },
+                ),
+            )
+    }
+
+    fn start_background_tasks(self: Arc<Self>) {
+        // O receiver é criado aqui para não perder eventos do primeiro ciclo
+        let cache_events = self.broadcaster.subscribe();
+        tokio::spawn(Arc::clone(&self.lookup_cache).run(cache_events));
+        let routing_events = self.broadcaster.subscribe();
+        tokio::spawn(Arc::clone(&self.app_routing).run(routing_events));
+        let profile_events = self.broadcaster.subscribe();
+        tokio::spawn(Arc::clone(&self.profiles).run(profile_events));
+        let memory_events = self.broadcaster.subscribe();
+        tokio::spawn(Arc::clone(&self.volume_memory).run(memory_events));
+        let rule_events = self.broadcaster.subscribe();
+        tokio::spawn(Arc::clone(&self.rules).run(rule_events));
+        tokio::spawn(Arc::clone(&self.scheduler).run());
+        tokio::spawn(Arc::clone(&self.executor).run());
+        tokio::spawn(Arc::clone(&self.sleep_timer).run());
+        let ducking_events = self.broadcaster.subscribe();
+        tokio::spawn(Arc::clone(&self.ducking).run(ducking_events));
+        tokio::spawn(Arc::clone(&self.focus_mode).run());
+        tokio::spawn(Arc::clone(&self.volume_limits).run());
+        tokio::spawn(Arc::clone(&self.volume_links).run());
+
+        if let Some(interval) = self.monitor_interval {
+            tokio::spawn(Arc::clone(&self.session_monitor).run(interval));
+            tokio::spawn(Arc::clone(&self.state).run(interval));
+        }
+    }
+}
+
+/// Responde na hora aos pedidos de volume juntados a uma escrita em andamento
+fn coalesced_response(outcome: WriteOutcome<ModuleResponse>) -> ModuleResponse {
+    match outcome {
+        WriteOutcome::Applied(result) => result,
+        WriteOutcome::Coalesced => audio_handlers::handle_coalesced_volume_write(),
+    }
+}
+
+fn handle_audio_response(operation: &str, result: ModuleResponse) -> Response {
+    match result {
+        Ok(response) => response,
+        Err(error) => {
+            let (status_code, details) = AudioError::error_response_from_anyhow(&error);
+            let message = match error.downcast_ref::<AudioError>() {
+                Some(AudioError::DeviceNotFound { .. }) => "Device not found".to_string(),
+                Some(AudioError::InvalidRequestBody { message }) => message.clone(),
+                Some(AudioError::InvalidDeviceId) => "Invalid device ID".to_string(),
+                Some(AudioError::NoSessionsFound) => "No sessions found".to_string(),
+                Some(AudioError::SessionNotFound { .. }) => "Session not found".to_string(),
+                Some(AudioError::IconNotFound { .. }) => "Icon not found".to_string(),
+                Some(AudioError::ApplicationNotFound { .. }) => "Application not found".to_string(),
+                Some(AudioError::ProfileNotFound { .. }) => "Profile not found".to_string(),
+                Some(AudioError::RuleNotFound { .. }) => "Rule not found".to_string(),
+                Some(AudioError::ScheduledTaskNotFound { .. }) => {
+                    "Scheduled task not found".to_string()
+                }
+                Some(AudioError::LinkNotFound { .. }) => "Volume link not found".to_string(),
+                Some(AudioError::NoSleepTimer) => "No sleep timer running".to_string(),
+                Some(AudioError::FocusModeInactive) => "Focus mode is not active".to_string(),
+                Some(AudioError::NothingToUndo) => "Nothing to undo".to_string(),
+                Some(AudioError::NothingToRedo) => "Nothing to redo".to_string(),
+                Some(AudioError::NotUndoable { .. }) => "Change cannot be undone".to_string(),
+                Some(AudioError::VolumeLimitExceeded { .. }) => "Volume limit exceeded".to_string(),
+                Some(AudioError::ChannelCountMismatch { .. }) => {
+                    "Channel count mismatch".to_string()
+                }
+                Some(AudioError::Backend(BackendError::Timeout(_))) => {
+                    "Audio backend timed out".to_string()
+                }
+                _ => format!("Failed to handle audio operation '{operation}'"),
+            };
+
+            tracing::error!("Audio operation '{}' failed: {}", operation, error);
+            create_error_response(status_code, &message, details)
+        }
+    }
+}
+
+/// Mensagens de validação dos tipos de request expostas diretamente ao cliente
+const VALIDATION_MESSAGES: &[&str] = &[
+    "Volume must be between 0.0 and 100.0",
+    "Balance must be between -1.0 and 1.0",
+];
+
+fn rejection_to_anyhow(rejection: JsonRejection) -> AnyhowError {
+    let body_text = rejection.body_text();
+    let message = VALIDATION_MESSAGES
+        .iter()
+        .find(|validation| body_text.contains(*validation))
+        .map(|validation| validation.to_string())
+        .unwrap_or_else(|| format!("Invalid request body: {}", body_text));
+
+    AnyhowError::new(AudioError::InvalidRequestBody { message })
+}
+
+fn query_rejection_to_anyhow(rejection: QueryRejection) -> AnyhowError {
+    AnyhowError::new(AudioError::InvalidRequestBody {
+        message: format!("Invalid query parameters: {}", rejection.body_text()),
+    })
 }
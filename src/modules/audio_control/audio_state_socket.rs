@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use axum::extract::ws::{Message, WebSocket};
+use futures::{SinkExt, StreamExt};
+use serde::Serialize;
+use tokio::sync::watch;
+
+use crate::modules::audio_control::{
+    models::{StateChanges, StateCommand, StateStreamError},
+    services::StateService,
+};
+
+/// Atende uma conexão WebSocket de mudanças do estado de áudio
+///
+/// Nada é enviado até o cliente mandar `subscribe`. A resposta traz as
+/// mudanças desde `since`, ou o estado completo; a partir daí cada versão
+/// nova chega como um patch sobre a última entregue, até `unsubscribe` ou o
+/// fim da conexão.
+pub async fn handle_state_socket(socket: WebSocket, state: Arc<StateService>) {
+    let (mut sender, mut receiver) = socket.split();
+    // Só existe com inscrição ativa; enquanto existir o estado é relido
+    let mut versions: Option<watch::Receiver<u64>> = None;
+    let mut delivered = 0;
+
+    loop {
+        tokio::select! {
+            message = receiver.next() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+
+                let sent = match serde_json::from_str::<StateCommand>(&text) {
+                    Ok(StateCommand::Subscribe { since, epoch }) => {
+                        let mut subscription = state.subscribe();
+                        subscription.borrow_and_update();
+                        versions = Some(subscription);
+
+                        match state.refresh().await {
+                            Ok(_) => {
+                                let changes = state.changes_since(since.unwrap_or(0), epoch);
+                                delivered = version_of(&changes);
+                                send_message(&mut sender, &changes).await
+                            }
+                            Err(error) => {
+                                versions = None;
+                                let error = error.to_string();
+                                send_message(&mut sender, &StateStreamError { error }).await
+                            }
+                        }
+                    }
+                    Ok(StateCommand::Unsubscribe) => {
+                        versions = None;
+                        Ok(())
+                    }
+                    Err(error) => {
+                        let error = format!("Invalid state command: {error}");
+                        send_message(&mut sender, &StateStreamError { error }).await
+                    }
+                };
+                if sent.is_err() {
+                    break;
+                }
+            }
+            _ = next_version(&mut versions) => {
+                let changes = state.changes_since(delivered, None);
+                // A versão lida na inscrição já foi entregue
+                if version_of(&changes) == delivered {
+                    continue;
+                }
+                delivered = version_of(&changes);
+                if send_message(&mut sender, &changes).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Aguarda a próxima versão do estado; sem inscrição nunca completa
+async fn next_version(versions: &mut Option<watch::Receiver<u64>>) {
+    match versions {
+        Some(receiver) => {
+            if receiver.changed().await.is_err() {
+                std::future::pending::<()>().await;
+            }
+        }
+        None => std::future::pending().await,
+    }
+}
+
+fn version_of(changes: &StateChanges) -> u64 {
+    match changes {
+        StateChanges::Patch { version, .. } | StateChanges::Resync { version, .. } => *version,
+    }
+}
+
+async fn send_message<S, T>(sender: &mut S, message: &T) -> Result<(), ()>
+where
+    S: SinkExt<Message> + Unpin,
+    T: Serialize,
+{
+    let json = serde_json::to_string(message).map_err(|_| ())?;
+    sender
+        .send(Message::Text(json.into()))
+        .await
+        .map_err(|_| ())
+}
@@ -1,7 +1,10 @@
 use std::string::FromUtf16Error;
 use thiserror::Error;
 
+use crate::modules::audio_control::models::LimitReason;
 use crate::modules::core::errors::error_codes;
+use crate::modules::core::executor::BackendError;
+use crate::modules::core::persistence::PersistenceError;
 
 #[derive(Debug, Error)]
 pub enum AudioError {
@@ -17,9 +20,61 @@ pub enum AudioError {
     #[error("No sessions found")]
     NoSessionsFound,
 
+    #[error("Session not found: {session_id}")]
+    SessionNotFound { session_id: String },
+
+    #[error("Application not found: {process_name}")]
+    ApplicationNotFound { process_name: String },
+
+    #[error("Profile not found: {name}")]
+    ProfileNotFound { name: String },
+
+    #[error("Rule not found: {name}")]
+    RuleNotFound { name: String },
+
+    #[error("Scheduled task not found: {id}")]
+    ScheduledTaskNotFound { id: u64 },
+
+    #[error("Volume link not found: {name}")]
+    LinkNotFound { name: String },
+
+    #[error("No sleep timer running")]
+    NoSleepTimer,
+
+    #[error("Focus mode is not active")]
+    FocusModeInactive,
+
+    #[error("Nothing to undo")]
+    NothingToUndo,
+
+    #[error("Nothing to redo")]
+    NothingToRedo,
+
+    #[error("{action} cannot be undone")]
+    NotUndoable { action: String },
+
+    #[error("{reason}: requested {requested}, allowed {allowed}")]
+    VolumeLimitExceeded {
+        requested: f32,
+        allowed: f32,
+        reason: LimitReason,
+    },
+
+    #[error("No icon available for group {group_id}")]
+    IconNotFound { group_id: String },
+
     #[error("Invalid device ID")]
     InvalidDeviceId,
 
+    #[error("Expected {expected} channel levels, got {actual}")]
+    ChannelCountMismatch { expected: usize, actual: usize },
+
+    #[error("Audio backend error: {0}")]
+    Backend(#[from] BackendError),
+
+    #[error("Persistence error: {0}")]
+    Persistence(#[from] PersistenceError),
+
     #[error("Windows API error: {0}")]
     WindowsError(#[from] windows::core::Error),
 
@@ -36,6 +91,29 @@ impl AudioError {
                 AudioError::InvalidRequestBody { .. } => (error_codes::BAD_REQUEST, None),
                 AudioError::InvalidDeviceId => (error_codes::BAD_REQUEST, None),
                 AudioError::NoSessionsFound => (error_codes::NOT_FOUND, None),
+                AudioError::SessionNotFound { .. } => (error_codes::NOT_FOUND, None),
+                AudioError::IconNotFound { .. } => (error_codes::NOT_FOUND, None),
+                AudioError::ApplicationNotFound { .. } => (error_codes::NOT_FOUND, None),
+                AudioError::ProfileNotFound { .. } => (error_codes::NOT_FOUND, None),
+                AudioError::RuleNotFound { .. } => (error_codes::NOT_FOUND, None),
+                AudioError::ScheduledTaskNotFound { .. } => (error_codes::NOT_FOUND, None),
+                AudioError::LinkNotFound { .. } => (error_codes::NOT_FOUND, None),
+                AudioError::NoSleepTimer => (error_codes::NOT_FOUND, None),
+                AudioError::FocusModeInactive => (error_codes::NOT_FOUND, None),
+                AudioError::NothingToUndo => (error_codes::NOT_FOUND, None),
+                AudioError::NothingToRedo => (error_codes::NOT_FOUND, None),
+                AudioError::NotUndoable { .. } => {
+                    (error_codes::CONFLICT, Some(session_err.to_string()))
+                }
+                AudioError::VolumeLimitExceeded { .. } => {
+                    (error_codes::BAD_REQUEST, Some(session_err.to_string()))
+                }
+                AudioError::ChannelCountMismatch { .. } => {
+                    (error_codes::BAD_REQUEST, Some(session_err.to_string()))
+                }
+                AudioError::Backend(BackendError::Timeout(_)) => {
+                    (error_codes::GATEWAY_TIMEOUT, Some(session_err.to_string()))
+                }
                 _ => (error_codes::INTERNAL_ERROR, Some(session_err.to_string())),
             }
         } else {
@@ -1,12 +1,14 @@
 pub mod audio_handlers;
+pub mod audio_meter_socket;
 pub mod audio_module;
+pub mod audio_state_socket;
 pub mod errors;
 pub mod models;
 pub mod services;
 pub mod types;
 pub mod utils;
 
-pub use audio_module::AudioModule;
+pub use audio_module::{AudioModule, AudioModuleConfig};
 
 mod platform;
 
@@ -0,0 +1,42 @@
+use serde::Serialize;
+
+/// Nome exibido para a sessão de sons do sistema
+pub const SYSTEM_SOUNDS_NAME: &str = "System Sounds";
+
+/// Identificação da aplicação dona de um grupo de sessões
+#[derive(Debug, Clone, Serialize)]
+pub struct AppInfo {
+    /// Nome amigável: descrição do executável, nome do produto ou nome do arquivo
+    pub display_name: String,
+    /// Nome do executável sem extensão, usado como chave por aplicação
+    pub process_name: String,
+    /// PID do processo dono da primeira sessão; 0 para sons do sistema
+    pub pid: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable_path: Option<String>,
+    pub is_system_sounds: bool,
+}
+
+impl AppInfo {
+    /// Sessão de sons do sistema, que não pertence a nenhum processo
+    pub fn system_sounds() -> Self {
+        Self {
+            display_name: SYSTEM_SOUNDS_NAME.to_string(),
+            process_name: "system_sounds".to_string(),
+            pid: 0,
+            executable_path: None,
+            is_system_sounds: true,
+        }
+    }
+
+    /// Processo cujo executável não pôde ser lido (ex: processo protegido)
+    pub fn unresolved(pid: u32) -> Self {
+        Self {
+            display_name: format!("PID {pid}"),
+            process_name: format!("pid_{pid}"),
+            pid,
+            executable_path: None,
+            is_system_sounds: false,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Dispositivo de saída preferido de um executável
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RoutingPreference {
+    pub process_name: String,
+    pub device_id: String,
+}
+
+/// Normaliza o nome do processo usado como chave das preferências
+///
+/// A comparação ignora maiúsculas e a extensão `.exe`, de forma que
+/// `Discord.exe` e `discord` se refiram ao mesmo executável.
+pub fn normalize_process_name(process_name: &str) -> String {
+    let name = process_name.trim().to_lowercase();
+    match name.strip_suffix(".exe") {
+        Some(stem) => stem.to_string(),
+        None => name,
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::modules::audio_control::{
+    models::{normalize_process_name, MuteState, SessionGroup},
+    types::GroupId,
+};
+
+/// Grupos de sessões de um dispositivo de saída
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceSessions {
+    pub device_id: String,
+    pub device_name: String,
+    pub is_default: bool,
+    pub sessions: Vec<SessionGroup>,
+}
+
+/// Aplicação vista através de todos os dispositivos em que toca
+///
+/// Grupos com o mesmo executável são unidos; cada um aparece em
+/// `instances` com o dispositivo onde está.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApplicationView {
+    /// Nome normalizado do executável, chave da aplicação
+    pub process_name: String,
+    pub display_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable_path: Option<String>,
+    pub is_system_sounds: bool,
+    /// Média dos volumes dos grupos da aplicação
+    pub volume_level: f32,
+    pub mute_state: MuteState,
+    pub instances: Vec<ApplicationInstance>,
+}
+
+/// Grupo de sessões de uma aplicação em um dispositivo
+#[derive(Debug, Clone, Serialize)]
+pub struct ApplicationInstance {
+    pub device_id: String,
+    pub group_id: GroupId,
+    pub volume_level: f32,
+    pub mute_state: MuteState,
+}
+
+impl ApplicationView {
+    /// Agrupa os grupos de sessões de todos os dispositivos por executável
+    pub fn from_devices(devices: &[DeviceSessions]) -> Vec<Self> {
+        let mut applications: BTreeMap<String, ApplicationView> = BTreeMap::new();
+
+        for device in devices {
+            for group in &device.sessions {
+                let process_name = normalize_process_name(&group.app.process_name);
+                let application =
+                    applications
+                        .entry(process_name.clone())
+                        .or_insert_with(|| ApplicationView {
+                            process_name,
+                            display_name: group.app.display_name.clone(),
+                            executable_path: group.app.executable_path.clone(),
+                            is_system_sounds: group.app.is_system_sounds,
+                            volume_level: 0.0,
+                            mute_state: MuteState::None,
+                            instances: Vec::new(),
+                        });
+
+                application.instances.push(ApplicationInstance {
+                    device_id: device.device_id.clone(),
+                    group_id: group.id.clone(),
+                    volume_level: group.volume_level,
+                    mute_state: group.mute_state,
+                });
+            }
+        }
+
+        applications
+            .into_values()
+            .map(ApplicationView::with_aggregates)
+            .collect()
+    }
+
+    fn with_aggregates(mut self) -> Self {
+        let count = self.instances.len();
+        self.volume_level =
+            self.instances.iter().map(|i| i.volume_level).sum::<f32>() / count as f32;
+
+        let all = |state: MuteState| self.instances.iter().all(|i| i.mute_state == state);
+        self.mute_state = if all(MuteState::All) {
+            MuteState::All
+        } else if all(MuteState::None) {
+            MuteState::None
+        } else {
+            MuteState::Partial
+        };
+        self
+    }
+}
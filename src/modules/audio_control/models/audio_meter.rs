@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use crate::modules::audio_control::types::GroupId;
+
+/// Pico de áudio de um grupo de sessões (0.0 a 1.0)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct GroupPeak {
+    pub group_id: GroupId,
+    pub peak: f32,
+}
+
+/// Comandos aceitos pelo WebSocket de medição
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum MeterCommand {
+    Subscribe {
+        device_id: String,
+        #[serde(default = "default_rate_hz")]
+        rate_hz: u32,
+        #[serde(default = "default_include_groups")]
+        include_groups: bool,
+    },
+    Unsubscribe,
+}
+
+fn default_rate_hz() -> u32 {
+    20
+}
+
+fn default_include_groups() -> bool {
+    true
+}
+
+/// Quadro compacto enviado a cada leitura de pico
+///
+/// * `t` - Timestamp Unix em milissegundos
+/// * `d` - Pico do dispositivo
+/// * `g` - Pares `[group_id, pico]` de cada grupo de sessões
+#[derive(Debug, Serialize)]
+pub struct MeterFrame {
+    pub t: u64,
+    pub d: f32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub g: Vec<(GroupId, f32)>,
+}
+
+/// Quadro de erro enviado quando um comando ou leitura falha
+#[derive(Debug, Serialize)]
+pub struct MeterError {
+    pub error: String,
+}
@@ -1,11 +1,212 @@
+use chrono::NaiveTime;
 use serde::{Deserialize, Serialize};
 
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{ChannelAdjustment, GroupVolumeMode, RuleAction, SleepTarget, TaskSchedule},
+    types::AudioResult,
+};
+use crate::modules::core::ServerEvent;
+
+/// Volume master de um dispositivo
+#[derive(Debug, Deserialize)]
+pub struct SetDeviceVolumeRequest {
+    pub device_id: String,
+    pub volume: Volume,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SetGroupVolumeRequest {
     pub device_id: String,
     pub group_id: String,
     pub volume: Volume,
+    #[serde(default)]
+    pub mode: GroupVolumeMode,
+}
+/// Volume de uma aplicação em todos os dispositivos onde ela toca
+#[derive(Debug, Deserialize)]
+pub struct SetApplicationVolumeRequest {
+    pub process_name: String,
+    pub volume: Volume,
+    #[serde(default)]
+    pub mode: GroupVolumeMode,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetDeviceChannelsRequest {
+    pub device_id: String,
+    #[serde(flatten)]
+    pub levels: ChannelLevelsRequest,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetGroupChannelsRequest {
+    pub device_id: String,
+    pub group_id: String,
+    #[serde(flatten)]
+    pub levels: ChannelLevelsRequest,
+}
+
+/// Níveis por canal ou `balance`; exatamente um dos dois deve ser enviado
+#[derive(Debug, Deserialize)]
+pub struct ChannelLevelsRequest {
+    pub channels: Option<Vec<Volume>>,
+    pub balance: Option<Balance>,
+}
+
+impl TryFrom<ChannelLevelsRequest> for ChannelAdjustment {
+    type Error = AudioError;
+
+    fn try_from(request: ChannelLevelsRequest) -> AudioResult<Self> {
+        match (request.channels, request.balance) {
+            (Some(channels), None) => Ok(ChannelAdjustment::Levels(
+                channels.into_iter().map(f32::from).collect(),
+            )),
+            (None, Some(balance)) => Ok(ChannelAdjustment::Balance(balance.into())),
+            _ => Err(AudioError::InvalidRequestBody {
+                message: "Exactly one of 'channels' or 'balance' must be provided".to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetSessionVolumeRequest {
+    pub device_id: String,
+    pub session_id: String,
+    pub volume: Volume,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetSessionMuteRequest {
+    pub device_id: String,
+    pub session_id: String,
+    pub muted: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MoveGroupRequest {
+    pub device_id: String,
+    pub group_id: String,
+    pub target_device_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetRoutingPreferenceRequest {
+    pub process_name: String,
+    pub device_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RemoveRoutingPreferenceRequest {
+    pub process_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetVolumeMemoryRequest {
+    pub process_name: String,
+    pub enabled: bool,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct ForgetVolumeMemoryRequest {
+    pub process_name: String,
+}
+
+/// Identifica um perfil de som pelo nome
+#[derive(Debug, Deserialize)]
+pub struct ProfileNameRequest {
+    pub name: String,
+}
+
+/// Identifica uma regra de automação pelo nome
+#[derive(Debug, Deserialize)]
+pub struct RuleNameRequest {
+    pub name: String,
+}
+
+/// Liga o modo foco
+///
+/// Sem `duration_secs` o modo foco fica ligado até ser desligado.
+#[derive(Debug, Deserialize)]
+pub struct StartFocusModeRequest {
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+    #[serde(default)]
+    pub max_master: Option<Volume>,
+    #[serde(default)]
+    pub duration_secs: Option<u64>,
+}
+
+/// Identifica um link de volume pelo nome
+#[derive(Debug, Deserialize)]
+pub struct LinkNameRequest {
+    pub name: String,
+}
+
+/// Simulação pedida ao endpoint de dry-run
+///
+/// Sem `event` são avaliados os gatilhos de horário em `time`; sem `time`
+/// vale o horário atual.
+#[derive(Debug, Deserialize)]
+pub struct RuleDryRunRequest {
+    #[serde(default)]
+    pub event: Option<ServerEvent>,
+    #[serde(default)]
+    pub time: Option<NaiveTime>,
+}
+
+/// Nova tarefa para o agendador
+#[derive(Debug, Deserialize)]
+pub struct ScheduleTaskRequest {
+    pub name: String,
+    pub schedule: TaskSchedule,
+    pub actions: Vec<RuleAction>,
+}
+
+/// Identifica uma tarefa agendada
+#[derive(Debug, Deserialize)]
+pub struct ScheduledTaskIdRequest {
+    pub id: u64,
+}
+
+/// Início do timer de sono
+///
+/// Sem `fade_secs` o volume cai nos últimos 5 minutos (ou durante todo o
+/// timer, se ele for mais curto).
+#[derive(Debug, Deserialize)]
+pub struct StartSleepTimerRequest {
+    pub duration_secs: u64,
+    #[serde(default)]
+    pub fade_secs: Option<u64>,
+    #[serde(default)]
+    pub target: SleepTarget,
+    /// Pausa a mídia em reprodução no fim, além de silenciar os alvos
+    #[serde(default)]
+    pub pause_media: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ListSessionsQuery {
+    #[serde(default)]
+    pub expand: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ListDevicesQuery {
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StateChangesQuery {
+    /// Última versão do estado conhecida pelo cliente
+    pub since: u64,
+    /// `epoch` da resposta em que o cliente recebeu `since`
+    #[serde(default)]
+    pub epoch: Option<u64>,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(try_from = "f32")]
 pub struct Volume(f32);
@@ -27,3 +228,25 @@ impl From<Volume> for f32 {
         val.0
     }
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "f32")]
+pub struct Balance(f32);
+
+impl TryFrom<f32> for Balance {
+    type Error = &'static str;
+
+    fn try_from(v: f32) -> Result<Self, Self::Error> {
+        if (-1.0..=1.0).contains(&v) {
+            Ok(Balance(v))
+        } else {
+            Err("Balance must be between -1.0 and 1.0")
+        }
+    }
+}
+
+impl From<Balance> for f32 {
+    fn from(val: Balance) -> Self {
+        val.0
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::modules::audio_control::models::{DeviceSound, SessionGroup};
+use crate::modules::core::utils::PatchOperation;
+
+/// Estado completo do sistema de áudio em um único documento
+///
+/// Dispositivos e grupos ficam em mapas pelo id, para que as mudanças
+/// apareçam como caminhos estáveis no JSON Patch.
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioState {
+    pub default_device_id: Option<String>,
+    /// Dispositivos ativos
+    pub devices: BTreeMap<String, DeviceStateEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceStateEntry {
+    #[serde(flatten)]
+    pub device: DeviceSound,
+    /// Volume master do dispositivo
+    pub volume: f32,
+    pub muted: bool,
+    /// Grupos de sessões do dispositivo
+    pub sessions: BTreeMap<String, SessionGroup>,
+}
+
+/// Estado completo com a versão a que corresponde
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionedState {
+    /// Identifica o processo do servidor; as versões só valem dentro dele
+    pub epoch: u64,
+    pub version: u64,
+    pub state: Value,
+}
+
+/// Mudanças do estado desde uma versão conhecida pelo cliente
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StateChanges {
+    /// Operações que, aplicadas em ordem, levam de `since` a `version`
+    Patch {
+        since: u64,
+        version: u64,
+        operations: Vec<PatchOperation>,
+    },
+    /// `since` é antigo demais, desconhecido ou de outro `epoch`: o estado
+    /// completo
+    Resync {
+        epoch: u64,
+        version: u64,
+        state: Value,
+    },
+}
+
+/// Comandos aceitos pelo WebSocket de estado
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum StateCommand {
+    /// Sem `since`, começa pelo estado completo
+    Subscribe {
+        #[serde(default)]
+        since: Option<u64>,
+        /// `epoch` em que `since` foi recebido
+        #[serde(default)]
+        epoch: Option<u64>,
+    },
+    Unsubscribe,
+}
+
+/// Mensagem de erro enviada quando um comando ou leitura falha
+#[derive(Debug, Serialize)]
+pub struct StateStreamError {
+    pub error: String,
+}
@@ -0,0 +1,214 @@
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+use crate::modules::audio_control::{errors::AudioError, types::AudioResult};
+
+/// Regra de automação: quando o gatilho dispara e todas as condições
+/// valem, as ações são executadas em ordem
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AutomationRule {
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub trigger: RuleTrigger,
+    #[serde(default)]
+    pub conditions: Vec<RuleCondition>,
+    pub actions: Vec<RuleAction>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Evento que faz uma regra ser avaliada
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleTrigger {
+    /// Sessão iniciada; sem `process_name` vale para qualquer aplicação
+    SessionStarted {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        process_name: Option<String>,
+    },
+    SessionEnded {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        process_name: Option<String>,
+    },
+    DeviceConnected {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        device_id: Option<String>,
+    },
+    /// Horário local, com precisão de minutos
+    TimeOfDay { at: NaiveTime },
+    /// Volume master cruzou o limite na direção indicada; sem `device_id`
+    /// vale para o dispositivo padrão
+    VolumeThreshold {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        device_id: Option<String>,
+        threshold: f32,
+        direction: ThresholdDirection,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdDirection {
+    /// O volume subiu e passou a ser maior ou igual ao limite
+    Above,
+    /// O volume desceu e passou a ser menor ou igual ao limite
+    Below,
+}
+
+/// Condição verificada no momento em que o gatilho dispara
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleCondition {
+    ApplicationRunning {
+        process_name: String,
+    },
+    ApplicationNotRunning {
+        process_name: String,
+    },
+    DefaultDevice {
+        device_id: String,
+    },
+    /// Intervalo de horário local; `from` maior que `to` atravessa a meia-noite
+    TimeBetween {
+        from: NaiveTime,
+        to: NaiveTime,
+    },
+    MasterVolume {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<f32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<f32>,
+    },
+}
+
+/// Ação executada quando a regra dispara
+///
+/// Nas ações de aplicação, `process_name` ausente se refere à aplicação
+/// que disparou a regra (gatilhos de sessão).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleAction {
+    SetMasterVolume {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        device_id: Option<String>,
+        volume: f32,
+    },
+    SetApplicationVolume {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        process_name: Option<String>,
+        volume: f32,
+    },
+    MuteApplication {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        process_name: Option<String>,
+        #[serde(default = "default_muted")]
+        muted: bool,
+    },
+    /// Leva o volume master até `volume` em `duration_secs` segundos
+    FadeMasterVolume {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        device_id: Option<String>,
+        volume: f32,
+        duration_secs: u64,
+    },
+    SetDefaultDevice {
+        device_id: String,
+    },
+    ApplyProfile {
+        name: String,
+    },
+    /// Publica um `ServerEvent::Notification`; sem `title` usa o nome da regra
+    Notify {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        message: String,
+    },
+}
+
+fn default_muted() -> bool {
+    true
+}
+
+impl AutomationRule {
+    pub fn validate(&self) -> AudioResult<()> {
+        let invalid = |message: String| Err(AudioError::InvalidRequestBody { message });
+
+        if self.name.trim().is_empty() {
+            return invalid("Rule name must not be empty".to_string());
+        }
+        if self.actions.is_empty() {
+            return invalid(format!(
+                "Rule '{}': At least one action is required",
+                self.name
+            ));
+        }
+
+        let mut volumes = Vec::new();
+        if let RuleTrigger::VolumeThreshold { threshold, .. } = &self.trigger {
+            volumes.push(*threshold);
+        }
+        for condition in &self.conditions {
+            if let RuleCondition::MasterVolume { min, max } = condition {
+                volumes.extend(min.iter().chain(max.iter()));
+            }
+        }
+
+        if volumes.iter().any(|volume| !(0.0..=100.0).contains(volume)) {
+            return invalid(format!(
+                "Rule '{}': Volume must be between 0.0 and 100.0",
+                self.name
+            ));
+        }
+
+        let session_trigger = matches!(
+            self.trigger,
+            RuleTrigger::SessionStarted { .. } | RuleTrigger::SessionEnded { .. }
+        );
+        for action in &self.actions {
+            if let Err(message) = action.validate(session_trigger) {
+                return invalid(format!("Rule '{}': {}", self.name, message));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RuleAction {
+    /// Valida os parâmetros da ação
+    ///
+    /// Sem `has_triggering_app`, ações de aplicação precisam de `process_name`.
+    pub fn validate(&self, has_triggering_app: bool) -> Result<(), &'static str> {
+        let (volume, process_name) = match self {
+            RuleAction::SetMasterVolume { volume, .. }
+            | RuleAction::FadeMasterVolume { volume, .. } => (Some(volume), None),
+            RuleAction::SetApplicationVolume {
+                process_name,
+                volume,
+            } => (Some(volume), Some(process_name)),
+            RuleAction::MuteApplication { process_name, .. } => (None, Some(process_name)),
+            _ => (None, None),
+        };
+
+        if volume.is_some_and(|volume| !(0.0..=100.0).contains(volume)) {
+            return Err("Volume must be between 0.0 and 100.0");
+        }
+        if process_name.is_some_and(Option::is_none) && !has_triggering_app {
+            return Err("process_name is required without a session trigger");
+        }
+        Ok(())
+    }
+}
+
+/// Resultado da avaliação de uma regra cujo gatilho disparou
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleEvaluation {
+    pub rule: String,
+    pub conditions_met: bool,
+    /// Ações executadas (ou que seriam executadas, no dry-run)
+    pub actions: Vec<RuleAction>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<String>,
+}
@@ -0,0 +1,109 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use super::{GroupVolumeMode, SessionGroup};
+
+/// O que uma mudança registrada no histórico alterou
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HistoryTarget {
+    DeviceVolume {
+        device_id: String,
+    },
+    DeviceChannels {
+        device_id: String,
+    },
+    GroupVolume {
+        device_id: String,
+        group_id: String,
+    },
+    GroupChannels {
+        device_id: String,
+        group_id: String,
+    },
+    SessionVolume {
+        device_id: String,
+        session_id: String,
+    },
+    SessionMute {
+        device_id: String,
+        session_id: String,
+    },
+    /// Todas as sessões da aplicação, em todos os dispositivos
+    ApplicationVolume {
+        process_name: String,
+    },
+    /// Mudança que o histórico não sabe desfazer (`move_group`,
+    /// `apply_profile` e preferências de roteamento); `undo` para nela
+    Irreversible {
+        action: String,
+    },
+}
+
+/// Volume de uma sessão guardado para ser restaurado
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SessionVolume {
+    pub device_id: String,
+    pub session_id: String,
+    pub volume: f32,
+}
+
+impl SessionVolume {
+    /// Volumes atuais das sessões de um grupo da visão expandida
+    pub fn of_group(device_id: &str, group: &SessionGroup) -> Vec<Self> {
+        Self::with_volumes(device_id, group, &group.member_volumes)
+    }
+
+    /// Volumes das sessões de um grupo depois de aplicar `volume` em `mode`
+    pub fn after_group_volume(
+        device_id: &str,
+        group: &SessionGroup,
+        volume: f32,
+        mode: GroupVolumeMode,
+    ) -> Vec<Self> {
+        let targets = mode.member_targets(&group.member_volumes, volume);
+        Self::with_volumes(device_id, group, &targets)
+    }
+
+    fn with_volumes(device_id: &str, group: &SessionGroup, volumes: &[f32]) -> Vec<Self> {
+        group
+            .sessions
+            .iter()
+            .flatten()
+            .zip(volumes)
+            .map(|(member, volume)| SessionVolume {
+                device_id: device_id.to_string(),
+                session_id: member.session_id.clone(),
+                volume: *volume,
+            })
+            .collect()
+    }
+}
+
+/// Valor do alvo antes ou depois de uma mudança
+///
+/// Grupos e aplicações guardam o volume de cada sessão, para que desfazer
+/// devolva também as diferenças entre elas.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum HistoryValue {
+    Volume(f32),
+    Muted(bool),
+    Levels(Vec<f32>),
+    Sessions(Vec<SessionVolume>),
+    /// Valor não guardado, de uma mudança `Irreversible`
+    Unknown,
+}
+
+/// Mudança feita pela API
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HistoryEntry {
+    pub id: u64,
+    pub client: String,
+    pub timestamp: NaiveDateTime,
+    pub target: HistoryTarget,
+    pub old_value: HistoryValue,
+    pub new_value: HistoryValue,
+    /// Se a mudança foi desfeita e pode ser refeita
+    pub undone: bool,
+}
@@ -0,0 +1,81 @@
+use serde::Serialize;
+
+use crate::modules::audio_control::{errors::AudioError, types::AudioResult};
+
+/// Volume individual de cada canal (0.0 a 100.0), na ordem do dispositivo
+///
+/// `balance` só é preenchido quando há pelo menos dois canais e representa
+/// a relação entre esquerda (canal 0) e direita (canal 1), de -1.0 a 1.0.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ChannelVolumes {
+    pub channels: Vec<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<f32>,
+}
+
+impl ChannelVolumes {
+    pub fn new(channels: Vec<f32>) -> Self {
+        let balance = balance_from_levels(&channels);
+        Self { channels, balance }
+    }
+}
+
+/// Ajuste solicitado para os canais de um alvo
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChannelAdjustment {
+    /// Define explicitamente o nível de cada canal
+    Levels(Vec<f32>),
+    /// Redistribui esquerda/direita mantendo o nível do canal mais alto
+    Balance(f32),
+}
+
+impl ChannelAdjustment {
+    /// Calcula os níveis finais a partir dos níveis atuais do alvo
+    pub fn resolve(self, current: &[f32]) -> AudioResult<Vec<f32>> {
+        match self {
+            ChannelAdjustment::Levels(levels) => {
+                if levels.len() != current.len() {
+                    return Err(AudioError::ChannelCountMismatch {
+                        expected: current.len(),
+                        actual: levels.len(),
+                    });
+                }
+                Ok(levels)
+            }
+            ChannelAdjustment::Balance(balance) => levels_from_balance(current, balance),
+        }
+    }
+}
+
+fn balance_from_levels(channels: &[f32]) -> Option<f32> {
+    let (left, right) = match channels {
+        [left, right, ..] => (*left, *right),
+        _ => return None,
+    };
+
+    let peak = left.max(right);
+    if peak <= 0.0 {
+        return Some(0.0);
+    }
+    Some((right - left) / peak)
+}
+
+fn levels_from_balance(current: &[f32], balance: f32) -> AudioResult<Vec<f32>> {
+    if current.len() < 2 {
+        return Err(AudioError::ChannelCountMismatch {
+            expected: 2,
+            actual: current.len(),
+        });
+    }
+
+    let peak = current[0].max(current[1]);
+    let mut levels = current.to_vec();
+    if balance >= 0.0 {
+        levels[0] = peak * (1.0 - balance);
+        levels[1] = peak;
+    } else {
+        levels[0] = peak;
+        levels[1] = peak * (1.0 + balance);
+    }
+    Ok(levels)
+}
@@ -1,13 +1,17 @@
 use serde::Serialize;
 use std::fmt::Display;
-use windows::Win32::Media::Audio::IMMDevice;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DeviceSound {
     pub id: String,
     pub name: String,
-    #[serde(skip_serializing)]
-    pub endpoint: IMMDevice,
+    pub state: DeviceState,
+    pub form_factor: FormFactor,
+    pub is_default: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<DeviceFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_range: Option<VolumeRange>,
 }
 
 impl Display for DeviceSound {
@@ -15,3 +19,76 @@ impl Display for DeviceSound {
         write!(f, "ID: {} - Device Name: {}", self.id, self.name)
     }
 }
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceState {
+    Active,
+    Disabled,
+    NotPresent,
+    Unplugged,
+}
+
+impl From<u32> for DeviceState {
+    /// Converte o valor `DEVICE_STATE_*` retornado pelo `IMMDevice::GetState`
+    fn from(state: u32) -> Self {
+        match state {
+            0x1 => DeviceState::Active,
+            0x2 => DeviceState::Disabled,
+            0x8 => DeviceState::Unplugged,
+            _ => DeviceState::NotPresent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FormFactor {
+    RemoteNetworkDevice,
+    Speakers,
+    LineLevel,
+    Headphones,
+    Microphone,
+    Headset,
+    Handset,
+    DigitalPassthrough,
+    Spdif,
+    Hdmi,
+    Unknown,
+}
+
+impl From<u32> for FormFactor {
+    /// Converte o valor `EndpointFormFactor` lido de `PKEY_AudioEndpoint_FormFactor`
+    fn from(form_factor: u32) -> Self {
+        match form_factor {
+            0 => FormFactor::RemoteNetworkDevice,
+            1 => FormFactor::Speakers,
+            2 => FormFactor::LineLevel,
+            3 => FormFactor::Headphones,
+            4 => FormFactor::Microphone,
+            5 => FormFactor::Headset,
+            6 => FormFactor::Handset,
+            7 => FormFactor::DigitalPassthrough,
+            8 => FormFactor::Spdif,
+            9 => FormFactor::Hdmi,
+            _ => FormFactor::Unknown,
+        }
+    }
+}
+
+/// Formato de mixagem usado pelo engine de áudio para o dispositivo
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub struct DeviceFormat {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub bits_per_sample: u16,
+}
+
+/// Faixa de volume suportada pelo hardware do dispositivo
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub struct VolumeRange {
+    pub min_db: f32,
+    pub max_db: f32,
+    pub increment_db: f32,
+    pub step_count: u32,
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+use crate::modules::audio_control::{
+    errors::AudioError, models::normalize_process_name, types::AudioResult,
+};
+
+/// Duração máxima das rampas, em milissegundos
+pub const MAX_RAMP_MS: u64 = 60_000;
+
+/// Configuração do ducking automático
+///
+/// Enquanto algum grupo de uma aplicação de comunicação estiver `active`,
+/// os demais grupos têm o volume reduzido em `reduction` por cento.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DuckingConfig {
+    pub enabled: bool,
+    /// Aplicações que disparam o ducking; elas mesmas nunca são abaixadas
+    pub communication_apps: Vec<String>,
+    /// Quanto o volume dos outros grupos cai, em percentual do volume atual
+    pub reduction: f32,
+    /// Aplicações que nunca são abaixadas
+    pub exclusions: Vec<String>,
+    /// Tempo para abaixar os grupos, em milissegundos
+    pub ramp_down_ms: u64,
+    /// Tempo para restaurar os grupos, em milissegundos
+    pub ramp_up_ms: u64,
+}
+
+impl Default for DuckingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            communication_apps: ["discord", "ms-teams", "teams", "zoom", "skype"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            reduction: 50.0,
+            exclusions: Vec::new(),
+            ramp_down_ms: 300,
+            ramp_up_ms: 1000,
+        }
+    }
+}
+
+impl DuckingConfig {
+    /// Valida a configuração e normaliza os nomes das aplicações
+    pub fn normalized(mut self) -> AudioResult<Self> {
+        if !(0.0..=100.0).contains(&self.reduction) {
+            return Err(AudioError::InvalidRequestBody {
+                message: "reduction must be between 0.0 and 100.0".to_string(),
+            });
+        }
+        if self.ramp_down_ms > MAX_RAMP_MS || self.ramp_up_ms > MAX_RAMP_MS {
+            return Err(AudioError::InvalidRequestBody {
+                message: format!("Ramp times must be at most {MAX_RAMP_MS} ms"),
+            });
+        }
+
+        for names in [&mut self.communication_apps, &mut self.exclusions] {
+            *names = names
+                .iter()
+                .map(|name| normalize_process_name(name))
+                .filter(|name| !name.is_empty())
+                .collect();
+            names.sort();
+            names.dedup();
+        }
+        Ok(self)
+    }
+
+    pub fn is_communication_app(&self, process_name: &str) -> bool {
+        self.communication_apps
+            .iter()
+            .any(|name| *name == normalize_process_name(process_name))
+    }
+
+    /// Se um grupo da aplicação pode ser abaixado
+    pub fn can_duck(&self, process_name: &str) -> bool {
+        let process_name = normalize_process_name(process_name);
+        !self.communication_apps.contains(&process_name) && !self.exclusions.contains(&process_name)
+    }
+}
+
+/// Configuração do ducking com o estado atual
+#[derive(Debug, Clone, Serialize)]
+pub struct DuckingStatus {
+    #[serde(flatten)]
+    pub config: DuckingConfig,
+    /// Se os grupos estão abaixados (ou sendo abaixados) agora
+    pub active: bool,
+    /// Grupos cujo volume está sob controle do ducking
+    pub ducked_groups: usize,
+}
@@ -0,0 +1,22 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+/// Estado do modo foco
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct FocusModeStatus {
+    pub active: bool,
+    /// Aplicações que continuam tocando, por nome normalizado
+    pub allowlist: Vec<String>,
+    /// Teto do volume master enquanto o modo foco estiver ligado
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_master: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<NaiveDateTime>,
+    /// Quando o modo foco desliga sozinho; ausente sem timer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_secs: Option<u64>,
+    /// Sessões silenciadas pelo modo foco, que voltam ao desligar
+    pub muted_sessions: usize,
+}
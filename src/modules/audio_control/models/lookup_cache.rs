@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+/// Contadores de um cache de consultas ao backend
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq)]
+pub struct CacheCounters {
+    /// Consultas respondidas pelo cache
+    pub hits: u64,
+    /// Consultas que foram ao backend, por falta ou expiração da entrada
+    pub misses: u64,
+    /// Entradas ainda válidas
+    pub entries: usize,
+}
+
+/// Estado dos caches de listas de dispositivos e de nomes de processos
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq)]
+pub struct LookupCacheStats {
+    pub devices: CacheCounters,
+    pub processes: CacheCounters,
+}
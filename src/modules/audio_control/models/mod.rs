@@ -1,6 +1,46 @@
+pub mod app_info;
+pub mod app_routing;
+pub mod application_view;
+pub mod audio_meter;
 pub mod audio_requests;
+pub mod audio_state;
+pub mod automation_rule;
+pub mod change_history;
+pub mod channel_volume;
 pub mod device_sound;
+pub mod ducking;
+pub mod focus_mode;
+pub mod lookup_cache;
+pub mod scheduled_task;
 pub mod session_sound;
+pub mod sleep_timer;
+pub mod sound_profile;
+pub mod volume_limits;
+pub mod volume_link;
+pub mod volume_memory;
 
-pub use device_sound::DeviceSound;
-pub use session_sound::{SessionGroup, SessionState};
+pub use app_info::{AppInfo, SYSTEM_SOUNDS_NAME};
+pub use app_routing::{normalize_process_name, RoutingPreference};
+pub use application_view::{ApplicationInstance, ApplicationView, DeviceSessions};
+pub use audio_meter::{GroupPeak, MeterCommand, MeterError, MeterFrame};
+pub use audio_state::{
+    AudioState, DeviceStateEntry, StateChanges, StateCommand, StateStreamError, VersionedState,
+};
+pub use automation_rule::{
+    AutomationRule, RuleAction, RuleCondition, RuleEvaluation, RuleTrigger, ThresholdDirection,
+};
+pub use change_history::{HistoryEntry, HistoryTarget, HistoryValue, SessionVolume};
+pub use channel_volume::{ChannelAdjustment, ChannelVolumes};
+pub use device_sound::{DeviceFormat, DeviceSound, DeviceState, FormFactor, VolumeRange};
+pub use ducking::{DuckingConfig, DuckingStatus};
+pub use focus_mode::FocusModeStatus;
+pub use lookup_cache::{CacheCounters, LookupCacheStats};
+pub use scheduled_task::{ScheduledTask, ScheduledTasks, TaskSchedule};
+pub use session_sound::{GroupVolumeMode, MuteState, SessionGroup, SessionMember, SessionState};
+pub use sleep_timer::{SleepTarget, SleepTimerStatus};
+pub use sound_profile::{
+    ApplicationSetting, DeviceVolumeSetting, ProfileApplyReport, SoundProfile,
+};
+pub use volume_limits::{LimitPolicy, LimitReason, QuietHours, VolumeLimits, LIMIT_TOLERANCE};
+pub use volume_link::{LinkMember, LinkTarget, VolumeLink};
+pub use volume_memory::{AppVolumeMemory, RememberedVolume};
@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::modules::audio_control::models::RuleAction;
+
+/// Quando uma tarefa agendada deve rodar
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaskSchedule {
+    /// Uma única vez, no horário local indicado
+    Once { at: NaiveDateTime },
+    /// Uma única vez, `seconds` depois da criação; gravada como `once`
+    After { seconds: u64 },
+    /// Recorrente, segundo uma expressão cron de cinco campos
+    Cron { expression: String },
+}
+
+/// Ações agendadas, executadas em ordem quando a tarefa vence
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScheduledTask {
+    pub id: u64,
+    pub name: String,
+    pub schedule: TaskSchedule,
+    pub actions: Vec<RuleAction>,
+    /// Próxima execução; tarefas únicas saem da lista depois de rodar
+    pub next_run: NaiveDateTime,
+}
+
+/// Conteúdo persistido do agendador
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledTasks {
+    pub next_id: u64,
+    pub tasks: BTreeMap<u64, ScheduledTask>,
+}
+
+impl Default for ScheduledTasks {
+    fn default() -> Self {
+        Self {
+            next_id: 1,
+            tasks: BTreeMap::new(),
+        }
+    }
+}
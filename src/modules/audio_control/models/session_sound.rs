@@ -1,17 +1,167 @@
-use crate::modules::audio_control::types::GroupId;
-use serde::Serialize;
+use crate::modules::audio_control::{models::AppInfo, types::GroupId};
+use serde::{Deserialize, Serialize};
 use windows::Win32::Media::Audio::AudioSessionState;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SessionGroup {
     pub id: GroupId,
-    pub display_name: String,
+    /// Aplicação dona do grupo (nome, PID e executável)
+    #[serde(flatten)]
+    pub app: AppInfo,
+    /// Média dos volumes das sessões do grupo
     pub volume_level: f32,
+    pub volume_min: f32,
+    pub volume_max: f32,
+    /// Volume de cada sessão, na mesma ordem de `sessions`
+    pub member_volumes: Vec<f32>,
     pub state: SessionState,
+    /// Verdadeiro apenas quando todas as sessões estão mudas
     pub muted: bool,
+    pub mute_state: MuteState,
+    /// Sessões individuais do grupo; só preenchido na visão expandida
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sessions: Option<Vec<SessionMember>>,
 }
 
-#[derive(Debug, Serialize)]
+impl SessionGroup {
+    /// Cria um SessionGroup agregando as sessões que compartilham o mesmo GUID
+    ///
+    /// O estado do grupo é `active` se alguma sessão estiver ativa,
+    /// `inactive` se alguma estiver inativa e `expired` só quando todas
+    /// as sessões expiraram.
+    pub fn from_members(
+        id: GroupId,
+        app: AppInfo,
+        members: Vec<SessionMember>,
+        expand: bool,
+    ) -> Self {
+        let valid_volumes: Vec<f32> = members
+            .iter()
+            .map(|member| member.volume_level)
+            .filter(|volume| !volume.is_nan())
+            .collect();
+
+        let (volume_level, volume_min, volume_max) = if valid_volumes.is_empty() {
+            (0.0, 0.0, 0.0)
+        } else {
+            (
+                valid_volumes.iter().sum::<f32>() / valid_volumes.len() as f32,
+                valid_volumes.iter().copied().fold(f32::MAX, f32::min),
+                valid_volumes.iter().copied().fold(f32::MIN, f32::max),
+            )
+        };
+
+        // Volumes inválidos (NaN) são reportados como 0.0 para manter a ordem
+        let member_volumes = members
+            .iter()
+            .map(|member| {
+                if member.volume_level.is_nan() {
+                    0.0
+                } else {
+                    member.volume_level
+                }
+            })
+            .collect();
+
+        let muted_count = members.iter().filter(|member| member.muted).count();
+        let mute_state = MuteState::from_counts(muted_count, members.len());
+
+        let has_state = |state: SessionState| members.iter().any(|member| member.state == state);
+        let state = if has_state(SessionState::Active) {
+            SessionState::Active
+        } else if has_state(SessionState::Inactive) {
+            SessionState::Inactive
+        } else {
+            SessionState::Expired
+        };
+
+        Self {
+            id,
+            app,
+            volume_level,
+            volume_min,
+            volume_max,
+            member_volumes,
+            state,
+            muted: mute_state == MuteState::All,
+            mute_state,
+            sessions: expand.then_some(members),
+        }
+    }
+}
+
+/// Estado de mute agregado de um grupo de sessões
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MuteState {
+    None,
+    Partial,
+    All,
+}
+
+impl MuteState {
+    pub fn from_counts(muted: usize, total: usize) -> Self {
+        if muted == 0 {
+            MuteState::None
+        } else if muted == total {
+            MuteState::All
+        } else {
+            MuteState::Partial
+        }
+    }
+}
+
+/// Como o volume de um grupo é aplicado às suas sessões
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupVolumeMode {
+    /// Todas as sessões recebem o mesmo volume
+    #[default]
+    Uniform,
+    /// Escala as sessões mantendo as diferenças relativas entre elas,
+    /// de forma que a média do grupo passe a ser o volume pedido
+    Proportional,
+}
+
+impl GroupVolumeMode {
+    /// Calcula o volume final de cada sessão a partir dos volumes atuais
+    pub fn member_targets(self, current: &[f32], volume: f32) -> Vec<f32> {
+        let valid: Vec<f32> = current.iter().copied().filter(|v| !v.is_nan()).collect();
+        let average = if valid.is_empty() {
+            0.0
+        } else {
+            valid.iter().sum::<f32>() / valid.len() as f32
+        };
+
+        match self {
+            GroupVolumeMode::Proportional if average > 0.0 => current
+                .iter()
+                .map(|v| {
+                    if v.is_nan() {
+                        volume
+                    } else {
+                        (v * volume / average).clamp(0.0, 100.0)
+                    }
+                })
+                .collect(),
+            _ => vec![volume; current.len()],
+        }
+    }
+}
+
+/// Sessão de áudio individual dentro de um SessionGroup
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMember {
+    /// Identificador da instância da sessão, único por dispositivo
+    pub session_id: String,
+    pub pid: u32,
+    pub process_name: String,
+    pub volume_level: f32,
+    pub muted: bool,
+    pub state: SessionState,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum SessionState {
     Active,
@@ -19,11 +169,13 @@ pub enum SessionState {
     Expired,
 }
 
+/// Converte `AudioSessionStateInactive` (0), `AudioSessionStateActive` (1)
+/// e `AudioSessionStateExpired` (2)
 impl From<AudioSessionState> for SessionState {
     fn from(state: AudioSessionState) -> Self {
         match state.0 {
-            0 => SessionState::Active,
-            1 => SessionState::Inactive,
+            1 => SessionState::Active,
+            0 => SessionState::Inactive,
             _ => SessionState::Expired,
         }
     }
@@ -0,0 +1,35 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// O que o timer de sono abaixa e silencia
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SleepTarget {
+    /// Volume master; sem `device_id` vale o dispositivo padrão no início
+    Master {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        device_id: Option<String>,
+    },
+    /// Aplicações selecionadas, em todos os dispositivos onde tocam
+    Applications { process_names: Vec<String> },
+}
+
+impl Default for SleepTarget {
+    fn default() -> Self {
+        SleepTarget::Master { device_id: None }
+    }
+}
+
+/// Estado do timer de sono em andamento
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SleepTimerStatus {
+    pub target: SleepTarget,
+    pub started_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    /// Duração do fade que termina em `ends_at`
+    pub fade_secs: u64,
+    pub pause_media: bool,
+    pub remaining_secs: u64,
+    /// Se o volume já começou a ser reduzido
+    pub fading: bool,
+}
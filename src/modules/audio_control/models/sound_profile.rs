@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use crate::modules::audio_control::{errors::AudioError, types::AudioResult};
+
+/// Perfil de som nomeado ("reunião", "jogo", "noite")
+///
+/// Guarda o dispositivo padrão, o volume master de cada dispositivo e o
+/// volume e mute de cada aplicação no momento da captura.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SoundProfile {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_device_id: Option<String>,
+    #[serde(default)]
+    pub devices: Vec<DeviceVolumeSetting>,
+    #[serde(default)]
+    pub applications: Vec<ApplicationSetting>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeviceVolumeSetting {
+    pub device_id: String,
+    pub volume: f32,
+}
+
+/// Volume e mute de uma aplicação em um dispositivo
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApplicationSetting {
+    /// Nome normalizado do executável
+    pub process_name: String,
+    pub device_id: String,
+    pub volume: f32,
+    #[serde(default)]
+    pub muted: bool,
+}
+
+impl SoundProfile {
+    /// Valida um perfil recebido de fora (importação)
+    pub fn validate(&self) -> AudioResult<()> {
+        let invalid = |message: String| Err(AudioError::InvalidRequestBody { message });
+
+        if self.name.trim().is_empty() {
+            return invalid("Profile name must not be empty".to_string());
+        }
+
+        let volumes = self
+            .devices
+            .iter()
+            .map(|device| device.volume)
+            .chain(self.applications.iter().map(|app| app.volume));
+        for volume in volumes {
+            if !(0.0..=100.0).contains(&volume) {
+                return invalid(format!(
+                    "Profile '{}': Volume must be between 0.0 and 100.0",
+                    self.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Resultado da aplicação de um perfil
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileApplyReport {
+    pub profile: String,
+    /// Quantidade de aplicações ajustadas imediatamente
+    pub applied_applications: usize,
+    /// Aplicações que não estão tocando; serão ajustadas quando aparecerem
+    pub pending_applications: Vec<String>,
+    /// Ajustes que falharam (ex: dispositivo removido)
+    pub failures: Vec<String>,
+}
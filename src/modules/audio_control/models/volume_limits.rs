@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+use crate::modules::audio_control::{
+    errors::AudioError, models::normalize_process_name, types::AudioResult,
+};
+
+/// Folga para comparar volumes lidos do sistema, que voltam arredondados
+pub const LIMIT_TOLERANCE: f32 = 0.01;
+
+/// O que fazer com um pedido de volume acima de um limite
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitPolicy {
+    /// Aplica o maior volume permitido
+    #[default]
+    Clamp,
+    /// Recusa o pedido com `VolumeLimitExceeded`
+    Reject,
+}
+
+/// Horário silencioso com teto próprio para o volume master
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuietHours {
+    /// Horário local; `from` maior que `to` atravessa a meia-noite
+    pub from: NaiveTime,
+    pub to: NaiveTime,
+    pub max_volume: f32,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.from <= self.to {
+            self.from <= time && time < self.to
+        } else {
+            time >= self.from || time < self.to
+        }
+    }
+}
+
+/// Limite que restringiu um pedido de volume
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitReason {
+    MaxMaster,
+    ApplicationMax,
+    QuietHours,
+    MaxStep,
+}
+
+impl fmt::Display for LimitReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LimitReason::MaxMaster => "max_master",
+            LimitReason::ApplicationMax => "application_max",
+            LimitReason::QuietHours => "quiet_hours",
+            LimitReason::MaxStep => "max_step",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Limites de volume aplicados aos pedidos dos clientes
+///
+/// Os tetos (`max_master`, `application_max` e `quiet_hours`) também são
+/// mantidos em segundo plano contra mudanças externas; `max_step` vale só
+/// para os pedidos e limita quanto um único pedido pode aumentar o volume.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct VolumeLimits {
+    pub policy: LimitPolicy,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_master: Option<f32>,
+    /// Teto por aplicação, pelo nome normalizado do executável
+    pub application_max: BTreeMap<String, f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_step: Option<f32>,
+    /// Teto do volume master durante o horário silencioso
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHours>,
+}
+
+impl VolumeLimits {
+    /// Valida os limites e normaliza os nomes das aplicações
+    pub fn normalized(mut self) -> AudioResult<Self> {
+        let caps = self
+            .max_master
+            .iter()
+            .chain(self.application_max.values())
+            .chain(self.quiet_hours.as_ref().map(|quiet| &quiet.max_volume));
+        for cap in caps {
+            if !(0.0..=100.0).contains(cap) {
+                return Err(AudioError::InvalidRequestBody {
+                    message: "Volume limits must be between 0.0 and 100.0".to_string(),
+                });
+            }
+        }
+        if let Some(step) = self.max_step {
+            if !(step > 0.0 && step <= 100.0) {
+                return Err(AudioError::InvalidRequestBody {
+                    message: "max_step must be greater than 0.0 and at most 100.0".to_string(),
+                });
+            }
+        }
+
+        self.application_max = self
+            .application_max
+            .into_iter()
+            .map(|(name, cap)| (normalize_process_name(&name), cap))
+            .filter(|(name, _)| !name.is_empty())
+            .collect();
+        Ok(self)
+    }
+
+    /// Se há algum teto para manter em segundo plano
+    pub fn has_caps(&self) -> bool {
+        self.max_master.is_some() || self.quiet_hours.is_some() || !self.application_max.is_empty()
+    }
+
+    /// Teto do volume master no horário `time`: o menor entre `max_master`
+    /// e o do horário silencioso, se estiver em vigor
+    pub fn master_cap(&self, time: NaiveTime) -> Option<(f32, LimitReason)> {
+        let quiet = self
+            .quiet_hours
+            .as_ref()
+            .filter(|quiet| quiet.contains(time))
+            .map(|quiet| (quiet.max_volume, LimitReason::QuietHours));
+        let master = self.max_master.map(|cap| (cap, LimitReason::MaxMaster));
+        match (master, quiet) {
+            (Some(master), Some(quiet)) if quiet.0 < master.0 => Some(quiet),
+            (Some(master), _) => Some(master),
+            (None, quiet) => quiet,
+        }
+    }
+
+    pub fn application_cap(&self, process_name: &str) -> Option<(f32, LimitReason)> {
+        self.application_max
+            .get(&normalize_process_name(process_name))
+            .map(|cap| (*cap, LimitReason::ApplicationMax))
+    }
+
+    /// Verifica um pedido que leva o volume de `current` a `requested`
+    ///
+    /// `peak_ratio` é quanto o volume mais alto resultante fica acima de
+    /// `requested` (grupos em modo proporcional), e é ele que o teto limita.
+    /// Retorna o maior volume permitido e o limite que o restringiu, ou
+    /// `None` quando o pedido cabe nos limites.
+    pub fn check(
+        &self,
+        current: f32,
+        requested: f32,
+        peak_ratio: f32,
+        cap: Option<(f32, LimitReason)>,
+    ) -> Option<(f32, LimitReason)> {
+        let mut allowed = cap
+            .filter(|(cap, _)| requested * peak_ratio > cap + LIMIT_TOLERANCE)
+            .map(|(cap, reason)| (cap / peak_ratio, reason));
+
+        if let Some(step) = self.max_step {
+            // Baixar o volume nunca é limitado
+            let ceiling = current + step;
+            if requested > ceiling + LIMIT_TOLERANCE {
+                match allowed {
+                    Some((volume, _)) if volume <= ceiling => {}
+                    _ => allowed = Some((ceiling, LimitReason::MaxStep)),
+                }
+            }
+        }
+        allowed
+    }
+}
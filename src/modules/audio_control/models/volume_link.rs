@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+use crate::modules::audio_control::{
+    errors::AudioError, models::normalize_process_name, types::AudioResult,
+};
+
+/// Volume que participa de um link
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LinkTarget {
+    /// Volume master do dispositivo padrão no momento da mudança
+    Master,
+    Device {
+        device_id: String,
+    },
+    Group {
+        device_id: String,
+        group_id: String,
+    },
+    /// Todos os grupos da aplicação, em todos os dispositivos
+    Application {
+        process_name: String,
+    },
+}
+
+/// Membro de um link e sua relação com o nível comum do link
+///
+/// O volume do membro é `nível * ratio + offset`, limitado a 0..=100.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LinkMember {
+    pub target: LinkTarget,
+    #[serde(default = "default_ratio")]
+    pub ratio: f32,
+    #[serde(default)]
+    pub offset: f32,
+}
+
+fn default_ratio() -> f32 {
+    1.0
+}
+
+impl LinkMember {
+    /// Nível do link que corresponde ao volume deste membro
+    pub fn level_for(&self, volume: f32) -> f32 {
+        (volume - self.offset) / self.ratio
+    }
+
+    /// Volume deste membro para um nível do link
+    pub fn volume_for(&self, level: f32) -> f32 {
+        (level * self.ratio + self.offset).clamp(0.0, 100.0)
+    }
+}
+
+/// Grupo de volumes que se movem juntos
+///
+/// Quando um membro muda (pela API ou por fora), os outros são ajustados
+/// para o mesmo nível do link. Um volume pode estar em um único link.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VolumeLink {
+    pub name: String,
+    pub members: Vec<LinkMember>,
+}
+
+impl VolumeLink {
+    /// Valida o link e normaliza os nomes das aplicações
+    pub fn normalized(mut self) -> AudioResult<Self> {
+        let invalid = |message: String| Err(AudioError::InvalidRequestBody { message });
+
+        self.name = self.name.trim().to_string();
+        if self.name.is_empty() {
+            return invalid("Link name must not be empty".to_string());
+        }
+        if self.members.len() < 2 {
+            return invalid(format!(
+                "Link '{}': At least two members are required",
+                self.name
+            ));
+        }
+
+        for member in &mut self.members {
+            if !(member.ratio > 0.0 && member.ratio.is_finite()) {
+                return invalid(format!(
+                    "Link '{}': ratio must be greater than 0.0",
+                    self.name
+                ));
+            }
+            if !(-100.0..=100.0).contains(&member.offset) {
+                return invalid(format!(
+                    "Link '{}': offset must be between -100.0 and 100.0",
+                    self.name
+                ));
+            }
+            if let LinkTarget::Application { process_name } = &mut member.target {
+                *process_name = normalize_process_name(process_name);
+            }
+        }
+
+        for (index, member) in self.members.iter().enumerate() {
+            if self.members[..index]
+                .iter()
+                .any(|other| other.target == member.target)
+            {
+                return invalid(format!(
+                    "Link '{}': Each target may appear only once",
+                    self.name
+                ));
+            }
+        }
+        Ok(self)
+    }
+}
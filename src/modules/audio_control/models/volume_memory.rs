@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Último volume conhecido de um executável
+///
+/// Persistido por nome normalizado do processo, já que o `GroupId` muda
+/// entre execuções de algumas aplicações.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RememberedVolume {
+    /// Quando desligado, o volume não é gravado nem reaplicado
+    pub enabled: bool,
+    /// Ausente até a primeira alteração observada
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f32>,
+    #[serde(default)]
+    pub muted: bool,
+}
+
+impl Default for RememberedVolume {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            volume: None,
+            muted: false,
+        }
+    }
+}
+
+/// Entrada da memória de volume exposta pela API
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AppVolumeMemory {
+    pub process_name: String,
+    #[serde(flatten)]
+    pub remembered: RememberedVolume,
+}
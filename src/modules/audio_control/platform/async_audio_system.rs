@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::modules::audio_control::{
+    models::{AppInfo, DeviceSound, GroupPeak, GroupVolumeMode, SessionGroup},
+    platform::audio_system_interface::AudioProcessLookup,
+    types::{AudioResult, GroupId},
+};
+
+/// Async counterpart of `AudioSystemInterface`, for backends that reach the
+/// audio system through a socket or another process.
+///
+/// Synchronous backends are exposed through `BlockingAudioAdapter`, which
+/// runs each call on a dedicated backend thread.
+pub trait AsyncAudioSystem:
+    AsyncAudioOutputDeviceControl
+    + AsyncAudioSessionControl
+    + AsyncAudioMeteringControl
+    + AsyncAudioProcessLookup
+    + AsyncMediaPlaybackControl
+{
+}
+impl<T> AsyncAudioSystem for T where
+    T: AsyncAudioOutputDeviceControl
+        + AsyncAudioSessionControl
+        + AsyncAudioMeteringControl
+        + AsyncAudioProcessLookup
+        + AsyncMediaPlaybackControl
+{
+}
+
+/// Applications already identified by pid, handed to the backend so that a
+/// listing does not inspect the same process again.
+pub trait ProcessCache: Send + Sync {
+    /// Returns the application cached for `pid`, identifying it through
+    /// `processes` when it is missing or expired.
+    fn get_or_lookup(&self, pid: u32, processes: &dyn AudioProcessLookup) -> AppInfo;
+}
+
+/// Async counterpart of `AudioOutputDeviceControl`; every method has the
+/// same meaning as its synchronous version.
+#[async_trait]
+pub trait AsyncAudioOutputDeviceControl: Send + Sync {
+    /// Lists the audio output devices available on the system, including
+    /// inactive ones when `include_inactive` is set.
+    async fn list_output_devices(&self, include_inactive: bool) -> AudioResult<Vec<DeviceSound>>;
+
+    /// Retrieves the master volume of the default output device, between
+    /// 0.0 and 100.0.
+    async fn get_actual_volume(&self) -> AudioResult<f32>;
+
+    /// Returns the master volume of the specified output device.
+    async fn get_device_volume(&self, device_id: &str) -> AudioResult<f32>;
+
+    /// Sets the master volume of the specified output device.
+    async fn set_device_volume(&self, device_id: &str, volume: f32) -> AudioResult<()>;
+
+    /// Returns whether the specified output device is muted.
+    async fn get_device_mute(&self, device_id: &str) -> AudioResult<bool>;
+
+    /// Mutes or unmutes the specified output device.
+    async fn set_device_mute(&self, device_id: &str, muted: bool) -> AudioResult<()>;
+
+    /// Makes the specified device the default output device.
+    async fn set_default_device(&self, device_id: &str) -> AudioResult<()>;
+
+    /// Returns the volume of each channel of the specified output device.
+    async fn get_device_channel_volumes(&self, device_id: &str) -> AudioResult<Vec<f32>>;
+
+    /// Sets the volume of each channel of the specified output device.
+    async fn set_device_channel_volumes(&self, device_id: &str, levels: &[f32]) -> AudioResult<()>;
+
+    /// Returns the id of the current default output device, if there is one.
+    async fn default_device_id(&self) -> AudioResult<Option<String>> {
+        Ok(self
+            .list_output_devices(false)
+            .await?
+            .into_iter()
+            .find(|device| device.is_default)
+            .map(|device| device.id))
+    }
+}
+
+/// Async counterpart of `AudioSessionControl`; every method has the same
+/// meaning as its synchronous version.
+#[async_trait]
+pub trait AsyncAudioSessionControl: Send + Sync {
+    /// Returns the session groups of the specified output device, with
+    /// their member sessions when `expand` is set.
+    async fn get_sessions_for_device(
+        &self,
+        device_id: &str,
+        expand: bool,
+    ) -> AudioResult<Vec<SessionGroup>>;
+
+    /// Same as `get_sessions_for_device`, identifying the application behind
+    /// each session through `processes`.
+    ///
+    /// Backends that do not inspect processes themselves can keep the
+    /// default, which ignores `processes`.
+    async fn get_sessions_for_device_cached(
+        &self,
+        device_id: &str,
+        expand: bool,
+        processes: Arc<dyn ProcessCache>,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        let _ = processes;
+        self.get_sessions_for_device(device_id, expand).await
+    }
+
+    /// Sets the volume of a session group on the specified output device.
+    async fn set_group_volume(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        volume: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<()>;
+
+    /// Sets the volume of a single session.
+    async fn set_session_volume(
+        &self,
+        device_id: &str,
+        session_id: &str,
+        volume: f32,
+    ) -> AudioResult<()>;
+
+    /// Mutes or unmutes every session of a group.
+    async fn set_group_mute(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        muted: bool,
+    ) -> AudioResult<()>;
+
+    /// Mutes or unmutes a single session.
+    async fn set_session_mute(
+        &self,
+        device_id: &str,
+        session_id: &str,
+        muted: bool,
+    ) -> AudioResult<()>;
+
+    /// Moves the audio of every process in a session group to another
+    /// output device.
+    async fn move_group_to_device(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        target_device_id: &str,
+    ) -> AudioResult<()>;
+
+    /// Routes the audio output of a single process to the specified device.
+    async fn set_process_output_device(&self, pid: u32, device_id: &str) -> AudioResult<()>;
+
+    /// Returns the icon of the application behind a session group, as PNG.
+    async fn get_group_icon(&self, group_id: &GroupId) -> AudioResult<Vec<u8>>;
+
+    /// Returns the volume of each channel of a session group.
+    async fn get_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+    ) -> AudioResult<Vec<f32>>;
+
+    /// Sets the volume of each channel on every session of a group.
+    async fn set_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        levels: &[f32],
+    ) -> AudioResult<()>;
+}
+
+/// Async counterpart of `AudioMeteringControl`; every method has the same
+/// meaning as its synchronous version.
+#[async_trait]
+pub trait AsyncAudioMeteringControl: Send + Sync {
+    /// Returns the current peak level of the specified output device.
+    async fn get_device_peak(&self, device_id: &str) -> AudioResult<f32>;
+
+    /// Returns the current peak level of every session group on the device.
+    async fn get_group_peaks(&self, device_id: &str) -> AudioResult<Vec<GroupPeak>>;
+}
+
+/// Async counterpart of `AudioProcessLookup`.
+#[async_trait]
+pub trait AsyncAudioProcessLookup: Send + Sync {
+    /// Identifies the application that owns the specified process; a process
+    /// that cannot be inspected is reported through `AppInfo::unresolved`.
+    async fn get_app_info(&self, pid: u32) -> AppInfo;
+}
+
+/// Async counterpart of `MediaPlaybackControl`.
+#[async_trait]
+pub trait AsyncMediaPlaybackControl: Send + Sync {
+    /// Toggles playback of whichever application owns the system media
+    /// session.
+    async fn toggle_media_playback(&self) -> AudioResult<()>;
+}
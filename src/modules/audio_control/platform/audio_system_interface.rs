@@ -1,18 +1,34 @@
 use crate::modules::audio_control::{
-    models::{DeviceSound, SessionGroup},
+    models::{AppInfo, DeviceSound, GroupPeak, GroupVolumeMode, SessionGroup},
     types::{AudioResult, GroupId},
 };
 
 pub trait AudioSystemInterface:
-    AudioOutputDeviceControl + AudioSessionControl + Send + Sync
+    AudioOutputDeviceControl
+    + AudioSessionControl
+    + AudioMeteringControl
+    + AudioProcessLookup
+    + MediaPlaybackControl
+    + Send
+    + Sync
+{
+}
+impl<T> AudioSystemInterface for T where
+    T: AudioOutputDeviceControl
+        + AudioSessionControl
+        + AudioMeteringControl
+        + AudioProcessLookup
+        + MediaPlaybackControl
 {
 }
-impl<T> AudioSystemInterface for T where T: AudioOutputDeviceControl + AudioSessionControl {}
 
 pub trait AudioOutputDeviceControl: Send + Sync {
-    /// Lists all active audio output devices available on the system.
+    /// Lists the audio output devices available on the system.
     ///
-    fn list_output_devices(&self) -> AudioResult<Vec<DeviceSound>>;
+    /// Only active devices are returned unless `include_inactive` is set,
+    /// in which case disabled, unplugged and not-present devices are
+    /// listed as well, each reporting its current state.
+    fn list_output_devices(&self, include_inactive: bool) -> AudioResult<Vec<DeviceSound>>;
 
     /// Retrieves the master volume level from the system's current
     /// default audio output device.
@@ -20,17 +36,167 @@ pub trait AudioOutputDeviceControl: Send + Sync {
     /// Returns the master volume as a percentage value
     /// between 0.0 and 100.0.
     fn get_actual_volume(&self) -> AudioResult<f32>;
+
+    /// Returns the master volume of the specified output device, as a
+    /// percentage between 0.0 and 100.0.
+    fn get_device_volume(&self, device_id: &str) -> AudioResult<f32>;
+
+    /// Sets the master volume of the specified output device.
+    ///
+    /// # Arguments
+    /// * `volume` - Volume level between 0.0 and 100.0.
+    fn set_device_volume(&self, device_id: &str, volume: f32) -> AudioResult<()>;
+
+    /// Returns whether the master volume of the specified output device is
+    /// muted.
+    fn get_device_mute(&self, device_id: &str) -> AudioResult<bool>;
+
+    /// Mutes or unmutes the master volume of the specified output device,
+    /// leaving its volume level untouched.
+    fn set_device_mute(&self, device_id: &str, muted: bool) -> AudioResult<()>;
+
+    /// Makes the specified device the system default output for every role
+    /// (console, multimedia and communications).
+    fn set_default_device(&self, device_id: &str) -> AudioResult<()>;
+
+    /// Returns the volume of each channel of the specified output device,
+    /// as percentages between 0.0 and 100.0, in the device channel order.
+    fn get_device_channel_volumes(&self, device_id: &str) -> AudioResult<Vec<f32>>;
+
+    /// Sets the volume of each channel of the specified output device.
+    ///
+    /// # Arguments
+    /// * `levels` - One level per channel, between 0.0 and 100.0. Must match
+    ///   the device channel count.
+    fn set_device_channel_volumes(&self, device_id: &str, levels: &[f32]) -> AudioResult<()>;
+
+    /// Returns the id of the current default output device, if there is one.
+    fn default_device_id(&self) -> AudioResult<Option<String>> {
+        Ok(self
+            .list_output_devices(false)?
+            .into_iter()
+            .find(|device| device.is_default)
+            .map(|device| device.id))
+    }
 }
 
 pub trait AudioSessionControl: Send + Sync {
     /// Returns all audio sessions associated with the specified output device.
-    fn get_sessions_for_device(&self, device_id: &str) -> AudioResult<Vec<SessionGroup>>;
+    ///
+    /// Sessions sharing a grouping GUID are collapsed into one `SessionGroup`.
+    /// When `expand` is set, each group also lists its member sessions.
+    fn get_sessions_for_device(
+        &self,
+        device_id: &str,
+        expand: bool,
+    ) -> AudioResult<Vec<SessionGroup>>;
+
+    /// Same as `get_sessions_for_device`, identifying the application behind
+    /// each session through `processes` instead of querying the process.
+    ///
+    /// Backends that do not inspect processes themselves can keep the
+    /// default, which ignores `processes`.
+    fn get_sessions_for_device_with(
+        &self,
+        device_id: &str,
+        expand: bool,
+        processes: &dyn AudioProcessLookup,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        let _ = processes;
+        self.get_sessions_for_device(device_id, expand)
+    }
 
     /// Sets the volume of a session associated with the specified output device.
     ///
     /// # Arguments
     /// * `group_id` - Unique identifier of the session.
     /// * `volume` - Volume level between 0.0 and 100.0.
-    fn set_group_volume(&self, group_id: &GroupId, device_id: &str, volume: f32)
+    /// * `mode` - Whether every member gets `volume` or members are scaled
+    ///   so the group average becomes `volume`.
+    fn set_group_volume(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        volume: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<()>;
+
+    /// Sets the volume of a single session, identified by its session
+    /// instance identifier, leaving the rest of its group untouched.
+    ///
+    /// # Arguments
+    /// * `session_id` - Session instance identifier, as listed in the expanded view.
+    /// * `volume` - Volume level between 0.0 and 100.0.
+    fn set_session_volume(&self, device_id: &str, session_id: &str, volume: f32)
         -> AudioResult<()>;
+
+    /// Mutes or unmutes every session of a group.
+    fn set_group_mute(&self, group_id: &GroupId, device_id: &str, muted: bool) -> AudioResult<()>;
+
+    /// Mutes or unmutes a single session, leaving the rest of its group untouched.
+    fn set_session_mute(&self, device_id: &str, session_id: &str, muted: bool) -> AudioResult<()>;
+
+    /// Moves the audio of every process in a session group to another output
+    /// device. The change is persisted by the system for those executables.
+    ///
+    /// # Arguments
+    /// * `device_id` - Device the group is currently playing on.
+    /// * `target_device_id` - Device the group should play on.
+    fn move_group_to_device(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        target_device_id: &str,
+    ) -> AudioResult<()>;
+
+    /// Routes the audio output of a single process to the specified device.
+    fn set_process_output_device(&self, pid: u32, device_id: &str) -> AudioResult<()>;
+
+    /// Returns the icon of the application behind a session group, encoded
+    /// as PNG. The group is looked up on every active output device.
+    fn get_group_icon(&self, group_id: &GroupId) -> AudioResult<Vec<u8>>;
+
+    /// Returns the volume of each channel of a session group, as percentages
+    /// between 0.0 and 100.0.
+    fn get_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+    ) -> AudioResult<Vec<f32>>;
+
+    /// Sets the volume of each channel on every session of a group.
+    ///
+    /// # Arguments
+    /// * `levels` - One level per channel, between 0.0 and 100.0. Must match
+    ///   the session channel count.
+    fn set_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        levels: &[f32],
+    ) -> AudioResult<()>;
+}
+
+pub trait AudioMeteringControl: Send + Sync {
+    /// Returns the current peak sample level of the specified output device,
+    /// as a scalar between 0.0 and 1.0.
+    fn get_device_peak(&self, device_id: &str) -> AudioResult<f32>;
+
+    /// Returns the current peak sample level of every session group on the
+    /// specified output device. Each group reports the loudest of its sessions.
+    fn get_group_peaks(&self, device_id: &str) -> AudioResult<Vec<GroupPeak>>;
+}
+
+pub trait AudioProcessLookup: Send + Sync {
+    /// Identifies the application that owns the specified process.
+    ///
+    /// Never fails: a process that cannot be inspected is reported through
+    /// `AppInfo::unresolved`.
+    fn get_app_info(&self, pid: u32) -> AppInfo;
+}
+
+pub trait MediaPlaybackControl: Send + Sync {
+    /// Toggles playback of whichever application currently owns the system
+    /// media session, like the play/pause key of a keyboard.
+    fn toggle_media_playback(&self) -> AudioResult<()>;
 }
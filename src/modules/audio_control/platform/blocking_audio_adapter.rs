@@ -0,0 +1,284 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{AppInfo, DeviceSound, GroupPeak, GroupVolumeMode, SessionGroup},
+    platform::{
+        async_audio_system::{
+            AsyncAudioMeteringControl, AsyncAudioOutputDeviceControl, AsyncAudioProcessLookup,
+            AsyncAudioSessionControl, AsyncMediaPlaybackControl, ProcessCache,
+        },
+        audio_system_interface::{AudioProcessLookup, AudioSystemInterface},
+    },
+    types::{AudioResult, GroupId},
+};
+use crate::modules::core::com::ComContext;
+use crate::modules::core::executor::{BackendExecutor, BackendExecutorConfig};
+
+/// Exposes a synchronous `AudioSystemInterface` through the async traits.
+///
+/// Every call is sent to a dedicated backend thread, which keeps COM
+/// initialised for its whole life so COM objects can be reused between
+/// calls. Callers only await the reply; a call that exceeds the configured
+/// timeout fails with `AudioError::Backend`. Clones share the same backend
+/// threads.
+#[derive(Clone)]
+pub struct BlockingAudioAdapter {
+    executor: BackendExecutor<dyn AudioSystemInterface>,
+}
+
+impl BlockingAudioAdapter {
+    pub fn new(audio_system: Arc<dyn AudioSystemInterface>, config: BackendExecutorConfig) -> Self {
+        Self {
+            executor: BackendExecutor::spawn_with_init(
+                "audio-backend",
+                audio_system,
+                config,
+                keep_com_initialized,
+            ),
+        }
+    }
+
+    /// Runs `operation` against the synchronous backend on the backend thread.
+    pub async fn run<R, F>(&self, operation: F) -> AudioResult<R>
+    where
+        F: FnOnce(&(dyn AudioSystemInterface + 'static)) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.executor
+            .call(operation)
+            .await
+            .map_err(AudioError::from)
+    }
+}
+
+/// Resolves processes through `cache`, falling back to the backend.
+struct CachedProcesses<'a> {
+    cache: &'a dyn ProcessCache,
+    backend: &'a dyn AudioSystemInterface,
+}
+
+impl AudioProcessLookup for CachedProcesses<'_> {
+    fn get_app_info(&self, pid: u32) -> AppInfo {
+        self.cache.get_or_lookup(pid, self.backend)
+    }
+}
+
+/// Keeps COM initialised on the backend thread for as long as it runs.
+fn keep_com_initialized() -> Option<ComContext> {
+    ComContext::new()
+        .map_err(|error| tracing::warn!("Failed to initialize COM on backend thread: {}", error))
+        .ok()
+}
+
+#[async_trait]
+impl AsyncAudioOutputDeviceControl for BlockingAudioAdapter {
+    async fn list_output_devices(&self, include_inactive: bool) -> AudioResult<Vec<DeviceSound>> {
+        self.run(move |audio| audio.list_output_devices(include_inactive))
+            .await?
+    }
+
+    async fn get_actual_volume(&self) -> AudioResult<f32> {
+        self.run(|audio| audio.get_actual_volume()).await?
+    }
+
+    async fn get_device_volume(&self, device_id: &str) -> AudioResult<f32> {
+        let device_id = device_id.to_string();
+        self.run(move |audio| audio.get_device_volume(&device_id))
+            .await?
+    }
+
+    async fn set_device_volume(&self, device_id: &str, volume: f32) -> AudioResult<()> {
+        let device_id = device_id.to_string();
+        self.run(move |audio| audio.set_device_volume(&device_id, volume))
+            .await?
+    }
+
+    async fn get_device_mute(&self, device_id: &str) -> AudioResult<bool> {
+        let device_id = device_id.to_string();
+        self.run(move |audio| audio.get_device_mute(&device_id))
+            .await?
+    }
+
+    async fn set_device_mute(&self, device_id: &str, muted: bool) -> AudioResult<()> {
+        let device_id = device_id.to_string();
+        self.run(move |audio| audio.set_device_mute(&device_id, muted))
+            .await?
+    }
+
+    async fn set_default_device(&self, device_id: &str) -> AudioResult<()> {
+        let device_id = device_id.to_string();
+        self.run(move |audio| audio.set_default_device(&device_id))
+            .await?
+    }
+
+    async fn get_device_channel_volumes(&self, device_id: &str) -> AudioResult<Vec<f32>> {
+        let device_id = device_id.to_string();
+        self.run(move |audio| audio.get_device_channel_volumes(&device_id))
+            .await?
+    }
+
+    async fn set_device_channel_volumes(&self, device_id: &str, levels: &[f32]) -> AudioResult<()> {
+        let device_id = device_id.to_string();
+        let levels = levels.to_vec();
+        self.run(move |audio| audio.set_device_channel_volumes(&device_id, &levels))
+            .await?
+    }
+}
+
+#[async_trait]
+impl AsyncAudioSessionControl for BlockingAudioAdapter {
+    async fn get_sessions_for_device(
+        &self,
+        device_id: &str,
+        expand: bool,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        let device_id = device_id.to_string();
+        self.run(move |audio| audio.get_sessions_for_device(&device_id, expand))
+            .await?
+    }
+
+    async fn get_sessions_for_device_cached(
+        &self,
+        device_id: &str,
+        expand: bool,
+        processes: Arc<dyn ProcessCache>,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        let device_id = device_id.to_string();
+        self.run(move |audio| {
+            let processes = CachedProcesses {
+                cache: processes.as_ref(),
+                backend: audio,
+            };
+            audio.get_sessions_for_device_with(&device_id, expand, &processes)
+        })
+        .await?
+    }
+
+    async fn set_group_volume(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        volume: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<()> {
+        let (group_id, device_id) = (group_id.clone(), device_id.to_string());
+        self.run(move |audio| audio.set_group_volume(&group_id, &device_id, volume, mode))
+            .await?
+    }
+
+    async fn set_session_volume(
+        &self,
+        device_id: &str,
+        session_id: &str,
+        volume: f32,
+    ) -> AudioResult<()> {
+        let (device_id, session_id) = (device_id.to_string(), session_id.to_string());
+        self.run(move |audio| audio.set_session_volume(&device_id, &session_id, volume))
+            .await?
+    }
+
+    async fn set_group_mute(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        muted: bool,
+    ) -> AudioResult<()> {
+        let (group_id, device_id) = (group_id.clone(), device_id.to_string());
+        self.run(move |audio| audio.set_group_mute(&group_id, &device_id, muted))
+            .await?
+    }
+
+    async fn set_session_mute(
+        &self,
+        device_id: &str,
+        session_id: &str,
+        muted: bool,
+    ) -> AudioResult<()> {
+        let (device_id, session_id) = (device_id.to_string(), session_id.to_string());
+        self.run(move |audio| audio.set_session_mute(&device_id, &session_id, muted))
+            .await?
+    }
+
+    async fn move_group_to_device(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        target_device_id: &str,
+    ) -> AudioResult<()> {
+        let (group_id, device_id) = (group_id.clone(), device_id.to_string());
+        let target_device_id = target_device_id.to_string();
+        self.run(move |audio| audio.move_group_to_device(&group_id, &device_id, &target_device_id))
+            .await?
+    }
+
+    async fn set_process_output_device(&self, pid: u32, device_id: &str) -> AudioResult<()> {
+        let device_id = device_id.to_string();
+        self.run(move |audio| audio.set_process_output_device(pid, &device_id))
+            .await?
+    }
+
+    async fn get_group_icon(&self, group_id: &GroupId) -> AudioResult<Vec<u8>> {
+        let group_id = group_id.clone();
+        self.run(move |audio| audio.get_group_icon(&group_id))
+            .await?
+    }
+
+    async fn get_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+    ) -> AudioResult<Vec<f32>> {
+        let (group_id, device_id) = (group_id.clone(), device_id.to_string());
+        self.run(move |audio| audio.get_group_channel_volumes(&group_id, &device_id))
+            .await?
+    }
+
+    async fn set_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        levels: &[f32],
+    ) -> AudioResult<()> {
+        let (group_id, device_id) = (group_id.clone(), device_id.to_string());
+        let levels = levels.to_vec();
+        self.run(move |audio| audio.set_group_channel_volumes(&group_id, &device_id, &levels))
+            .await?
+    }
+}
+
+#[async_trait]
+impl AsyncAudioMeteringControl for BlockingAudioAdapter {
+    async fn get_device_peak(&self, device_id: &str) -> AudioResult<f32> {
+        let device_id = device_id.to_string();
+        self.run(move |audio| audio.get_device_peak(&device_id))
+            .await?
+    }
+
+    async fn get_group_peaks(&self, device_id: &str) -> AudioResult<Vec<GroupPeak>> {
+        let device_id = device_id.to_string();
+        self.run(move |audio| audio.get_group_peaks(&device_id))
+            .await?
+    }
+}
+
+#[async_trait]
+impl AsyncAudioProcessLookup for BlockingAudioAdapter {
+    async fn get_app_info(&self, pid: u32) -> AppInfo {
+        self.run(move |audio| audio.get_app_info(pid))
+            .await
+            .unwrap_or_else(|error| {
+                tracing::warn!("Failed to identify process {}: {}", pid, error);
+                AppInfo::unresolved(pid)
+            })
+    }
+}
+
+#[async_trait]
+impl AsyncMediaPlaybackControl for BlockingAudioAdapter {
+    async fn toggle_media_playback(&self) -> AudioResult<()> {
+        self.run(|audio| audio.toggle_media_playback()).await?
+    }
+}
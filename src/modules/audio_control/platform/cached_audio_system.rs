@@ -0,0 +1,433 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, TimeDelta};
+
+use crate::modules::audio_control::{
+    models::{
+        AppInfo, CacheCounters, DeviceSound, GroupPeak, GroupVolumeMode, LookupCacheStats,
+        SessionGroup,
+    },
+    platform::{
+        async_audio_system::{
+            AsyncAudioMeteringControl, AsyncAudioOutputDeviceControl, AsyncAudioProcessLookup,
+            AsyncAudioSessionControl, AsyncAudioSystem, AsyncMediaPlaybackControl, ProcessCache,
+        },
+        audio_system_interface::AudioProcessLookup,
+    },
+    types::{AudioResult, GroupId},
+};
+use crate::modules::core::{
+    broadcasting::{recv_event, EventReceiver, Received},
+    Clock, ServerEvent,
+};
+
+/// How long the lookups kept by `CachedAudioSystem` stay valid.
+#[derive(Debug, Clone, Copy)]
+pub struct LookupCacheConfig {
+    /// Lifetime of a device list. Devices connected or removed are noticed
+    /// by the session monitor, which drops the list earlier.
+    pub device_ttl: Duration,
+    /// Lifetime of a pid → application resolution.
+    pub process_ttl: Duration,
+}
+
+impl Default for LookupCacheConfig {
+    fn default() -> Self {
+        Self {
+            device_ttl: Duration::from_secs(5),
+            process_ttl: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Values loaded from the backend, reused until they expire or are dropped.
+struct TtlCache<K, V> {
+    ttl: TimeDelta,
+    entries: Mutex<HashMap<K, (NaiveDateTime, V)>>,
+    /// Bumped on every invalidation, so a load that raced with one is not
+    /// stored.
+    generation: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl: TimeDelta::from_std(ttl).unwrap_or(TimeDelta::MAX),
+            entries: Mutex::new(HashMap::new()),
+            generation: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<K, (NaiveDateTime, V)>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn is_fresh(&self, loaded_at: NaiveDateTime, now: NaiveDateTime) -> bool {
+        now - loaded_at < self.ttl
+    }
+
+    /// Returns the value cached for `key` while it is fresh.
+    fn get(&self, key: &K, now: NaiveDateTime) -> Option<V> {
+        if let Some((loaded_at, value)) = self.lock().get(key) {
+            if self.is_fresh(*loaded_at, now) {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Some(value.clone());
+            }
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+
+    /// Current generation, to be handed back to `insert` after a load.
+    fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Stores a value loaded at `generation`, unless an invalidation came in
+    /// meanwhile.
+    fn insert(&self, key: K, now: NaiveDateTime, value: V, generation: u64) {
+        let mut entries = self.lock();
+        if self.generation.load(Ordering::SeqCst) == generation {
+            entries.insert(key, (now, value));
+        }
+    }
+
+    /// Returns the value cached for `key`, loading it when missing or
+    /// expired.
+    fn get_or_load(&self, key: K, now: NaiveDateTime, load: impl FnOnce() -> V) -> V {
+        if let Some(value) = self.get(&key, now) {
+            return value;
+        }
+        let generation = self.generation();
+        let value = load();
+        self.insert(key, now, value.clone(), generation);
+        value
+    }
+
+    fn remove(&self, key: &K) {
+        let mut entries = self.lock();
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        entries.remove(key);
+    }
+
+    fn clear(&self) {
+        let mut entries = self.lock();
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        entries.clear();
+    }
+
+    fn counters(&self, now: NaiveDateTime) -> CacheCounters {
+        let entries = self
+            .lock()
+            .values()
+            .filter(|(loaded_at, _)| self.is_fresh(*loaded_at, now))
+            .count();
+        CacheCounters {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries,
+        }
+    }
+}
+
+struct LookupCaches {
+    clock: Arc<dyn Clock>,
+    /// Keyed by `include_inactive`.
+    devices: TtlCache<bool, Vec<DeviceSound>>,
+    processes: TtlCache<u32, AppInfo>,
+}
+
+/// Decorates an `AsyncAudioSystem`, caching device lists and the
+/// application behind each session process.
+///
+/// Listing sessions normally opens every process that owns a session; here
+/// each pid is resolved once per `process_ttl`. Device lists are reused for
+/// `device_ttl`, dropped when the default device changes through this
+/// decorator and, once `run` is listening, when the session monitor reports
+/// a device connected or removed or a new default device. Volumes, mute
+/// states and sessions are always read from the backend.
+pub struct CachedAudioSystem {
+    inner: Arc<dyn AsyncAudioSystem>,
+    caches: Arc<LookupCaches>,
+    /// When unset, device lists always come from `inner`.
+    cache_devices: bool,
+}
+
+impl CachedAudioSystem {
+    pub fn new(
+        inner: Arc<dyn AsyncAudioSystem>,
+        config: LookupCacheConfig,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            inner,
+            caches: Arc::new(LookupCaches {
+                clock,
+                devices: TtlCache::new(config.device_ttl),
+                processes: TtlCache::new(config.process_ttl),
+            }),
+            cache_devices: true,
+        }
+    }
+
+    /// A view sharing the process cache that always lists devices from the
+    /// backend, for the session monitor that detects device changes.
+    pub fn with_live_devices(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            caches: Arc::clone(&self.caches),
+            cache_devices: false,
+        }
+    }
+
+    pub fn stats(&self) -> LookupCacheStats {
+        let now = self.caches.clock.now();
+        LookupCacheStats {
+            devices: self.caches.devices.counters(now),
+            processes: self.caches.processes.counters(now),
+        }
+    }
+
+    /// Drops cached lookups made stale by the events, until the broadcaster
+    /// is closed.
+    pub async fn run(self: Arc<Self>, mut events: EventReceiver) {
+        while let Some(received) = recv_event(&mut events, "Lookup cache").await {
+            match received {
+                Received::Event(event) => self.invalidate(&event),
+                // Changes may have been missed; start over
+                Received::Lagged => {
+                    self.caches.devices.clear();
+                    self.caches.processes.clear();
+                }
+            }
+        }
+    }
+
+    pub fn invalidate(&self, event: &ServerEvent) {
+        match event {
+            ServerEvent::DeviceConnected { .. }
+            | ServerEvent::DeviceDisconnected { .. }
+            | ServerEvent::DefaultDeviceChanged { .. } => {
+                self.caches.devices.clear();
+            }
+            // The process may be gone and its pid reused by another one
+            ServerEvent::SessionEnded { pid, .. } => {
+                self.caches.processes.remove(pid);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ProcessCache for LookupCaches {
+    fn get_or_lookup(&self, pid: u32, processes: &dyn AudioProcessLookup) -> AppInfo {
+        self.processes
+            .get_or_load(pid, self.clock.now(), || processes.get_app_info(pid))
+    }
+}
+
+#[async_trait]
+impl AsyncAudioProcessLookup for CachedAudioSystem {
+    async fn get_app_info(&self, pid: u32) -> AppInfo {
+        let now = self.caches.clock.now();
+        if let Some(app) = self.caches.processes.get(&pid, now) {
+            return app;
+        }
+        let generation = self.caches.processes.generation();
+        let app = self.inner.get_app_info(pid).await;
+        self.caches
+            .processes
+            .insert(pid, now, app.clone(), generation);
+        app
+    }
+}
+
+#[async_trait]
+impl AsyncAudioOutputDeviceControl for CachedAudioSystem {
+    async fn list_output_devices(&self, include_inactive: bool) -> AudioResult<Vec<DeviceSound>> {
+        if !self.cache_devices {
+            return self.inner.list_output_devices(include_inactive).await;
+        }
+        let now = self.caches.clock.now();
+        if let Some(devices) = self.caches.devices.get(&include_inactive, now) {
+            return Ok(devices);
+        }
+        let generation = self.caches.devices.generation();
+        let devices = self.inner.list_output_devices(include_inactive).await?;
+        self.caches
+            .devices
+            .insert(include_inactive, now, devices.clone(), generation);
+        Ok(devices)
+    }
+
+    async fn get_actual_volume(&self) -> AudioResult<f32> {
+        self.inner.get_actual_volume().await
+    }
+
+    async fn get_device_volume(&self, device_id: &str) -> AudioResult<f32> {
+        self.inner.get_device_volume(device_id).await
+    }
+
+    async fn set_device_volume(&self, device_id: &str, volume: f32) -> AudioResult<()> {
+        self.inner.set_device_volume(device_id, volume).await
+    }
+
+    async fn get_device_mute(&self, device_id: &str) -> AudioResult<bool> {
+        self.inner.get_device_mute(device_id).await
+    }
+
+    async fn set_device_mute(&self, device_id: &str, muted: bool) -> AudioResult<()> {
+        self.inner.set_device_mute(device_id, muted).await
+    }
+
+    async fn set_default_device(&self, device_id: &str) -> AudioResult<()> {
+        let result = self.inner.set_default_device(device_id).await;
+        // Cached lists carry the old `is_default` flags
+        self.caches.devices.clear();
+        result
+    }
+
+    async fn get_device_channel_volumes(&self, device_id: &str) -> AudioResult<Vec<f32>> {
+        self.inner.get_device_channel_volumes(device_id).await
+    }
+
+    async fn set_device_channel_volumes(&self, device_id: &str, levels: &[f32]) -> AudioResult<()> {
+        self.inner
+            .set_device_channel_volumes(device_id, levels)
+            .await
+    }
+}
+
+#[async_trait]
+impl AsyncAudioSessionControl for CachedAudioSystem {
+    async fn get_sessions_for_device(
+        &self,
+        device_id: &str,
+        expand: bool,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        self.inner
+            .get_sessions_for_device_cached(device_id, expand, self.caches.clone())
+            .await
+    }
+
+    async fn get_sessions_for_device_cached(
+        &self,
+        device_id: &str,
+        expand: bool,
+        processes: Arc<dyn ProcessCache>,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        self.inner
+            .get_sessions_for_device_cached(device_id, expand, processes)
+            .await
+    }
+
+    async fn set_group_volume(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        volume: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<()> {
+        self.inner
+            .set_group_volume(group_id, device_id, volume, mode)
+            .await
+    }
+
+    async fn set_session_volume(
+        &self,
+        device_id: &str,
+        session_id: &str,
+        volume: f32,
+    ) -> AudioResult<()> {
+        self.inner
+            .set_session_volume(device_id, session_id, volume)
+            .await
+    }
+
+    async fn set_group_mute(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        muted: bool,
+    ) -> AudioResult<()> {
+        self.inner.set_group_mute(group_id, device_id, muted).await
+    }
+
+    async fn set_session_mute(
+        &self,
+        device_id: &str,
+        session_id: &str,
+        muted: bool,
+    ) -> AudioResult<()> {
+        self.inner
+            .set_session_mute(device_id, session_id, muted)
+            .await
+    }
+
+    async fn move_group_to_device(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        target_device_id: &str,
+    ) -> AudioResult<()> {
+        self.inner
+            .move_group_to_device(group_id, device_id, target_device_id)
+            .await
+    }
+
+    async fn set_process_output_device(&self, pid: u32, device_id: &str) -> AudioResult<()> {
+        self.inner.set_process_output_device(pid, device_id).await
+    }
+
+    async fn get_group_icon(&self, group_id: &GroupId) -> AudioResult<Vec<u8>> {
+        self.inner.get_group_icon(group_id).await
+    }
+
+    async fn get_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+    ) -> AudioResult<Vec<f32>> {
+        self.inner
+            .get_group_channel_volumes(group_id, device_id)
+            .await
+    }
+
+    async fn set_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        levels: &[f32],
+    ) -> AudioResult<()> {
+        self.inner
+            .set_group_channel_volumes(group_id, device_id, levels)
+            .await
+    }
+}
+
+#[async_trait]
+impl AsyncAudioMeteringControl for CachedAudioSystem {
+    async fn get_device_peak(&self, device_id: &str) -> AudioResult<f32> {
+        self.inner.get_device_peak(device_id).await
+    }
+
+    async fn get_group_peaks(&self, device_id: &str) -> AudioResult<Vec<GroupPeak>> {
+        self.inner.get_group_peaks(device_id).await
+    }
+}
+
+#[async_trait]
+impl AsyncMediaPlaybackControl for CachedAudioSystem {
+    async fn toggle_media_playback(&self) -> AudioResult<()> {
+        self.inner.toggle_media_playback().await
+    }
+}
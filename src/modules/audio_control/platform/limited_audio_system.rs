@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+
+use crate::modules::audio_control::{
+    models::{AppInfo, DeviceSound, GroupPeak, GroupVolumeMode, SessionGroup, SessionMember},
+    platform::async_audio_system::{
+        AsyncAudioMeteringControl, AsyncAudioOutputDeviceControl, AsyncAudioProcessLookup,
+        AsyncAudioSessionControl, AsyncAudioSystem, AsyncMediaPlaybackControl, ProcessCache,
+    },
+    services::VolumeLimitService,
+    types::{AudioResult, GroupId},
+};
+
+/// Backend that caps every volume write with `VolumeLimitService::cap_*`.
+///
+/// Profiles, undo/redo and the automations write through it. Client requests
+/// do not: their handlers already apply every limit with `limit_*` and write
+/// to the backend below, so each write is capped once. `max_step` does not
+/// apply here, since ducking and the sleep timer must restore volumes at once.
+///
+/// Application caps need the group or session being written. They are taken
+/// from the last listing of the device that went through this backend; only
+/// a target missing from it costs a new listing.
+pub struct LimitedAudioSystem {
+    inner: Arc<dyn AsyncAudioSystem>,
+    limits: Arc<VolumeLimitService>,
+    known: Mutex<KnownTargets>,
+}
+
+/// Groups and sessions seen in the last listing of each device.
+#[derive(Default)]
+struct KnownTargets {
+    groups: HashMap<(String, GroupId), SessionGroup>,
+    sessions: HashMap<(String, String), SessionMember>,
+}
+
+impl LimitedAudioSystem {
+    pub fn new(inner: Arc<dyn AsyncAudioSystem>, limits: Arc<VolumeLimitService>) -> Self {
+        Self {
+            inner,
+            limits,
+            known: Mutex::new(KnownTargets::default()),
+        }
+    }
+
+    fn known(&self) -> MutexGuard<'_, KnownTargets> {
+        self.known.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Replaces what is known about `device_id` with a fresh listing.
+    fn remember(&self, device_id: &str, groups: &[SessionGroup], expand: bool) {
+        let mut known = self.known();
+        known.groups.retain(|(device, _), _| device != device_id);
+        if expand {
+            known.sessions.retain(|(device, _), _| device != device_id);
+        }
+        for group in groups {
+            if expand {
+                for member in group.sessions.iter().flatten() {
+                    known.sessions.insert(
+                        (device_id.to_string(), member.session_id.clone()),
+                        member.clone(),
+                    );
+                }
+            }
+            known.groups.insert(
+                (device_id.to_string(), group.id.clone()),
+                SessionGroup {
+                    sessions: None,
+                    ..group.clone()
+                },
+            );
+        }
+    }
+
+    async fn group(
+        &self,
+        device_id: &str,
+        group_id: &GroupId,
+    ) -> AudioResult<Option<SessionGroup>> {
+        let key = (device_id.to_string(), group_id.clone());
+        if let Some(group) = self.known().groups.get(&key) {
+            return Ok(Some(group.clone()));
+        }
+        self.get_sessions_for_device(device_id, false).await?;
+        Ok(self.known().groups.get(&key).cloned())
+    }
+
+    async fn session(
+        &self,
+        device_id: &str,
+        session_id: &str,
+    ) -> AudioResult<Option<SessionMember>> {
+        let key = (device_id.to_string(), session_id.to_string());
+        if let Some(member) = self.known().sessions.get(&key) {
+            return Ok(Some(member.clone()));
+        }
+        self.get_sessions_for_device(device_id, true).await?;
+        Ok(self.known().sessions.get(&key).cloned())
+    }
+
+    /// Keeps a remembered group in step with a volume written to it, so the
+    /// next cap sees the new balance between its sessions.
+    fn written(&self, device_id: &str, group_id: &GroupId, volume: f32, mode: GroupVolumeMode) {
+        let key = (device_id.to_string(), group_id.clone());
+        if let Some(group) = self.known().groups.get_mut(&key) {
+            group.member_volumes = mode.member_targets(&group.member_volumes, volume);
+            group.volume_level = volume;
+            group.volume_max = group.member_volumes.iter().copied().fold(volume, f32::max);
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncAudioProcessLookup for LimitedAudioSystem {
+    async fn get_app_info(&self, pid: u32) -> AppInfo {
+        self.inner.get_app_info(pid).await
+    }
+}
+
+#[async_trait]
+impl AsyncAudioOutputDeviceControl for LimitedAudioSystem {
+    async fn list_output_devices(&self, include_inactive: bool) -> AudioResult<Vec<DeviceSound>> {
+        self.inner.list_output_devices(include_inactive).await
+    }
+
+    async fn get_actual_volume(&self) -> AudioResult<f32> {
+        self.inner.get_actual_volume().await
+    }
+
+    async fn get_device_volume(&self, device_id: &str) -> AudioResult<f32> {
+        self.inner.get_device_volume(device_id).await
+    }
+
+    async fn set_device_volume(&self, device_id: &str, volume: f32) -> AudioResult<()> {
+        let volume = self.limits.cap_master(volume)?;
+        self.inner.set_device_volume(device_id, volume).await
+    }
+
+    async fn get_device_mute(&self, device_id: &str) -> AudioResult<bool> {
+        self.inner.get_device_mute(device_id).await
+    }
+
+    async fn set_device_mute(&self, device_id: &str, muted: bool) -> AudioResult<()> {
+        self.inner.set_device_mute(device_id, muted).await
+    }
+
+    async fn set_default_device(&self, device_id: &str) -> AudioResult<()> {
+        self.inner.set_default_device(device_id).await
+    }
+
+    async fn get_device_channel_volumes(&self, device_id: &str) -> AudioResult<Vec<f32>> {
+        self.inner.get_device_channel_volumes(device_id).await
+    }
+
+    async fn set_device_channel_volumes(&self, device_id: &str, levels: &[f32]) -> AudioResult<()> {
+        let levels = self.limits.cap_device_channels(levels.to_vec())?;
+        self.inner
+            .set_device_channel_volumes(device_id, &levels)
+            .await
+    }
+}
+
+#[async_trait]
+impl AsyncAudioSessionControl for LimitedAudioSystem {
+    async fn get_sessions_for_device(
+        &self,
+        device_id: &str,
+        expand: bool,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        let groups = self
+            .inner
+            .get_sessions_for_device(device_id, expand)
+            .await?;
+        self.remember(device_id, &groups, expand);
+        Ok(groups)
+    }
+
+    async fn get_sessions_for_device_cached(
+        &self,
+        device_id: &str,
+        expand: bool,
+        processes: Arc<dyn ProcessCache>,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        let groups = self
+            .inner
+            .get_sessions_for_device_cached(device_id, expand, processes)
+            .await?;
+        self.remember(device_id, &groups, expand);
+        Ok(groups)
+    }
+
+    async fn set_group_volume(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        volume: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<()> {
+        if !self.limits.has_application_caps() {
+            return self
+                .inner
+                .set_group_volume(group_id, device_id, volume, mode)
+                .await;
+        }
+        let volume = match self.group(device_id, group_id).await? {
+            Some(group) => self.limits.cap_group(&group, volume, mode)?,
+            None => volume,
+        };
+        self.inner
+            .set_group_volume(group_id, device_id, volume, mode)
+            .await?;
+        self.written(device_id, group_id, volume, mode);
+        Ok(())
+    }
+
+    async fn set_session_volume(
+        &self,
+        device_id: &str,
+        session_id: &str,
+        volume: f32,
+    ) -> AudioResult<()> {
+        if !self.limits.has_application_caps() {
+            return self
+                .inner
+                .set_session_volume(device_id, session_id, volume)
+                .await;
+        }
+        let volume = match self.session(device_id, session_id).await? {
+            Some(member) => self.limits.cap_session(&member, volume)?,
+            None => volume,
+        };
+        self.inner
+            .set_session_volume(device_id, session_id, volume)
+            .await?;
+        // The balance of the session's group changed; list it again when
+        // it is written next
+        self.known()
+            .groups
+            .retain(|(device, _), _| device != device_id);
+        Ok(())
+    }
+
+    async fn set_group_mute(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        muted: bool,
+    ) -> AudioResult<()> {
+        self.inner.set_group_mute(group_id, device_id, muted).await
+    }
+
+    async fn set_session_mute(
+        &self,
+        device_id: &str,
+        session_id: &str,
+        muted: bool,
+    ) -> AudioResult<()> {
+        self.inner
+            .set_session_mute(device_id, session_id, muted)
+            .await
+    }
+
+    async fn move_group_to_device(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        target_device_id: &str,
+    ) -> AudioResult<()> {
+        self.inner
+            .move_group_to_device(group_id, device_id, target_device_id)
+            .await
+    }
+
+    async fn set_process_output_device(&self, pid: u32, device_id: &str) -> AudioResult<()> {
+        self.inner.set_process_output_device(pid, device_id).await
+    }
+
+    async fn get_group_icon(&self, group_id: &GroupId) -> AudioResult<Vec<u8>> {
+        self.inner.get_group_icon(group_id).await
+    }
+
+    async fn get_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+    ) -> AudioResult<Vec<f32>> {
+        self.inner
+            .get_group_channel_volumes(group_id, device_id)
+            .await
+    }
+
+    async fn set_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        levels: &[f32],
+    ) -> AudioResult<()> {
+        if !self.limits.has_application_caps() {
+            return self
+                .inner
+                .set_group_channel_volumes(group_id, device_id, levels)
+                .await;
+        }
+        let levels = match self.group(device_id, group_id).await? {
+            Some(group) => self.limits.cap_group_channels(&group, levels.to_vec())?,
+            None => levels.to_vec(),
+        };
+        self.inner
+            .set_group_channel_volumes(group_id, device_id, &levels)
+            .await
+    }
+}
+
+#[async_trait]
+impl AsyncAudioMeteringControl for LimitedAudioSystem {
+    async fn get_device_peak(&self, device_id: &str) -> AudioResult<f32> {
+        self.inner.get_device_peak(device_id).await
+    }
+
+    async fn get_group_peaks(&self, device_id: &str) -> AudioResult<Vec<GroupPeak>> {
+        self.inner.get_group_peaks(device_id).await
+    }
+}
+
+#[async_trait]
+impl AsyncMediaPlaybackControl for LimitedAudioSystem {
+    async fn toggle_media_playback(&self) -> AudioResult<()> {
+        self.inner.toggle_media_playback().await
+    }
+}
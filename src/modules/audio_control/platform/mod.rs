@@ -1,2 +1,6 @@
+pub mod async_audio_system;
 pub mod audio_system_interface;
+pub mod blocking_audio_adapter;
+pub mod cached_audio_system;
+pub mod limited_audio_system;
 pub mod windows_audio_adapter;
@@ -1,13 +1,18 @@
 use crate::modules::audio_control::{
-    models::{DeviceSound, SessionGroup},
-    platform::audio_system_interface::{AudioOutputDeviceControl, AudioSessionControl},
+    models::{AppInfo, DeviceSound, GroupPeak, GroupVolumeMode, SessionGroup},
+    platform::audio_system_interface::{
+        AudioMeteringControl, AudioOutputDeviceControl, AudioProcessLookup, AudioSessionControl,
+        MediaPlaybackControl,
+    },
     services as windows_audio_service,
     types::{AudioResult, GroupId},
+    utils,
 };
 
 pub struct WindowsAudioAdapter {
     devices_control: WindowsAudioDeviceControl,
     audio_session: WindowsAudioSession,
+    audio_meter: WindowsAudioMeter,
 }
 
 impl WindowsAudioAdapter {
@@ -15,23 +20,68 @@ impl WindowsAudioAdapter {
         Self {
             devices_control: WindowsAudioDeviceControl::new(),
             audio_session: WindowsAudioSession::new(),
+            audio_meter: WindowsAudioMeter::new(),
         }
     }
 }
 
 impl AudioOutputDeviceControl for WindowsAudioAdapter {
-    fn list_output_devices(&self) -> AudioResult<Vec<DeviceSound>> {
-        self.devices_control.list_output_devices()
+    fn list_output_devices(&self, include_inactive: bool) -> AudioResult<Vec<DeviceSound>> {
+        self.devices_control.list_output_devices(include_inactive)
     }
 
     fn get_actual_volume(&self) -> AudioResult<f32> {
         self.devices_control.get_actual_volume()
     }
+
+    fn get_device_volume(&self, device_id: &str) -> AudioResult<f32> {
+        self.devices_control.get_device_volume(device_id)
+    }
+
+    fn set_device_volume(&self, device_id: &str, volume: f32) -> AudioResult<()> {
+        self.devices_control.set_device_volume(device_id, volume)
+    }
+
+    fn get_device_mute(&self, device_id: &str) -> AudioResult<bool> {
+        self.devices_control.get_device_mute(device_id)
+    }
+
+    fn set_device_mute(&self, device_id: &str, muted: bool) -> AudioResult<()> {
+        self.devices_control.set_device_mute(device_id, muted)
+    }
+
+    fn set_default_device(&self, device_id: &str) -> AudioResult<()> {
+        self.devices_control.set_default_device(device_id)
+    }
+
+    fn get_device_channel_volumes(&self, device_id: &str) -> AudioResult<Vec<f32>> {
+        self.devices_control.get_device_channel_volumes(device_id)
+    }
+
+    fn set_device_channel_volumes(&self, device_id: &str, levels: &[f32]) -> AudioResult<()> {
+        self.devices_control
+            .set_device_channel_volumes(device_id, levels)
+    }
 }
 
 impl AudioSessionControl for WindowsAudioAdapter {
-    fn get_sessions_for_device(&self, device_id: &str) -> AudioResult<Vec<SessionGroup>> {
-        self.audio_session.get_sessions_for_device(device_id)
+    fn get_sessions_for_device(
+        &self,
+        device_id: &str,
+        expand: bool,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        self.audio_session
+            .get_sessions_for_device(device_id, expand)
+    }
+
+    fn get_sessions_for_device_with(
+        &self,
+        device_id: &str,
+        expand: bool,
+        processes: &dyn AudioProcessLookup,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        self.audio_session
+            .get_sessions_for_device_with(device_id, expand, processes)
     }
 
     fn set_group_volume(
@@ -39,9 +89,89 @@ impl AudioSessionControl for WindowsAudioAdapter {
         group_id: &GroupId,
         device_id: &str,
         volume: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<()> {
+        self.audio_session
+            .set_group_volume(group_id, device_id, volume, mode)
+    }
+
+    fn set_session_volume(
+        &self,
+        device_id: &str,
+        session_id: &str,
+        volume: f32,
+    ) -> AudioResult<()> {
+        self.audio_session
+            .set_session_volume(device_id, session_id, volume)
+    }
+
+    fn set_group_mute(&self, group_id: &GroupId, device_id: &str, muted: bool) -> AudioResult<()> {
+        self.audio_session
+            .set_group_mute(group_id, device_id, muted)
+    }
+
+    fn set_session_mute(&self, device_id: &str, session_id: &str, muted: bool) -> AudioResult<()> {
+        self.audio_session
+            .set_session_mute(device_id, session_id, muted)
+    }
+
+    fn move_group_to_device(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        target_device_id: &str,
+    ) -> AudioResult<()> {
+        self.audio_session
+            .move_group_to_device(group_id, device_id, target_device_id)
+    }
+
+    fn set_process_output_device(&self, pid: u32, device_id: &str) -> AudioResult<()> {
+        self.audio_session.set_process_output_device(pid, device_id)
+    }
+
+    fn get_group_icon(&self, group_id: &GroupId) -> AudioResult<Vec<u8>> {
+        self.audio_session.get_group_icon(group_id)
+    }
+
+    fn get_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+    ) -> AudioResult<Vec<f32>> {
+        self.audio_session
+            .get_group_channel_volumes(group_id, device_id)
+    }
+
+    fn set_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        levels: &[f32],
     ) -> AudioResult<()> {
         self.audio_session
-            .set_group_volume(group_id, device_id, volume)
+            .set_group_channel_volumes(group_id, device_id, levels)
+    }
+}
+
+impl AudioProcessLookup for WindowsAudioAdapter {
+    fn get_app_info(&self, pid: u32) -> AppInfo {
+        WindowsProcessLookup.get_app_info(pid)
+    }
+}
+
+impl AudioMeteringControl for WindowsAudioAdapter {
+    fn get_device_peak(&self, device_id: &str) -> AudioResult<f32> {
+        self.audio_meter.get_device_peak(device_id)
+    }
+
+    fn get_group_peaks(&self, device_id: &str) -> AudioResult<Vec<GroupPeak>> {
+        self.audio_meter.get_group_peaks(device_id)
+    }
+}
+
+impl MediaPlaybackControl for WindowsAudioAdapter {
+    fn toggle_media_playback(&self) -> AudioResult<()> {
+        windows_audio_service::send_media_play_pause()
     }
 }
 
@@ -54,13 +184,41 @@ impl WindowsAudioDeviceControl {
 }
 
 impl AudioOutputDeviceControl for WindowsAudioDeviceControl {
-    fn list_output_devices(&self) -> AudioResult<Vec<DeviceSound>> {
-        windows_audio_service::list_output_devices()
+    fn list_output_devices(&self, include_inactive: bool) -> AudioResult<Vec<DeviceSound>> {
+        windows_audio_service::list_output_devices(include_inactive)
     }
 
     fn get_actual_volume(&self) -> AudioResult<f32> {
         windows_audio_service::get_actual_volume()
     }
+
+    fn get_device_volume(&self, device_id: &str) -> AudioResult<f32> {
+        windows_audio_service::get_device_volume(device_id)
+    }
+
+    fn set_device_volume(&self, device_id: &str, volume: f32) -> AudioResult<()> {
+        windows_audio_service::set_device_volume(device_id, volume)
+    }
+
+    fn get_device_mute(&self, device_id: &str) -> AudioResult<bool> {
+        windows_audio_service::get_device_mute(device_id)
+    }
+
+    fn set_device_mute(&self, device_id: &str, muted: bool) -> AudioResult<()> {
+        windows_audio_service::set_device_mute(device_id, muted)
+    }
+
+    fn set_default_device(&self, device_id: &str) -> AudioResult<()> {
+        windows_audio_service::set_default_device(device_id)
+    }
+
+    fn get_device_channel_volumes(&self, device_id: &str) -> AudioResult<Vec<f32>> {
+        windows_audio_service::get_device_channel_volumes(device_id)
+    }
+
+    fn set_device_channel_volumes(&self, device_id: &str, levels: &[f32]) -> AudioResult<()> {
+        windows_audio_service::set_device_channel_volumes(device_id, levels)
+    }
 }
 
 struct WindowsAudioSession;
@@ -72,8 +230,21 @@ impl WindowsAudioSession {
 }
 
 impl AudioSessionControl for WindowsAudioSession {
-    fn get_sessions_for_device(&self, device_id: &str) -> AudioResult<Vec<SessionGroup>> {
-        windows_audio_service::get_session_for_device(device_id)
+    fn get_sessions_for_device(
+        &self,
+        device_id: &str,
+        expand: bool,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        windows_audio_service::get_session_for_device(device_id, expand, &WindowsProcessLookup)
+    }
+
+    fn get_sessions_for_device_with(
+        &self,
+        device_id: &str,
+        expand: bool,
+        processes: &dyn AudioProcessLookup,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        windows_audio_service::get_session_for_device(device_id, expand, processes)
     }
 
     fn set_group_volume(
@@ -81,7 +252,86 @@ impl AudioSessionControl for WindowsAudioSession {
         group_id: &GroupId,
         device_id: &str,
         volume: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<()> {
+        windows_audio_service::set_group_volume(group_id, device_id, volume, mode)
+    }
+
+    fn set_session_volume(
+        &self,
+        device_id: &str,
+        session_id: &str,
+        volume: f32,
+    ) -> AudioResult<()> {
+        windows_audio_service::set_session_volume(device_id, session_id, volume)
+    }
+
+    fn set_group_mute(&self, group_id: &GroupId, device_id: &str, muted: bool) -> AudioResult<()> {
+        windows_audio_service::set_group_mute(group_id, device_id, muted)
+    }
+
+    fn set_session_mute(&self, device_id: &str, session_id: &str, muted: bool) -> AudioResult<()> {
+        windows_audio_service::set_session_mute(device_id, session_id, muted)
+    }
+
+    fn move_group_to_device(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        target_device_id: &str,
+    ) -> AudioResult<()> {
+        windows_audio_service::move_group_to_device(group_id, device_id, target_device_id)
+    }
+
+    fn set_process_output_device(&self, pid: u32, device_id: &str) -> AudioResult<()> {
+        windows_audio_service::set_process_output_device(pid, device_id)
+    }
+
+    fn get_group_icon(&self, group_id: &GroupId) -> AudioResult<Vec<u8>> {
+        windows_audio_service::get_group_icon(group_id)
+    }
+
+    fn get_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+    ) -> AudioResult<Vec<f32>> {
+        windows_audio_service::get_group_channel_volumes(group_id, device_id)
+    }
+
+    fn set_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        levels: &[f32],
     ) -> AudioResult<()> {
-        windows_audio_service::set_group_volume(group_id, device_id, volume)
+        windows_audio_service::set_group_channel_volumes(group_id, device_id, levels)
+    }
+}
+
+/// Opens the process and reads its executable on every lookup.
+struct WindowsProcessLookup;
+
+impl AudioProcessLookup for WindowsProcessLookup {
+    fn get_app_info(&self, pid: u32) -> AppInfo {
+        utils::get_app_info(pid)
+    }
+}
+
+struct WindowsAudioMeter;
+
+impl WindowsAudioMeter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl AudioMeteringControl for WindowsAudioMeter {
+    fn get_device_peak(&self, device_id: &str) -> AudioResult<f32> {
+        windows_audio_service::get_device_peak(device_id)
+    }
+
+    fn get_group_peaks(&self, device_id: &str) -> AudioResult<Vec<GroupPeak>> {
+        windows_audio_service::get_group_peaks(device_id)
     }
 }
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, TimeDelta};
+
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{GroupVolumeMode, RuleAction},
+    platform::async_audio_system::AsyncAudioSystem,
+    services::{
+        application_service::{set_application_mute, set_application_volume},
+        Notifier, ProfileService,
+    },
+    types::AudioResult,
+};
+use crate::modules::core::Clock;
+
+/// Intervalo entre os passos de um fade
+const FADE_STEP_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Fade do volume master em andamento
+#[derive(Debug, Clone)]
+struct ActiveFade {
+    from: f32,
+    to: f32,
+    started_at: NaiveDateTime,
+    duration: TimeDelta,
+}
+
+/// Executa as ações de automação (regras, agendamentos)
+///
+/// Ações instantâneas são aplicadas na hora; fades ficam registrados e
+/// avançam conforme o relógio, a cada passo de `run` ou `advance_fades`.
+pub struct ActionExecutor {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    notifier: Notifier,
+    profiles: Arc<ProfileService>,
+    clock: Arc<dyn Clock>,
+    /// Fades por dispositivo; um novo fade substitui o anterior
+    fades: Mutex<HashMap<String, ActiveFade>>,
+}
+
+impl ActionExecutor {
+    pub fn new(
+        audio_system: Arc<dyn AsyncAudioSystem>,
+        notifier: Notifier,
+        profiles: Arc<ProfileService>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            audio_system,
+            notifier,
+            profiles,
+            clock,
+            fades: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Executa uma ação
+    ///
+    /// `source` identifica quem pediu a ação (título padrão das
+    /// notificações) e `triggering_app` é a aplicação usada quando a ação
+    /// não informa `process_name`.
+    pub async fn execute(
+        &self,
+        action: &RuleAction,
+        source: &str,
+        triggering_app: Option<&str>,
+    ) -> AudioResult<()> {
+        let target_app = |process_name: &Option<String>| {
+            process_name
+                .as_deref()
+                .or(triggering_app)
+                .map(str::to_string)
+                .ok_or_else(|| AudioError::InvalidRequestBody {
+                    message: "No application to act on".to_string(),
+                })
+        };
+
+        match action {
+            RuleAction::SetMasterVolume { device_id, volume } => {
+                let device_id = self.resolve_device(device_id.as_deref()).await?;
+                self.cancel_fade(&device_id);
+                self.audio_system
+                    .set_device_volume(&device_id, *volume)
+                    .await
+            }
+            RuleAction::FadeMasterVolume {
+                device_id,
+                volume,
+                duration_secs,
+            } => {
+                let device_id = self.resolve_device(device_id.as_deref()).await?;
+                self.start_fade(&device_id, *volume, Duration::from_secs(*duration_secs))
+                    .await
+            }
+            RuleAction::SetApplicationVolume {
+                process_name,
+                volume,
+            } => set_application_volume(
+                self.audio_system.as_ref(),
+                &target_app(process_name)?,
+                *volume,
+                GroupVolumeMode::Uniform,
+            )
+            .await
+            .map(|_| ()),
+            RuleAction::MuteApplication {
+                process_name,
+                muted,
+            } => set_application_mute(
+                self.audio_system.as_ref(),
+                &target_app(process_name)?,
+                *muted,
+            )
+            .await
+            .map(|_| ()),
+            RuleAction::SetDefaultDevice { device_id } => {
+                self.audio_system.set_default_device(device_id).await
+            }
+            RuleAction::ApplyProfile { name } => self.profiles.apply(name).await.map(|_| ()),
+            RuleAction::Notify { title, message } => {
+                self.notifier.notify(
+                    title.clone().unwrap_or_else(|| source.to_string()),
+                    message.clone(),
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Inicia um fade linear do volume master até `volume`
+    ///
+    /// Duração zero aplica o volume imediatamente.
+    pub async fn start_fade(
+        &self,
+        device_id: &str,
+        volume: f32,
+        duration: Duration,
+    ) -> AudioResult<()> {
+        let from = self.audio_system.get_device_volume(device_id).await?;
+        let duration = TimeDelta::from_std(duration).unwrap_or(TimeDelta::MAX);
+        if duration <= TimeDelta::zero() {
+            self.cancel_fade(device_id);
+            return self.audio_system.set_device_volume(device_id, volume).await;
+        }
+
+        self.lock_fades().insert(
+            device_id.to_string(),
+            ActiveFade {
+                from,
+                to: volume,
+                started_at: self.clock.now(),
+                duration,
+            },
+        );
+        Ok(())
+    }
+
+    /// Interrompe o fade de um dispositivo, mantendo o volume atual
+    pub fn cancel_fade(&self, device_id: &str) -> bool {
+        self.lock_fades().remove(device_id).is_some()
+    }
+
+    /// Aplica o volume de cada fade no horário atual e encerra os concluídos
+    pub async fn advance_fades(&self) {
+        let now = self.clock.now();
+        let fades: Vec<(String, ActiveFade)> = self
+            .lock_fades()
+            .iter()
+            .map(|(device_id, fade)| (device_id.clone(), fade.clone()))
+            .collect();
+
+        for (device_id, fade) in fades {
+            let elapsed = (now - fade.started_at).max(TimeDelta::zero());
+            let progress = (elapsed.num_milliseconds() as f64
+                / fade.duration.num_milliseconds() as f64)
+                .min(1.0) as f32;
+            let volume = fade.from + (fade.to - fade.from) * progress;
+
+            if let Err(error) = self
+                .audio_system
+                .set_device_volume(&device_id, volume)
+                .await
+            {
+                tracing::warn!("Fade on {} stopped: {}", device_id, error);
+                self.lock_fades().remove(&device_id);
+                continue;
+            }
+            if progress >= 1.0 {
+                let mut fades = self.lock_fades();
+                // Um novo fade pode ter substituído este durante o passo
+                if fades
+                    .get(&device_id)
+                    .is_some_and(|current| current.started_at == fade.started_at)
+                {
+                    fades.remove(&device_id);
+                }
+            }
+        }
+    }
+
+    /// Avança os fades periodicamente até o runtime ser encerrado
+    pub async fn run(self: Arc<Self>) {
+        let mut ticker = tokio::time::interval(FADE_STEP_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            ticker.tick().await;
+            if self.lock_fades().is_empty() {
+                continue;
+            }
+
+            self.advance_fades().await;
+        }
+    }
+
+    async fn resolve_device(&self, device_id: Option<&str>) -> AudioResult<String> {
+        match device_id {
+            Some(device_id) => Ok(device_id.to_string()),
+            None => self.audio_system.default_device_id().await?.ok_or_else(|| {
+                AudioError::DeviceNotFound {
+                    device_id: "default".to_string(),
+                }
+            }),
+        }
+    }
+
+    fn lock_fades(&self) -> std::sync::MutexGuard<'_, HashMap<String, ActiveFade>> {
+        self.fades.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
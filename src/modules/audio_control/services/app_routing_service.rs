@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{normalize_process_name, RoutingPreference},
+    platform::async_audio_system::AsyncAudioSystem,
+    services::event_loop::{handle_events, is_session_started},
+    types::{AudioResult, GroupId},
+};
+use crate::modules::core::{
+    broadcasting::EventReceiver, persistence::JsonStore, Broadcaster, ServerEvent,
+};
+
+/// Roteamento de saída por aplicação
+///
+/// Move grupos de sessões entre dispositivos sob demanda e mantém um
+/// dispositivo preferido por executável, aplicado sempre que uma sessão
+/// desse executável aparece em outro dispositivo.
+pub struct AppRoutingService {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    broadcaster: Broadcaster,
+    /// Nome normalizado do processo -> id do dispositivo preferido
+    preferences: JsonStore<BTreeMap<String, String>>,
+}
+
+impl AppRoutingService {
+    pub fn new(
+        audio_system: Arc<dyn AsyncAudioSystem>,
+        broadcaster: Broadcaster,
+        preferences: JsonStore<BTreeMap<String, String>>,
+    ) -> Self {
+        Self {
+            audio_system,
+            broadcaster,
+            preferences,
+        }
+    }
+
+    /// Move um grupo de sessões para outro dispositivo e publica `SessionRouted`
+    pub async fn move_group(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        target_device_id: &str,
+    ) -> AudioResult<()> {
+        let process_name = self
+            .audio_system
+            .get_sessions_for_device(device_id, false)
+            .await?
+            .into_iter()
+            .find(|group| &group.id == group_id)
+            .map(|group| group.app.process_name)
+            .ok_or(AudioError::NoSessionsFound)?;
+
+        self.audio_system
+            .move_group_to_device(group_id, device_id, target_device_id)
+            .await?;
+
+        let _ = self.broadcaster.broadcast(ServerEvent::SessionRouted {
+            group_id: group_id.to_string(),
+            process_name,
+            from_device_id: device_id.to_string(),
+            to_device_id: target_device_id.to_string(),
+        });
+        Ok(())
+    }
+
+    pub fn preferences(&self) -> Vec<RoutingPreference> {
+        self.preferences.read(|preferences| {
+            preferences
+                .iter()
+                .map(|(process_name, device_id)| RoutingPreference {
+                    process_name: process_name.clone(),
+                    device_id: device_id.clone(),
+                })
+                .collect()
+        })
+    }
+
+    /// Define o dispositivo preferido de um executável
+    ///
+    /// O dispositivo precisa existir, mas pode estar desconectado: a
+    /// preferência passa a valer quando ele voltar.
+    pub async fn set_preference(
+        &self,
+        process_name: &str,
+        device_id: &str,
+    ) -> AudioResult<RoutingPreference> {
+        let process_name = normalize_process_name(process_name);
+        if process_name.is_empty() {
+            return Err(AudioError::InvalidRequestBody {
+                message: "Process name must not be empty".to_string(),
+            });
+        }
+
+        let known_device = self
+            .audio_system
+            .list_output_devices(true)
+            .await?
+            .iter()
+            .any(|device| device.id == device_id);
+        if !known_device {
+            return Err(AudioError::DeviceNotFound {
+                device_id: device_id.to_string(),
+            });
+        }
+
+        self.preferences.update(|preferences| {
+            preferences.insert(process_name.clone(), device_id.to_string());
+        })?;
+
+        Ok(RoutingPreference {
+            process_name,
+            device_id: device_id.to_string(),
+        })
+    }
+
+    /// Remove a preferência de um executável; retorna se ela existia
+    pub fn remove_preference(&self, process_name: &str) -> AudioResult<bool> {
+        let process_name = normalize_process_name(process_name);
+        let removed = self
+            .preferences
+            .update(|preferences| preferences.remove(&process_name).is_some())?;
+        Ok(removed)
+    }
+
+    /// Aplica a preferência do executável a uma sessão recém iniciada
+    ///
+    /// Retorna o evento `SessionRouted` publicado quando a sessão foi movida.
+    pub async fn apply_preference(&self, event: &ServerEvent) -> AudioResult<Option<ServerEvent>> {
+        let ServerEvent::SessionStarted {
+            process_name,
+            device_id,
+            group_id,
+            pid,
+            ..
+        } = event
+        else {
+            return Ok(None);
+        };
+
+        let process_name = normalize_process_name(process_name);
+        let Some(preferred) = self
+            .preferences
+            .read(|preferences| preferences.get(&process_name).cloned())
+        else {
+            return Ok(None);
+        };
+
+        if &preferred == device_id || *pid == 0 {
+            return Ok(None);
+        }
+
+        self.audio_system
+            .set_process_output_device(*pid, &preferred)
+            .await?;
+
+        let routed = ServerEvent::SessionRouted {
+            group_id: group_id.clone(),
+            process_name,
+            from_device_id: device_id.clone(),
+            to_device_id: preferred,
+        };
+        let _ = self.broadcaster.broadcast(routed.clone());
+        Ok(Some(routed))
+    }
+
+    /// Aplica as preferências a cada `SessionStarted` recebido
+    ///
+    /// O receiver é criado por quem inicia a tarefa, para que nenhum evento
+    /// publicado entre o início e a primeira execução seja perdido.
+    pub async fn run(self: Arc<Self>, events: EventReceiver) {
+        handle_events(
+            self,
+            events,
+            "App routing",
+            is_session_started,
+            |service, event| async move { service.apply_preference(&event).await },
+        )
+        .await
+    }
+}
@@ -0,0 +1,101 @@
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{
+        normalize_process_name, ApplicationInstance, ApplicationView, DeviceSessions,
+        GroupVolumeMode,
+    },
+    platform::async_audio_system::AsyncAudioSystem,
+    types::AudioResult,
+};
+
+/// Lista os grupos de sessões de todos os dispositivos ativos
+///
+/// Dispositivos cuja leitura falha (ex: removidos durante a listagem) são
+/// ignorados para não derrubar a visão inteira.
+pub async fn list_all_sessions(
+    audio_system: &dyn AsyncAudioSystem,
+    expand: bool,
+) -> AudioResult<Vec<DeviceSessions>> {
+    let mut devices = Vec::new();
+    for device in audio_system.list_output_devices(false).await? {
+        match audio_system
+            .get_sessions_for_device(&device.id, expand)
+            .await
+        {
+            Ok(sessions) => devices.push(DeviceSessions {
+                device_id: device.id,
+                device_name: device.name,
+                is_default: device.is_default,
+                sessions,
+            }),
+            Err(error) => tracing::warn!("Failed to list sessions for {}: {}", device.id, error),
+        }
+    }
+    Ok(devices)
+}
+
+/// Lista as aplicações com sessões ativas, unindo o mesmo executável
+/// em todos os dispositivos
+pub async fn list_applications(
+    audio_system: &dyn AsyncAudioSystem,
+) -> AudioResult<Vec<ApplicationView>> {
+    let devices = list_all_sessions(audio_system, false).await?;
+    Ok(ApplicationView::from_devices(&devices))
+}
+
+/// Define o volume de uma aplicação em todos os dispositivos onde ela toca
+///
+/// Retorna as instâncias alteradas.
+pub async fn set_application_volume(
+    audio_system: &dyn AsyncAudioSystem,
+    process_name: &str,
+    volume: f32,
+    mode: GroupVolumeMode,
+) -> AudioResult<Vec<ApplicationInstance>> {
+    let devices = list_all_sessions(audio_system, false).await?;
+    set_application_volume_in(audio_system, &devices, process_name, volume, mode).await
+}
+
+/// Como `set_application_volume`, procurando a aplicação em `devices`, a
+/// listagem que quem chama já leu
+pub async fn set_application_volume_in(
+    audio_system: &dyn AsyncAudioSystem,
+    devices: &[DeviceSessions],
+    process_name: &str,
+    volume: f32,
+    mode: GroupVolumeMode,
+) -> AudioResult<Vec<ApplicationInstance>> {
+    let application = find_application(ApplicationView::from_devices(devices), process_name)?;
+    for instance in &application.instances {
+        audio_system
+            .set_group_volume(&instance.group_id, &instance.device_id, volume, mode)
+            .await?;
+    }
+    Ok(application.instances)
+}
+
+/// Silencia ou reativa uma aplicação em todos os dispositivos onde ela toca
+pub async fn set_application_mute(
+    audio_system: &dyn AsyncAudioSystem,
+    process_name: &str,
+    muted: bool,
+) -> AudioResult<Vec<ApplicationInstance>> {
+    let application = find_application(list_applications(audio_system).await?, process_name)?;
+    for instance in &application.instances {
+        audio_system
+            .set_group_mute(&instance.group_id, &instance.device_id, muted)
+            .await?;
+    }
+    Ok(application.instances)
+}
+
+fn find_application(
+    applications: Vec<ApplicationView>,
+    process_name: &str,
+) -> AudioResult<ApplicationView> {
+    let process_name = normalize_process_name(process_name);
+    applications
+        .into_iter()
+        .find(|application| application.process_name == process_name)
+        .ok_or(AudioError::ApplicationNotFound { process_name })
+}
@@ -1,27 +1,39 @@
 use crate::modules::audio_control::{
-    models::device_sound::DeviceSound, types::audio_result::AudioResult,
+    errors::AudioError,
+    models::device_sound::{DeviceFormat, DeviceSound, DeviceState, FormFactor, VolumeRange},
+    types::audio_result::AudioResult,
 };
 use crate::modules::core::com::ComContext;
 
+use windows::core::PCWSTR;
 use windows::Win32::{
     Devices::FunctionDiscovery::PKEY_Device_FriendlyName,
     Media::Audio::{Endpoints::IAudioEndpointVolume, *},
-    System::Com::{CoCreateInstance, StructuredStorage::PROPVARIANT, CLSCTX_ALL, STGM_READ},
+    System::Com::{
+        CoCreateInstance, CoTaskMemFree, StructuredStorage::PROPVARIANT, CLSCTX_ALL, STGM_READ,
+    },
 };
 
 /// Usa a API de dispositivos de áudio do Windows para enumerar
-/// dispositivos de saída (speakers, headphones, etc.) que estão
-/// atualmente ativos e conectados.
-pub fn list_output_devices() -> AudioResult<Vec<DeviceSound>> {
+/// dispositivos de saída (speakers, headphones, etc.).
+///
+/// Por padrão apenas dispositivos ativos são listados; com `include_inactive`
+/// também retorna dispositivos desabilitados, desconectados e ausentes.
+pub fn list_output_devices(include_inactive: bool) -> AudioResult<Vec<DeviceSound>> {
     let _com_ctx = ComContext::new()?;
     unsafe {
-        // Cria enumerador de dispositivos de áudio
-        let device_enumerator: IMMDeviceEnumerator =
-            CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+        // Obtém o enumerador de dispositivos de áudio
+        let device_enumerator = device_enumerator()?;
+
+        let state_mask = if include_inactive {
+            DEVICE_STATEMASK_ALL
+        } else {
+            DEVICE_STATE_ACTIVE
+        };
 
-        // Enumera apenas dispositivos de saída (eRender) que estão ativos
+        // Enumera apenas dispositivos de saída (eRender) no estado solicitado
         let device_collection: IMMDeviceCollection =
-            device_enumerator.EnumAudioEndpoints(eRender, DEVICE_STATE_ACTIVE)?;
+            device_enumerator.EnumAudioEndpoints(eRender, state_mask)?;
         let device_count = device_collection.GetCount()?;
 
         if device_count == 0 {
@@ -29,6 +41,8 @@ pub fn list_output_devices() -> AudioResult<Vec<DeviceSound>> {
             return Ok(Vec::new());
         }
 
+        let default_id = get_default_device_id(&device_enumerator);
+
         let mut devices: Vec<DeviceSound> = Vec::new();
         for index in 0..device_count {
             let device = match device_collection.Item(index) {
@@ -39,48 +53,14 @@ pub fn list_output_devices() -> AudioResult<Vec<DeviceSound>> {
                 }
             };
 
-            let id_pointer = match device.GetId() {
-                Ok(id_pointer) => id_pointer,
-                Err(error) => {
-                    println!("Failed to get device ID at index {}: {:?}", index, error);
-                    continue;
-                }
-            };
-
-            let id = match id_pointer.to_string() {
-                Ok(id) => id,
-                Err(error) => {
-                    println!("Invalid device ID at index {}: {:?}", index, error);
-                    continue;
-                }
-            };
-
-            let property_store = match device.OpenPropertyStore(STGM_READ) {
-                Ok(property_store) => property_store,
+            let device_sound = match read_device_sound(&device, default_id.as_deref()) {
+                Ok(device_sound) => device_sound,
                 Err(error) => {
-                    println!(
-                        "Failed to open property store for device {}: {:?}",
-                        id, error
-                    );
+                    println!("Failed to read device at index {}: {:?}", index, error);
                     continue;
                 }
             };
 
-            let name_value: PROPVARIANT = match property_store.GetValue(&PKEY_Device_FriendlyName) {
-                Ok(name_value) => name_value,
-                Err(error) => {
-                    println!("Failed to get friendly name for device {}: {:?}", id, error);
-                    continue;
-                }
-            };
-
-            let device_name = name_value.to_string();
-
-            let device_sound = DeviceSound {
-                id,
-                name: device_name.clone(),
-                endpoint: device.clone(),
-            };
             println!("Device {} pushed", device_sound.name);
             devices.push(device_sound);
         }
@@ -88,15 +68,138 @@ pub fn list_output_devices() -> AudioResult<Vec<DeviceSound>> {
     }
 }
 
+/// Enumerador de dispositivos de áudio
+///
+/// Na thread do backend o enumerador é criado uma vez e reaproveitado; nas
+/// demais threads um novo é criado a cada chamada.
+pub(crate) fn device_enumerator() -> AudioResult<IMMDeviceEnumerator> {
+    let device_enumerator = ComContext::cached(|| unsafe {
+        CoCreateInstance::<_, IMMDeviceEnumerator>(&MMDeviceEnumerator, None, CLSCTX_ALL)
+    })?;
+    Ok(device_enumerator)
+}
+
+/// Obtém o endpoint de um dispositivo pelo ID
+pub(crate) fn get_device_endpoint_by_id(device_id: &str) -> AudioResult<IMMDevice> {
+    unsafe {
+        let device_enumerator = device_enumerator()?;
+
+        // Converte ID de string para WideString (UTF-16)
+        let wide_id: Vec<u16> = device_id.encode_utf16().chain(std::iter::once(0)).collect();
+        device_enumerator
+            .GetDevice(PCWSTR(wide_id.as_ptr()))
+            .map_err(|_| AudioError::DeviceNotFound {
+                device_id: device_id.to_string(),
+            })
+    }
+}
+
+/// Monta o `DeviceSound` a partir de um endpoint
+///
+/// Formato e faixa de volume só estão disponíveis para dispositivos ativos;
+/// para os demais esses campos ficam vazios.
+pub(crate) unsafe fn read_device_sound(
+    device: &IMMDevice,
+    default_id: Option<&str>,
+) -> AudioResult<DeviceSound> {
+    let id = device.GetId()?.to_string()?;
+
+    let property_store = device.OpenPropertyStore(STGM_READ)?;
+    let name_value: PROPVARIANT = property_store.GetValue(&PKEY_Device_FriendlyName)?;
+    let name = name_value.to_string();
+
+    // O form factor é um VT_UI4 com o valor de EndpointFormFactor
+    let form_factor = property_store
+        .GetValue(&PKEY_AudioEndpoint_FormFactor)
+        .ok()
+        .and_then(|value| u32::try_from(&value).ok())
+        .map(FormFactor::from)
+        .unwrap_or(FormFactor::Unknown);
+
+    let state = device
+        .GetState()
+        .map(|state| DeviceState::from(state.0))
+        .unwrap_or(DeviceState::NotPresent);
+
+    let (format, volume_range) = if state == DeviceState::Active {
+        (read_device_format(device), read_volume_range(device))
+    } else {
+        (None, None)
+    };
+
+    Ok(DeviceSound {
+        is_default: default_id == Some(id.as_str()),
+        id,
+        name,
+        state,
+        form_factor,
+        format,
+        volume_range,
+    })
+}
+
+/// Obtém o ID do dispositivo de saída padrão, se houver
+pub(crate) unsafe fn get_default_device_id(
+    device_enumerator: &IMMDeviceEnumerator,
+) -> Option<String> {
+    let device_default = device_enumerator
+        .GetDefaultAudioEndpoint(eRender, eConsole)
+        .ok()?;
+    device_default.GetId().ok()?.to_string().ok()
+}
+
+/// Lê o formato de mixagem compartilhado do dispositivo
+unsafe fn read_device_format(device: &IMMDevice) -> Option<DeviceFormat> {
+    let audio_client: IAudioClient = device.Activate(CLSCTX_ALL, None).ok()?;
+    let format_pointer = audio_client.GetMixFormat().ok()?;
+    if format_pointer.is_null() {
+        return None;
+    }
+
+    // WAVEFORMATEX é empacotado, então a leitura precisa ser não alinhada
+    let format = std::ptr::read_unaligned(format_pointer);
+    CoTaskMemFree(Some(format_pointer as *const _));
+
+    Some(DeviceFormat {
+        channels: format.nChannels,
+        sample_rate: format.nSamplesPerSec,
+        bits_per_sample: format.wBitsPerSample,
+    })
+}
+
+/// Lê a faixa de volume em dB e a quantidade de passos do hardware
+unsafe fn read_volume_range(device: &IMMDevice) -> Option<VolumeRange> {
+    let endpoint_volume: IAudioEndpointVolume = device.Activate(CLSCTX_ALL, None).ok()?;
+
+    let mut min_db = 0.0f32;
+    let mut max_db = 0.0f32;
+    let mut increment_db = 0.0f32;
+    endpoint_volume
+        .GetVolumeRange(&mut min_db, &mut max_db, &mut increment_db)
+        .ok()?;
+
+    let mut _current_step = 0u32;
+    let mut step_count = 0u32;
+    endpoint_volume
+        .GetVolumeStepInfo(&mut _current_step, &mut step_count)
+        .ok()?;
+
+    Some(VolumeRange {
+        min_db,
+        max_db,
+        increment_db,
+        step_count,
+    })
+}
+
 /// Obtém o volume atual do dispositivo de saída padrão
 ///
 /// Retorna o volume como um valor de 0.0 a 100.0 (percentual).
 pub fn get_actual_volume() -> AudioResult<f32> {
     let _com_ctx = ComContext::new()?;
     let result = unsafe {
-        // Cria enumerador de dispositivos
-        let device_enumerator: IMMDeviceEnumerator =
-            CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+        // Obtém o enumerador de dispositivos
+        let device_enumerator = device_enumerator()?;
 
         // Obtém o dispositivo de saída padrão
         let device_default = device_enumerator.GetDefaultAudioEndpoint(eRender, eConsole)?;
@@ -119,3 +222,92 @@ pub fn get_actual_volume() -> AudioResult<f32> {
     };
     Ok(result)
 }
+
+/// Obtém o volume master de um dispositivo, em percentual (0.0 a 100.0)
+pub fn get_device_volume(device_id: &str) -> AudioResult<f32> {
+    let _com_ctx = ComContext::new()?;
+    let device = get_device_endpoint_by_id(device_id)?;
+    unsafe {
+        let endpoint_volume: IAudioEndpointVolume = device.Activate(CLSCTX_ALL, None)?;
+        let volume = endpoint_volume.GetMasterVolumeLevelScalar()?;
+        Ok(if volume.is_nan() { 0.0 } else { volume * 100.0 })
+    }
+}
+
+/// Define o volume master de um dispositivo
+pub fn set_device_volume(device_id: &str, volume: f32) -> AudioResult<()> {
+    let _com_ctx = ComContext::new()?;
+    let device = get_device_endpoint_by_id(device_id)?;
+    unsafe {
+        let endpoint_volume: IAudioEndpointVolume = device.Activate(CLSCTX_ALL, None)?;
+        let scalar = (volume / 100.0).clamp(0.0, 1.0);
+        endpoint_volume.SetMasterVolumeLevelScalar(scalar, std::ptr::null())?;
+        Ok(())
+    }
+}
+
+/// Indica se o volume master de um dispositivo está mudo
+pub fn get_device_mute(device_id: &str) -> AudioResult<bool> {
+    let _com_ctx = ComContext::new()?;
+    let device = get_device_endpoint_by_id(device_id)?;
+    unsafe {
+        let endpoint_volume: IAudioEndpointVolume = device.Activate(CLSCTX_ALL, None)?;
+        Ok(endpoint_volume.GetMute()?.as_bool())
+    }
+}
+
+/// Silencia ou reativa o volume master de um dispositivo
+pub fn set_device_mute(device_id: &str, muted: bool) -> AudioResult<()> {
+    let _com_ctx = ComContext::new()?;
+    let device = get_device_endpoint_by_id(device_id)?;
+    unsafe {
+        let endpoint_volume: IAudioEndpointVolume = device.Activate(CLSCTX_ALL, None)?;
+        endpoint_volume.SetMute(muted, std::ptr::null())?;
+        Ok(())
+    }
+}
+
+/// Obtém o volume de cada canal do dispositivo, em percentual (0.0 a 100.0)
+pub fn get_device_channel_volumes(device_id: &str) -> AudioResult<Vec<f32>> {
+    let _com_ctx = ComContext::new()?;
+    let device = get_device_endpoint_by_id(device_id)?;
+    unsafe {
+        let endpoint_volume: IAudioEndpointVolume = device.Activate(CLSCTX_ALL, None)?;
+
+        let channel_count = endpoint_volume.GetChannelCount()?;
+        let mut levels = Vec::with_capacity(channel_count as usize);
+        for channel in 0..channel_count {
+            levels.push(endpoint_volume.GetChannelVolumeLevelScalar(channel)? * 100.0);
+        }
+        Ok(levels)
+    }
+}
+
+/// Define o volume de cada canal do dispositivo
+///
+/// A quantidade de níveis deve ser igual à quantidade de canais do endpoint.
+pub fn set_device_channel_volumes(device_id: &str, levels: &[f32]) -> AudioResult<()> {
+    let _com_ctx = ComContext::new()?;
+    let device = get_device_endpoint_by_id(device_id)?;
+    unsafe {
+        let endpoint_volume: IAudioEndpointVolume = device.Activate(CLSCTX_ALL, None)?;
+
+        let channel_count = endpoint_volume.GetChannelCount()? as usize;
+        if channel_count != levels.len() {
+            return Err(AudioError::ChannelCountMismatch {
+                expected: channel_count,
+                actual: levels.len(),
+            });
+        }
+
+        for (channel, level) in levels.iter().enumerate() {
+            let scalar = (level / 100.0).clamp(0.0, 1.0);
+            endpoint_volume.SetChannelVolumeLevelScalar(
+                channel as u32,
+                scalar,
+                std::ptr::null(),
+            )?;
+        }
+        Ok(())
+    }
+}
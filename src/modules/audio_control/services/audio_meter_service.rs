@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use windows::{
+    core::Interface,
+    Win32::{
+        Media::Audio::{
+            Endpoints::IAudioMeterInformation, IAudioSessionControl2, IAudioSessionManager2,
+        },
+        System::Com::CLSCTX_ALL,
+    },
+};
+
+use crate::modules::{
+    audio_control::{
+        models::GroupPeak,
+        services::audio_device_service::get_device_endpoint_by_id,
+        types::{audio_result::AudioResult, GroupId},
+    },
+    core::com::ComContext,
+};
+
+/// Obtém o pico de amostra atual do dispositivo (0.0 a 1.0)
+pub fn get_device_peak(device_id: &str) -> AudioResult<f32> {
+    let _com_ctx = ComContext::new()?;
+    let device = get_device_endpoint_by_id(device_id)?;
+    unsafe {
+        let meter: IAudioMeterInformation = device.Activate(CLSCTX_ALL, None)?;
+        Ok(meter.GetPeakValue()?)
+    }
+}
+
+/// Obtém o pico de amostra atual de cada grupo de sessões do dispositivo
+///
+/// Sessões com o mesmo GUID de agrupamento são combinadas usando o
+/// maior pico entre elas.
+pub fn get_group_peaks(device_id: &str) -> AudioResult<Vec<GroupPeak>> {
+    let _com_ctx = ComContext::new()?;
+    let device = get_device_endpoint_by_id(device_id)?;
+    unsafe {
+        let session_manager: IAudioSessionManager2 = device.Activate(CLSCTX_ALL, None)?;
+
+        let session_enum = session_manager.GetSessionEnumerator()?;
+        let count = session_enum.GetCount()?;
+
+        let mut peaks: HashMap<GroupId, f32> = HashMap::new();
+        for i in 0..count {
+            let session = session_enum.GetSession(i)?;
+            let session2: IAudioSessionControl2 = session.cast()?;
+            let guid = session2.GetGroupingParam()?;
+
+            // A sessão também expõe IAudioMeterInformation
+            let meter: IAudioMeterInformation = session2.cast()?;
+            let peak = meter.GetPeakValue()?;
+
+            let entry = peaks.entry(GroupId::from(&guid)).or_insert(0.0);
+            *entry = entry.max(peak);
+        }
+
+        Ok(peaks
+            .into_iter()
+            .map(|(group_id, peak)| GroupPeak { group_id, peak })
+            .collect())
+    }
+}
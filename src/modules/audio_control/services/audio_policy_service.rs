@@ -0,0 +1,186 @@
+#![allow(non_snake_case)]
+
+use std::ffi::c_void;
+
+use windows_core::interface;
+
+use windows::{
+    core::{IInspectable, IUnknown, GUID, HRESULT, HSTRING, PCWSTR},
+    Win32::{
+        Media::Audio::{eCommunications, eConsole, eMultimedia, eRender, EDataFlow, ERole},
+        System::{
+            Com::{CoCreateInstance, CLSCTX_ALL},
+            WinRT::RoGetActivationFactory,
+        },
+    },
+};
+
+use crate::modules::{
+    audio_control::{
+        errors::AudioError,
+        services::{
+            audio_device_service::get_device_endpoint_by_id,
+            audio_session_service::get_group_process_ids,
+        },
+        types::{audio_result::AudioResult, GroupId},
+    },
+    core::com::ComContext,
+};
+
+/// Classe WinRT interna usada pelo Windows para o roteamento por aplicação
+/// (Configurações > Som > Volume do aplicativo e preferências do dispositivo)
+const AUDIO_POLICY_CONFIG_CLASS: &str = "Windows.Media.Internal.AudioPolicyConfig";
+
+/// Prefixo e sufixo do caminho de interface de um endpoint de renderização
+const MMDEVAPI_PREFIX: &str = r"\\?\SWD#MMDEVAPI#";
+const DEVINTERFACE_AUDIO_RENDER: &str = "#{e6327cad-dcec-4949-ae8a-991e976a79d2}";
+
+/// Interface não documentada `IAudioPolicyConfigFactory` (Windows 10 21H2+)
+///
+/// Apenas os métodos de endpoint persistido são usados; os demais entram
+/// como espaços reservados para manter a ordem da vtable.
+#[interface("ab3d4648-e242-459f-b02f-541c70306324")]
+unsafe trait IAudioPolicyConfigFactory: IInspectable {
+    unsafe fn __add_CtxVolumeChange(&self) -> HRESULT;
+    unsafe fn __remove_CtxVolumeChanged(&self) -> HRESULT;
+    unsafe fn __add_RingerVibrateStateChanged(&self) -> HRESULT;
+    unsafe fn __remove_RingerVibrateStateChange(&self) -> HRESULT;
+    unsafe fn __SetVolumeGroupGainForId(&self) -> HRESULT;
+    unsafe fn __GetVolumeGroupGainForId(&self) -> HRESULT;
+    unsafe fn __GetActiveVolumeGroupForEndpointId(&self) -> HRESULT;
+    unsafe fn __GetVolumeGroupsForEndpoint(&self) -> HRESULT;
+    unsafe fn __GetCurrentVolumeContext(&self) -> HRESULT;
+    unsafe fn __SetVolumeGroupMuteForId(&self) -> HRESULT;
+    unsafe fn __GetVolumeGroupMuteForId(&self) -> HRESULT;
+    unsafe fn __SetRingerVibrateState(&self) -> HRESULT;
+    unsafe fn __GetRingerVibrateState(&self) -> HRESULT;
+    unsafe fn __SetPreferredChatApplication(&self) -> HRESULT;
+    unsafe fn __ResetPreferredChatApplication(&self) -> HRESULT;
+    unsafe fn __GetPreferredChatApplication(&self) -> HRESULT;
+    unsafe fn __GetCurrentChatApplications(&self) -> HRESULT;
+    unsafe fn __add_ChatContextChanged(&self) -> HRESULT;
+    unsafe fn __remove_ChatContextChanged(&self) -> HRESULT;
+    unsafe fn SetPersistedDefaultAudioEndpoint(
+        &self,
+        process_id: u32,
+        flow: EDataFlow,
+        role: ERole,
+        device_id: *mut c_void,
+    ) -> HRESULT;
+    unsafe fn GetPersistedDefaultAudioEndpoint(
+        &self,
+        process_id: u32,
+        flow: EDataFlow,
+        role: ERole,
+        device_id: *mut *mut c_void,
+    ) -> HRESULT;
+    unsafe fn ClearAllPersistedApplicationDefaultEndpoints(&self) -> HRESULT;
+}
+
+/// Classe COM `PolicyConfigClient`, usada pelo painel de som para trocar
+/// o dispositivo padrão
+const CLSID_POLICY_CONFIG_CLIENT: GUID = GUID::from_u128(0x870af99c_171d_4f9e_af0d_e63df40c2bc9);
+
+/// Interface não documentada `IPolicyConfig` (Windows 7+)
+#[interface("f8679f50-850a-41cf-9c72-430f290290c8")]
+unsafe trait IPolicyConfig: IUnknown {
+    unsafe fn __GetMixFormat(&self) -> HRESULT;
+    unsafe fn __GetDeviceFormat(&self) -> HRESULT;
+    unsafe fn __ResetDeviceFormat(&self) -> HRESULT;
+    unsafe fn __SetDeviceFormat(&self) -> HRESULT;
+    unsafe fn __GetProcessingPeriod(&self) -> HRESULT;
+    unsafe fn __SetProcessingPeriod(&self) -> HRESULT;
+    unsafe fn __GetShareMode(&self) -> HRESULT;
+    unsafe fn __SetShareMode(&self) -> HRESULT;
+    unsafe fn __GetPropertyValue(&self) -> HRESULT;
+    unsafe fn __SetPropertyValue(&self) -> HRESULT;
+    unsafe fn SetDefaultEndpoint(&self, device_id: PCWSTR, role: ERole) -> HRESULT;
+    unsafe fn __SetEndpointVisibility(&self) -> HRESULT;
+}
+
+fn get_policy_config() -> AudioResult<IAudioPolicyConfigFactory> {
+    unsafe {
+        let class_name = HSTRING::from(AUDIO_POLICY_CONFIG_CLASS);
+        Ok(RoGetActivationFactory(&class_name)?)
+    }
+}
+
+/// Define o dispositivo de saída persistido de um processo
+///
+/// Aplica o mesmo endpoint para os papéis `eConsole` e `eMultimedia`,
+/// que é o que a tela de configurações do Windows faz.
+unsafe fn set_persisted_endpoint(
+    policy_config: &IAudioPolicyConfigFactory,
+    pid: u32,
+    device_id: &str,
+) -> AudioResult<()> {
+    let interface_path = HSTRING::from(format!(
+        "{MMDEVAPI_PREFIX}{device_id}{DEVINTERFACE_AUDIO_RENDER}"
+    ));
+    // HSTRING é um handle transparente; a interface recebe o valor bruto
+    let raw_path: *mut c_void = std::mem::transmute_copy(&interface_path);
+
+    for role in [eConsole, eMultimedia] {
+        policy_config
+            .SetPersistedDefaultAudioEndpoint(pid, eRender, role, raw_path)
+            .ok()?;
+    }
+    Ok(())
+}
+
+/// Roteia a saída de áudio de um processo para o dispositivo informado
+pub fn set_process_output_device(pid: u32, device_id: &str) -> AudioResult<()> {
+    let _com_ctx = ComContext::new()?;
+
+    // Valida o dispositivo antes de persistir a preferência
+    get_device_endpoint_by_id(device_id)?;
+
+    unsafe {
+        let policy_config = get_policy_config()?;
+        set_persisted_endpoint(&policy_config, pid, device_id)
+    }
+}
+
+/// Move todos os processos de um grupo de sessões para outro dispositivo
+pub fn move_group_to_device(
+    group_id: &GroupId,
+    device_id: &str,
+    target_device_id: &str,
+) -> AudioResult<()> {
+    let _com_ctx = ComContext::new()?;
+
+    get_device_endpoint_by_id(target_device_id)?;
+    let pids = get_group_process_ids(group_id, device_id)?;
+    if pids.is_empty() {
+        return Err(AudioError::NoSessionsFound);
+    }
+
+    unsafe {
+        let policy_config = get_policy_config()?;
+        for pid in pids {
+            set_persisted_endpoint(&policy_config, pid, target_device_id)?;
+        }
+    }
+    Ok(())
+}
+
+/// Define o dispositivo de saída padrão do sistema
+///
+/// O dispositivo passa a ser o padrão para todos os papéis (console,
+/// multimídia e comunicações).
+pub fn set_default_device(device_id: &str) -> AudioResult<()> {
+    let _com_ctx = ComContext::new()?;
+    get_device_endpoint_by_id(device_id)?;
+
+    unsafe {
+        let policy_config: IPolicyConfig =
+            CoCreateInstance(&CLSID_POLICY_CONFIG_CLIENT, None, CLSCTX_ALL)?;
+        let wide_id = HSTRING::from(device_id);
+        for role in [eConsole, eMultimedia, eCommunications] {
+            policy_config
+                .SetDefaultEndpoint(PCWSTR(wide_id.as_ptr()), role)
+                .ok()?;
+        }
+    }
+    Ok(())
+}
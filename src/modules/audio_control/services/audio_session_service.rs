@@ -1,139 +1,134 @@
 use std::collections::HashMap;
 
 use windows::{
-    core::{Interface, GUID, PCWSTR},
+    core::{Interface, GUID},
     Win32::{
-        Devices::FunctionDiscovery::PKEY_Device_FriendlyName,
+        Foundation::S_OK,
         Media::Audio::{
-            IAudioSessionControl2, IAudioSessionManager2, IMMDeviceEnumerator, ISimpleAudioVolume,
-            MMDeviceEnumerator,
+            IAudioSessionControl2, IAudioSessionManager2, IChannelAudioVolume, ISimpleAudioVolume,
         },
-        System::Com::{CoCreateInstance, StructuredStorage::PROPVARIANT, CLSCTX_ALL, STGM_READ},
+        System::Com::{CoTaskMemFree, CLSCTX_ALL},
     },
 };
 
 use crate::modules::{
     audio_control::{
         errors::AudioError,
-        models::{DeviceSound, SessionGroup, SessionState},
+        models::{AppInfo, GroupVolumeMode, SessionGroup, SessionMember, SessionState},
+        platform::audio_system_interface::AudioProcessLookup,
+        services::audio_device_service::{get_device_endpoint_by_id, list_output_devices},
         types::{audio_result::AudioResult, GroupId},
-        utils::audio_process_utils::get_friendly_process_name,
+        utils::{extract_icon_png, get_process_path, IconLocation},
     },
     core::com::ComContext,
 };
 
-/// Obtém um dispositivo pelo ID
-fn get_device_by_id(device_id: &str) -> AudioResult<DeviceSound> {
-    unsafe {
-        let device_enumerator: IMMDeviceEnumerator =
-            CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
-
-        // Converte ID de string para WideString (UTF-16)
-        let wide_id: Vec<u16> = device_id.encode_utf16().chain(std::iter::once(0)).collect();
-        let device = device_enumerator.GetDevice(PCWSTR(wide_id.as_ptr()));
-        match device {
-            Ok(device) => {
-                let id = device
-                    .GetId()?
-                    .to_string()
-                    .map_err(AudioError::Utf16Error)?;
-                let property_store = device.OpenPropertyStore(STGM_READ)?;
-                let name_value: PROPVARIANT = property_store.GetValue(&PKEY_Device_FriendlyName)?;
-                let device_name = name_value.to_string();
-                Ok(DeviceSound {
-                    id,
-                    name: device_name.clone(),
-                    endpoint: device.clone(),
-                })
-            }
-            Err(_) => Err(AudioError::DeviceNotFound {
-                device_id: device_id.to_string(),
-            }),
-        }
+/// Lê os dados de uma sessão individual
+///
+/// O nome do processo só é resolvido quando `resolve_name` é verdadeiro,
+/// já que exige abrir o processo dono da sessão.
+unsafe fn read_session_member(
+    session: &IAudioSessionControl2,
+    resolve_name: bool,
+    processes: &dyn AudioProcessLookup,
+) -> Option<SessionMember> {
+    let simple_volume: ISimpleAudioVolume = session.cast().ok()?;
+    let volume = simple_volume.GetMasterVolume().ok()?;
+    let muted = simple_volume.GetMute().ok()?;
+    let state = session.GetState().ok()?;
+
+    // Sessões do sistema (ex: sons do Windows) não têm processo associado
+    let pid = session.GetProcessId().unwrap_or(0);
+    let process_name = if !resolve_name {
+        String::new()
+    } else if is_system_sounds(session) {
+        AppInfo::system_sounds().process_name
+    } else {
+        friendly_name_or_pid(processes, pid)
+    };
+
+    Some(SessionMember {
+        session_id: get_session_instance_id(session).ok()?,
+        pid,
+        process_name,
+        volume_level: volume * 100.0,
+        muted: muted.as_bool(),
+        state: SessionState::from(state),
+    })
+}
+
+/// Obtém o identificador da instância da sessão, liberando a string COM
+unsafe fn get_session_instance_id(session: &IAudioSessionControl2) -> AudioResult<String> {
+    let id_pointer = session.GetSessionInstanceIdentifier()?;
+    let id = id_pointer.to_string();
+    CoTaskMemFree(Some(id_pointer.0 as *const _));
+    Ok(id?)
+}
+
+/// A sessão de sons do sistema responde `S_OK`; as demais, `S_FALSE`
+unsafe fn is_system_sounds(session: &IAudioSessionControl2) -> bool {
+    session.IsSystemSoundsSession() == S_OK
+}
+
+fn friendly_name_or_pid(processes: &dyn AudioProcessLookup, pid: u32) -> String {
+    let app = processes.get_app_info(pid);
+    match app.executable_path {
+        Some(_) => app.process_name,
+        None => format!("PID {}", pid),
     }
 }
 
 /// Cria um SessionGroup a partir de múltiplas sessões com o mesmo GUID
 ///
 /// Sessões de áudio podem ser agrupadas (ex: Edge com múltiplas abas).
-/// Agrupa por GUID e calcula volume médio e estado agregado.
+/// Agrupa por GUID e calcula volume médio e estado agregado; com `expand`
+/// as sessões individuais também são retornadas.
 fn create_session_group_from_guid(
     guid: GUID,
     sessions: Vec<IAudioSessionControl2>,
+    expand: bool,
+    processes: &dyn AudioProcessLookup,
 ) -> Option<SessionGroup> {
     unsafe {
-        if sessions.is_empty() {
-            return None;
-        }
-
-        let first = &sessions[0];
+        let first = sessions.first()?;
 
-        // Obtém o PID e o nome amigável do processo
-        let pid = first.GetProcessId().ok()?;
-        let display_name = match get_friendly_process_name(pid) {
-            Ok(name) => name,
-            Err(_) => format!("PID {}", pid),
+        // Identifica a aplicação pelo processo dono da primeira sessão
+        let app = if is_system_sounds(first) {
+            AppInfo::system_sounds()
+        } else {
+            processes.get_app_info(first.GetProcessId().ok()?)
         };
 
-        let mut total_volume = 0.0f32;
-        let mut active_count = 0;
-        let mut is_muted = false;
-        let mut has_active = false;
-
         // Itera sobre todas as sessões do grupo
+        let mut members = Vec::with_capacity(sessions.len());
         for session in &sessions {
-            let simple_volume: ISimpleAudioVolume = session.cast().ok()?;
-            let volume = simple_volume.GetMasterVolume().ok()?;
-
-            if !volume.is_nan() {
-                total_volume += volume;
-                active_count += 1;
-            }
-
-            let muted = simple_volume.GetMute().ok()?;
-            is_muted = is_muted || muted.as_bool();
-
-            let state = session.GetState().ok()?;
-
-            // state.0 == 0 significa que a sessão está ativa
-            if state.0 == 0 {
-                has_active = true;
-            }
+            members.push(read_session_member(session, expand, processes)?);
         }
 
-        // Calcula volume médio e converte para percentual
-        let avg_volume = if active_count > 0 {
-            (total_volume / active_count as f32) * 100.0
-        } else {
-            0.0
-        };
-
-        let group_state = if has_active {
-            SessionState::Active
-        } else {
-            SessionState::Inactive
-        };
-
-        let id = GroupId::from(&guid);
-
-        Some(SessionGroup {
-            id,
-            display_name,
-            volume_level: avg_volume,
-            state: group_state,
-            muted: is_muted,
-        })
+        Some(SessionGroup::from_members(
+            GroupId::from(&guid),
+            app,
+            members,
+            expand,
+        ))
     }
 }
 
-pub fn get_session_for_device(device_id: &str) -> AudioResult<Vec<SessionGroup>> {
+/// Lista os grupos de sessões de um dispositivo
+///
+/// As aplicações donas das sessões são identificadas por `processes`, o que
+/// permite reaproveitar resoluções anteriores em vez de abrir cada processo.
+pub fn get_session_for_device(
+    device_id: &str,
+    expand: bool,
+    processes: &dyn AudioProcessLookup,
+) -> AudioResult<Vec<SessionGroup>> {
     let _com_ctx = ComContext::new()?;
-    let device = get_device_by_id(device_id);
+    let device = get_device_endpoint_by_id(device_id);
     match device {
         Ok(device) => unsafe {
             // Ativa o gerenciador de sessões para o dispositivo
-            let session_manager: IAudioSessionManager2 =
-                device.endpoint.Activate(CLSCTX_ALL, None)?;
+            let session_manager: IAudioSessionManager2 = device.Activate(CLSCTX_ALL, None)?;
 
             let session_enum = session_manager.GetSessionEnumerator()?;
             let count = session_enum.GetCount()?;
@@ -153,7 +148,9 @@ pub fn get_session_for_device(device_id: &str) -> AudioResult<Vec<SessionGroup>>
             // Converte grupos de sessões em SessionGroups
             let mut session_groups = Vec::new();
             for (guid, sessions) in groups {
-                if let Some(group) = create_session_group_from_guid(guid, sessions) {
+                if let Some(group) =
+                    create_session_group_from_guid(guid, sessions, expand, processes)
+                {
                     session_groups.push(group);
                 }
             }
@@ -164,44 +161,247 @@ pub fn get_session_for_device(device_id: &str) -> AudioResult<Vec<SessionGroup>>
     }
 }
 
-pub fn set_group_volume(group_id: &GroupId, device_id: &str, volume: f32) -> AudioResult<()> {
+/// Define o volume de todas as sessões de um grupo
+///
+/// No modo `Uniform` todas as sessões recebem o mesmo volume; no modo
+/// `Proportional` as diferenças relativas entre as sessões são mantidas.
+pub fn set_group_volume(
+    group_id: &GroupId,
+    device_id: &str,
+    volume: f32,
+    mode: GroupVolumeMode,
+) -> AudioResult<()> {
     let _com_ctx = ComContext::new()?;
-    let device = get_device_by_id(device_id);
-    match device {
-        Ok(device) => unsafe {
-            let session_manager: IAudioSessionManager2 =
-                device.endpoint.Activate(CLSCTX_ALL, None)?;
-
-            let session_enum = session_manager.GetSessionEnumerator()?;
-            let count = session_enum.GetCount()?;
+    unsafe {
+        let sessions = get_group_sessions(group_id, device_id)?;
 
-            let mut found_sessions = 0;
+        let mut volume_controls = Vec::with_capacity(sessions.len());
+        let mut current_volumes = Vec::with_capacity(sessions.len());
+        for session in &sessions {
+            let simple_volume: ISimpleAudioVolume = session.cast()?;
+            current_volumes.push(simple_volume.GetMasterVolume()? * 100.0);
+            volume_controls.push(simple_volume);
+        }
 
+        let targets = mode.member_targets(&current_volumes, volume);
+        for (simple_volume, target) in volume_controls.iter().zip(targets) {
             // Converte de percentual (0-100) para scalar (0.0-1.0)
-            let volume_scalar = (volume / 100.0).clamp(0.0, 1.0);
+            let volume_scalar = (target / 100.0).clamp(0.0, 1.0);
 
-            for i in 0..count {
-                let session = session_enum.GetSession(i)?;
-                let session2: IAudioSessionControl2 = session.cast()?;
+            // std::ptr::null() significa sem notificação de evento de mudança
+            simple_volume.SetMasterVolume(volume_scalar, std::ptr::null())?;
+        }
 
-                let guid = session2.GetGroupingParam()?;
+        Ok(())
+    }
+}
 
-                let current_group_id = GroupId::from(&guid);
-                if current_group_id == *group_id {
-                    let simple_volume: ISimpleAudioVolume = session2.cast()?;
+/// Obtém os PIDs distintos dos processos de um grupo de sessões
+///
+/// Sessões do sistema (PID 0) são ignoradas.
+pub(crate) fn get_group_process_ids(group_id: &GroupId, device_id: &str) -> AudioResult<Vec<u32>> {
+    let _com_ctx = ComContext::new()?;
+    unsafe {
+        let sessions = get_group_sessions(group_id, device_id)?;
 
-                    // std::ptr::null() significa sem notificação de evento de mudança
-                    simple_volume.SetMasterVolume(volume_scalar, std::ptr::null())?;
-                    found_sessions += 1;
-                }
+        let mut pids = Vec::new();
+        for session in &sessions {
+            let pid = session.GetProcessId().unwrap_or(0);
+            if pid != 0 && !pids.contains(&pid) {
+                pids.push(pid);
+            }
+        }
+        Ok(pids)
+    }
+}
+
+/// Ícone usado pelo Windows para a sessão de sons do sistema
+const SYSTEM_SOUNDS_ICON: &str = r"%SystemRoot%\System32\AudioSrv.dll,-203";
+
+/// Extrai o ícone da aplicação dona de um grupo, em PNG
+///
+/// O grupo é procurado em todos os dispositivos ativos. O ícone declarado
+/// pela sessão tem prioridade; sem ele é usado o ícone do executável.
+pub fn get_group_icon(group_id: &GroupId) -> AudioResult<Vec<u8>> {
+    let _com_ctx = ComContext::new()?;
+
+    for device in list_output_devices(false)? {
+        let sessions = match unsafe { get_group_sessions(group_id, &device.id) } {
+            Ok(sessions) => sessions,
+            Err(AudioError::NoSessionsFound) => continue,
+            Err(e) => return Err(e),
+        };
+
+        for location in unsafe { icon_locations(&sessions[0]) } {
+            if let Some(png) = extract_icon_png(&location)? {
+                return Ok(png);
             }
+        }
+        return Err(AudioError::IconNotFound {
+            group_id: group_id.to_string(),
+        });
+    }
+
+    Err(AudioError::NoSessionsFound)
+}
+
+/// Locais de ícone candidatos de uma sessão, em ordem de preferência
+unsafe fn icon_locations(session: &IAudioSessionControl2) -> Vec<IconLocation> {
+    let mut locations = Vec::new();
+
+    if let Ok(icon_path) = session.GetIconPath() {
+        let value = icon_path.to_string();
+        CoTaskMemFree(Some(icon_path.0 as *const _));
+        if let Some(location) = value.ok().as_deref().and_then(IconLocation::parse) {
+            locations.push(location);
+        }
+    }
+
+    if is_system_sounds(session) {
+        locations.extend(IconLocation::parse(SYSTEM_SOUNDS_ICON));
+    } else if let Ok(path) = get_process_path(session.GetProcessId().unwrap_or(0)) {
+        locations.push(IconLocation::new(path, 0));
+    }
+
+    locations
+}
+
+/// Coleta as sessões de um dispositivo que pertencem ao grupo informado
+unsafe fn get_group_sessions(
+    group_id: &GroupId,
+    device_id: &str,
+) -> AudioResult<Vec<IAudioSessionControl2>> {
+    let device = get_device_endpoint_by_id(device_id)?;
+    let session_manager: IAudioSessionManager2 = device.Activate(CLSCTX_ALL, None)?;
+
+    let session_enum = session_manager.GetSessionEnumerator()?;
+    let count = session_enum.GetCount()?;
+
+    let mut group_sessions = Vec::new();
+    for i in 0..count {
+        let session = session_enum.GetSession(i)?;
+        let session2: IAudioSessionControl2 = session.cast()?;
+
+        let guid = session2.GetGroupingParam()?;
+        if GroupId::from(&guid) == *group_id {
+            group_sessions.push(session2);
+        }
+    }
+
+    if group_sessions.is_empty() {
+        return Err(AudioError::NoSessionsFound);
+    }
+
+    Ok(group_sessions)
+}
 
-            if found_sessions == 0 {
-                return Err(AudioError::NoSessionsFound);
+/// Obtém o volume por canal de um grupo de sessões
+///
+/// Todas as sessões do grupo compartilham o mesmo layout de canais,
+/// então os níveis da primeira sessão representam o grupo.
+pub fn get_group_channel_volumes(group_id: &GroupId, device_id: &str) -> AudioResult<Vec<f32>> {
+    let _com_ctx = ComContext::new()?;
+    unsafe {
+        let sessions = get_group_sessions(group_id, device_id)?;
+        let channel_volume: IChannelAudioVolume = sessions[0].cast()?;
+
+        let channel_count = channel_volume.GetChannelCount()?;
+        let mut levels = Vec::with_capacity(channel_count as usize);
+        for channel in 0..channel_count {
+            // Converte de scalar (0.0-1.0) para percentual (0-100)
+            levels.push(channel_volume.GetChannelVolume(channel)? * 100.0);
+        }
+        Ok(levels)
+    }
+}
+
+/// Define o volume por canal em todas as sessões de um grupo
+pub fn set_group_channel_volumes(
+    group_id: &GroupId,
+    device_id: &str,
+    levels: &[f32],
+) -> AudioResult<()> {
+    let _com_ctx = ComContext::new()?;
+    unsafe {
+        let sessions = get_group_sessions(group_id, device_id)?;
+        for session in &sessions {
+            let channel_volume: IChannelAudioVolume = session.cast()?;
+
+            let channel_count = channel_volume.GetChannelCount()? as usize;
+            if channel_count != levels.len() {
+                return Err(AudioError::ChannelCountMismatch {
+                    expected: channel_count,
+                    actual: levels.len(),
+                });
             }
 
-            Ok(())
-        },
-        Err(e) => Err(e),
+            for (channel, level) in levels.iter().enumerate() {
+                let scalar = (level / 100.0).clamp(0.0, 1.0);
+                channel_volume.SetChannelVolume(channel as u32, scalar, std::ptr::null())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Localiza uma sessão individual pelo identificador da instância
+unsafe fn find_session_by_id(
+    device_id: &str,
+    session_id: &str,
+) -> AudioResult<IAudioSessionControl2> {
+    let device = get_device_endpoint_by_id(device_id)?;
+    let session_manager: IAudioSessionManager2 = device.Activate(CLSCTX_ALL, None)?;
+
+    let session_enum = session_manager.GetSessionEnumerator()?;
+    let count = session_enum.GetCount()?;
+
+    for i in 0..count {
+        let session = session_enum.GetSession(i)?;
+        let session2: IAudioSessionControl2 = session.cast()?;
+
+        if get_session_instance_id(&session2)? == session_id {
+            return Ok(session2);
+        }
+    }
+
+    Err(AudioError::SessionNotFound {
+        session_id: session_id.to_string(),
+    })
+}
+
+/// Define o volume de uma única sessão, sem afetar o restante do grupo
+pub fn set_session_volume(device_id: &str, session_id: &str, volume: f32) -> AudioResult<()> {
+    let _com_ctx = ComContext::new()?;
+    unsafe {
+        let session = find_session_by_id(device_id, session_id)?;
+        let simple_volume: ISimpleAudioVolume = session.cast()?;
+
+        // Converte de percentual (0-100) para scalar (0.0-1.0)
+        let volume_scalar = (volume / 100.0).clamp(0.0, 1.0);
+        simple_volume.SetMasterVolume(volume_scalar, std::ptr::null())?;
+        Ok(())
+    }
+}
+
+/// Define o mute de todas as sessões de um grupo
+pub fn set_group_mute(group_id: &GroupId, device_id: &str, muted: bool) -> AudioResult<()> {
+    let _com_ctx = ComContext::new()?;
+    unsafe {
+        for session in get_group_sessions(group_id, device_id)? {
+            let simple_volume: ISimpleAudioVolume = session.cast()?;
+            simple_volume.SetMute(muted, std::ptr::null())?;
+        }
+        Ok(())
+    }
+}
+
+/// Define o mute de uma única sessão, sem afetar o restante do grupo
+pub fn set_session_mute(device_id: &str, session_id: &str, muted: bool) -> AudioResult<()> {
+    let _com_ctx = ComContext::new()?;
+    unsafe {
+        let session = find_session_by_id(device_id, session_id)?;
+        let simple_volume: ISimpleAudioVolume = session.cast()?;
+        simple_volume.SetMute(muted, std::ptr::null())?;
+        Ok(())
     }
 }
@@ -0,0 +1,392 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, TimeDelta};
+use tokio::sync::Notify;
+
+use crate::modules::audio_control::{
+    models::{DuckingConfig, DuckingStatus, GroupVolumeMode, SessionState},
+    platform::async_audio_system::AsyncAudioSystem,
+    services::application_service::list_all_sessions,
+    types::{AudioResult, GroupId},
+};
+use crate::modules::core::{
+    broadcasting::{next_event, EventReceiver},
+    persistence::JsonStore,
+    Broadcaster, Clock, ServerEvent,
+};
+
+/// Intervalo entre os passos de uma rampa em andamento
+const RAMP_STEP: Duration = Duration::from_millis(250);
+
+/// Transição linear do fator aplicado aos volumes
+#[derive(Debug, Clone, Copy)]
+struct Ramp {
+    from: f32,
+    to: f32,
+    started_at: Option<NaiveDateTime>,
+    duration: TimeDelta,
+}
+
+impl Default for Ramp {
+    fn default() -> Self {
+        Self {
+            from: 1.0,
+            to: 1.0,
+            started_at: None,
+            duration: TimeDelta::zero(),
+        }
+    }
+}
+
+impl Ramp {
+    fn factor(&self, now: NaiveDateTime) -> f32 {
+        let Some(started_at) = self.started_at else {
+            return self.to;
+        };
+        if self.duration <= TimeDelta::zero() {
+            return self.to;
+        }
+        let progress = ((now - started_at).num_milliseconds() as f32
+            / self.duration.num_milliseconds() as f32)
+            .clamp(0.0, 1.0);
+        self.from + (self.to - self.from) * progress
+    }
+}
+
+#[derive(Debug)]
+struct DuckedGroup {
+    group_id: GroupId,
+    /// Volume do grupo antes do ducking
+    original: f32,
+    /// Último fator aplicado, para não reescrever o volume a cada leitura
+    applied: Option<f32>,
+}
+
+/// Volume a escrever em um grupo, copiado do estado antes da chamada ao
+/// backend
+struct GroupWrite {
+    key: (String, String),
+    group_id: GroupId,
+    volume: f32,
+    factor: f32,
+}
+
+impl GroupWrite {
+    fn new(key: &(String, String), ducked: &DuckedGroup, factor: f32) -> Self {
+        Self {
+            key: key.clone(),
+            group_id: ducked.group_id.clone(),
+            volume: ducked.original * factor,
+            factor,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct DuckingState {
+    active: bool,
+    ramp: Ramp,
+    /// Sessões tocando áudio: id -> nome do processo
+    playing: HashMap<String, String>,
+    /// Os grupos precisam ser relidos do backend no próximo passo
+    stale: bool,
+    /// Grupos abaixados, por (dispositivo, grupo)
+    groups: HashMap<(String, String), DuckedGroup>,
+}
+
+/// Ducking automático
+///
+/// Acompanha os eventos de sessão do monitor: enquanto uma sessão de
+/// aplicação de comunicação estiver tocando áudio, os demais grupos são
+/// abaixados em rampa e, quando ela para, voltam ao volume de antes.
+/// Mudanças de volume feitas em um grupo abaixado são desfeitas na
+/// restauração.
+pub struct DuckingService {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    broadcaster: Broadcaster,
+    config: JsonStore<DuckingConfig>,
+    clock: Arc<dyn Clock>,
+    state: Mutex<DuckingState>,
+    /// Acorda a tarefa de fundo quando a configuração muda
+    config_changed: Notify,
+}
+
+impl DuckingService {
+    pub fn new(
+        audio_system: Arc<dyn AsyncAudioSystem>,
+        broadcaster: Broadcaster,
+        config: JsonStore<DuckingConfig>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            audio_system,
+            broadcaster,
+            config,
+            clock,
+            state: Mutex::new(DuckingState::default()),
+            config_changed: Notify::new(),
+        }
+    }
+
+    pub fn status(&self) -> DuckingStatus {
+        let state = self.lock_state();
+        DuckingStatus {
+            config: self.config.read(Clone::clone),
+            active: state.active,
+            ducked_groups: state.groups.len(),
+        }
+    }
+
+    /// Substitui a configuração; passa a valer no próximo passo
+    pub fn set_config(&self, config: DuckingConfig) -> AudioResult<DuckingStatus> {
+        let config = config.normalized()?;
+        self.config.update(|current| *current = config)?;
+        // Exclusões novas mudam quais grupos podem ser abaixados
+        self.lock_state().stale = true;
+        self.config_changed.notify_one();
+        Ok(self.status())
+    }
+
+    /// Lê do backend as sessões que já estão tocando áudio
+    ///
+    /// O monitor não publica eventos na primeira leitura, então as sessões
+    /// abertas antes do servidor subir vêm daqui.
+    pub async fn load_sessions(&self) -> AudioResult<()> {
+        let devices = list_all_sessions(self.audio_system.as_ref(), true).await?;
+        let mut state = self.lock_state();
+        for device in &devices {
+            for group in &device.sessions {
+                for member in group.sessions.iter().flatten() {
+                    if member.state == SessionState::Active {
+                        state
+                            .playing
+                            .insert(member.session_id.clone(), member.process_name.clone());
+                    }
+                }
+            }
+        }
+        state.stale = true;
+        Ok(())
+    }
+
+    /// Atualiza as sessões tocando áudio a partir de um evento do monitor
+    ///
+    /// Retorna se o evento pede um novo passo.
+    pub fn observe(&self, event: &ServerEvent) -> bool {
+        let mut state = self.lock_state();
+        let (session_id, process_name, active) = match event {
+            ServerEvent::SessionStarted {
+                session_id,
+                process_name,
+                active,
+                ..
+            }
+            | ServerEvent::SessionActivityChanged {
+                session_id,
+                process_name,
+                active,
+                ..
+            } => (session_id, Some(process_name), *active),
+            ServerEvent::SessionEnded { session_id, .. } => (session_id, None, false),
+            _ => return false,
+        };
+
+        let changed = match (active, process_name) {
+            (true, Some(process_name)) => state
+                .playing
+                .insert(session_id.clone(), process_name.clone())
+                .is_none(),
+            _ => state.playing.remove(session_id).is_some(),
+        };
+        // Sessões novas ou encerradas mudam os grupos a abaixar
+        let regroup = (state.active || !state.groups.is_empty())
+            && !matches!(event, ServerEvent::SessionActivityChanged { .. });
+        if regroup {
+            state.stale = true;
+        }
+        changed || regroup
+    }
+
+    /// Inicia as rampas necessárias e aplica o volume atual aos grupos
+    /// abaixados
+    ///
+    /// Os grupos só são relidos do backend quando o ducking começa ou quando
+    /// sessões ou a configuração mudam; os passos da rampa reaplicam o fator
+    /// aos grupos já conhecidos. O estado não fica travado durante as
+    /// chamadas ao backend. Retorna se o ducking está ativo.
+    pub async fn tick(&self) -> AudioResult<bool> {
+        let config = self.config.read(Clone::clone);
+        let now = self.clock.now();
+        let (factor, stale) = {
+            let mut state = self.lock_state();
+            if !config.enabled && !state.active && state.groups.is_empty() {
+                return Ok(false);
+            }
+
+            let communicating = config.enabled
+                && state
+                    .playing
+                    .values()
+                    .any(|process_name| config.is_communication_app(process_name));
+
+            if communicating != state.active {
+                let (to, ramp_ms) = if communicating {
+                    (1.0 - config.reduction / 100.0, config.ramp_down_ms)
+                } else {
+                    (1.0, config.ramp_up_ms)
+                };
+                state.active = communicating;
+                state.stale |= communicating;
+                state.ramp = Ramp {
+                    from: state.ramp.factor(now),
+                    to,
+                    started_at: Some(now),
+                    duration: TimeDelta::milliseconds(ramp_ms as i64),
+                };
+                let _ = self.broadcaster.broadcast(ServerEvent::DuckingChanged {
+                    active: communicating,
+                });
+            }
+
+            // Eventos que chegarem durante a releitura marcam de novo
+            (state.ramp.factor(now), std::mem::take(&mut state.stale))
+        };
+
+        let writes = if stale {
+            self.regroup(&config, factor).await.inspect_err(|_| {
+                self.lock_state().stale = true;
+            })?
+        } else {
+            self.lock_state()
+                .groups
+                .iter()
+                .filter(|(_, ducked)| ducked.applied != Some(factor))
+                .map(|(key, ducked)| GroupWrite::new(key, ducked, factor))
+                .collect()
+        };
+        self.apply(writes).await;
+
+        let mut state = self.lock_state();
+        if !state.active && factor >= 1.0 {
+            state.groups.clear();
+        }
+        Ok(state.active)
+    }
+
+    /// Acompanha os eventos de sessão até o broadcaster fechar
+    ///
+    /// Um passo roda a cada evento relevante e, enquanto uma rampa estiver em
+    /// andamento, a cada `RAMP_STEP`.
+    pub async fn run(self: Arc<Self>, mut events: EventReceiver) {
+        if let Err(error) = self.load_sessions().await {
+            tracing::warn!("Failed to read sessions for ducking: {}", error);
+        }
+        self.step().await;
+
+        loop {
+            let ramping = self.ramping();
+            tokio::select! {
+                received = next_event(&mut events, "Ducking") => match received {
+                    Some(event) if self.observe(&event) => {}
+                    Some(_) => continue,
+                    None => break,
+                },
+                _ = self.config_changed.notified() => {}
+                _ = tokio::time::sleep(RAMP_STEP), if ramping => {}
+            }
+            self.step().await;
+        }
+    }
+
+    async fn step(&self) {
+        if let Err(error) = self.tick().await {
+            tracing::warn!("Ducking step failed: {}", error);
+        }
+    }
+
+    /// Se há uma rampa em andamento sobre grupos abaixados
+    fn ramping(&self) -> bool {
+        let state = self.lock_state();
+        !state.groups.is_empty() && state.ramp.factor(self.clock.now()) != state.ramp.to
+    }
+
+    /// Relê os grupos de todos os dispositivos, passando a abaixar os novos e
+    /// soltando os que sumiram ou foram excluídos
+    ///
+    /// Retorna as escritas a fazer; a leitura acontece sem o estado travado.
+    async fn regroup(&self, config: &DuckingConfig, factor: f32) -> AudioResult<Vec<GroupWrite>> {
+        let devices = list_all_sessions(self.audio_system.as_ref(), false).await?;
+        let mut state = self.lock_state();
+        let mut writes = Vec::new();
+        let mut present = HashSet::new();
+        for device in &devices {
+            for group in &device.sessions {
+                let key = (device.device_id.clone(), group.id.to_string());
+                let can_duck = config.can_duck(&group.app.process_name);
+                if state.active && can_duck {
+                    state
+                        .groups
+                        .entry(key.clone())
+                        .or_insert_with(|| DuckedGroup {
+                            group_id: group.id.clone(),
+                            original: group.volume_level,
+                            applied: None,
+                        });
+                }
+
+                // Aplicação excluída depois de abaixada volta na hora
+                match state.groups.get(&key) {
+                    Some(ducked) if !can_duck => writes.push(GroupWrite::new(&key, ducked, 1.0)),
+                    Some(ducked) if ducked.applied != Some(factor) => {
+                        writes.push(GroupWrite::new(&key, ducked, factor))
+                    }
+                    _ => {}
+                }
+                if !can_duck {
+                    state.groups.remove(&key);
+                }
+                present.insert(key);
+            }
+        }
+
+        state.groups.retain(|key, _| present.contains(key));
+        Ok(writes)
+    }
+
+    /// Escreve os volumes e registra o fator aplicado nos grupos que ainda
+    /// estão abaixados
+    async fn apply(&self, writes: Vec<GroupWrite>) {
+        for write in writes {
+            let (device_id, _) = &write.key;
+            // Proporcional para manter as diferenças entre as sessões do grupo
+            let result = self
+                .audio_system
+                .set_group_volume(
+                    &write.group_id,
+                    device_id,
+                    write.volume,
+                    GroupVolumeMode::Proportional,
+                )
+                .await;
+            match result {
+                Ok(()) => {
+                    if let Some(ducked) = self.lock_state().groups.get_mut(&write.key) {
+                        ducked.applied = Some(write.factor);
+                    }
+                }
+                Err(error) => tracing::warn!(
+                    "Failed to duck group {} on {}: {}",
+                    write.group_id,
+                    device_id,
+                    error
+                ),
+            }
+        }
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, DuckingState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,53 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::modules::{
+    audio_control::types::AudioResult,
+    core::{
+        broadcasting::{next_event, EventReceiver},
+        ServerEvent,
+    },
+};
+
+/// Repassa a `handle` cada evento aceito por `accept`, até o broadcaster fechar
+///
+/// Falhas de `handle` são registradas em log em nome de `listener` sem
+/// interromper o loop. O receiver é criado por quem inicia a tarefa, para
+/// que nenhum evento publicado entre o início e a primeira execução seja
+/// perdido.
+pub async fn handle_events<S, F, Fut, R>(
+    service: Arc<S>,
+    mut events: EventReceiver,
+    listener: &'static str,
+    accept: fn(&ServerEvent) -> bool,
+    handle: F,
+) where
+    F: Fn(Arc<S>, ServerEvent) -> Fut,
+    Fut: Future<Output = AudioResult<R>>,
+{
+    while let Some(event) = next_event(&mut events, listener).await {
+        if accept(&event) {
+            handle_event(&service, event, listener, &handle).await;
+        }
+    }
+}
+
+/// Repassa um único evento a `handle`, como em `handle_events`
+pub async fn handle_event<S, F, Fut, R>(
+    service: &Arc<S>,
+    event: ServerEvent,
+    listener: &'static str,
+    handle: F,
+) where
+    F: Fn(Arc<S>, ServerEvent) -> Fut,
+    Fut: Future<Output = AudioResult<R>>,
+{
+    if let Err(error) = handle(Arc::clone(service), event).await {
+        tracing::warn!("{} failed to handle event: {}", listener, error);
+    }
+}
+
+/// Filtro para `handle_events` dos serviços que reagem a sessões novas
+pub fn is_session_started(event: &ServerEvent) -> bool {
+    matches!(event, ServerEvent::SessionStarted { .. })
+}
@@ -0,0 +1,363 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, TimeDelta};
+
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{
+        audio_requests::StartFocusModeRequest, normalize_process_name, FocusModeStatus,
+        LIMIT_TOLERANCE,
+    },
+    platform::async_audio_system::AsyncAudioSystem,
+    services::{application_service::list_all_sessions, Notifier},
+    types::AudioResult,
+};
+use crate::modules::core::{Broadcaster, Clock, ServerEvent};
+
+/// Intervalo de verificação de novas sessões, do teto e do timer
+const FOCUS_MODE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Sessão identificada pelo dispositivo onde toca
+type SessionKey = (String, String);
+
+/// O que o modo foco mudou e precisa desfazer ao desligar
+#[derive(Debug, Default)]
+struct FocusChanges {
+    /// Sessões já avaliadas, silenciadas ou não; não são tocadas de novo
+    seen: HashSet<SessionKey>,
+    /// Sessões que o modo foco silenciou
+    muted: BTreeSet<SessionKey>,
+    /// Volume master antes do teto, por dispositivo
+    master_volumes: BTreeMap<String, f32>,
+}
+
+impl FocusChanges {
+    /// Junta as mudanças de um passo; o volume mais antigo de cada
+    /// dispositivo é o que volta
+    fn merge(&mut self, other: FocusChanges) {
+        self.seen.extend(other.seen);
+        self.muted.extend(other.muted);
+        for (device_id, volume) in other.master_volumes {
+            self.master_volumes.entry(device_id).or_insert(volume);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FocusSession {
+    /// Distingue uma sessão de outra iniciada depois dela
+    id: u64,
+    allowlist: Vec<String>,
+    max_master: Option<f32>,
+    started_at: NaiveDateTime,
+    ends_at: Option<NaiveDateTime>,
+    changes: FocusChanges,
+}
+
+impl FocusSession {
+    fn status(&self, now: NaiveDateTime) -> FocusModeStatus {
+        FocusModeStatus {
+            active: true,
+            allowlist: self.allowlist.clone(),
+            max_master: self.max_master,
+            started_at: Some(self.started_at),
+            ends_at: self.ends_at,
+            remaining_secs: self.remaining_secs(now),
+            muted_sessions: self.changes.muted.len(),
+        }
+    }
+
+    fn remaining_secs(&self, now: NaiveDateTime) -> Option<u64> {
+        self.ends_at
+            .map(|ends_at| ((ends_at - now).num_milliseconds().max(0) as u64).div_ceil(1000))
+    }
+}
+
+/// Modo foco (não perturbe)
+///
+/// Silencia todas as sessões fora da `allowlist`, inclusive as que surgem
+/// enquanto ele está ligado, descarta os avisos do `Notifier` e, com
+/// `max_master`, limita o volume do dispositivo padrão. Ao desligar (ou ao
+/// fim do timer) volta exatamente ao estado anterior: só as sessões que ele
+/// silenciou voltam a tocar e só os volumes que ele baixou são restaurados.
+/// Não sobrevive a reinícios do servidor.
+///
+/// A sessão só fica travada para copiar ou registrar o estado; as escritas
+/// no backend acontecem com ela solta.
+pub struct FocusModeService {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    broadcaster: Broadcaster,
+    notifier: Notifier,
+    clock: Arc<dyn Clock>,
+    session: Mutex<Option<FocusSession>>,
+    next_id: AtomicU64,
+}
+
+impl FocusModeService {
+    pub fn new(
+        audio_system: Arc<dyn AsyncAudioSystem>,
+        broadcaster: Broadcaster,
+        notifier: Notifier,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            audio_system,
+            broadcaster,
+            notifier,
+            clock,
+            session: Mutex::new(None),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn status(&self) -> FocusModeStatus {
+        let now = self.clock.now();
+        self.lock_session()
+            .as_ref()
+            .map(|session| session.status(now))
+            .unwrap_or_default()
+    }
+
+    /// Liga o modo foco; se já estiver ligado, o estado anterior é
+    /// restaurado antes de aplicar a nova configuração
+    pub async fn start(&self, request: StartFocusModeRequest) -> AudioResult<FocusModeStatus> {
+        let ends_after = match request.duration_secs {
+            Some(0) => {
+                return Err(AudioError::InvalidRequestBody {
+                    message: "duration_secs must be greater than zero".to_string(),
+                })
+            }
+            Some(secs) => Some(
+                i64::try_from(secs)
+                    .ok()
+                    .and_then(TimeDelta::try_seconds)
+                    .ok_or_else(|| AudioError::InvalidRequestBody {
+                        message: "duration_secs is too long".to_string(),
+                    })?,
+            ),
+            None => None,
+        };
+
+        let mut allowlist: Vec<String> = request
+            .allowlist
+            .iter()
+            .map(|name| normalize_process_name(name))
+            .filter(|name| !name.is_empty())
+            .collect();
+        allowlist.sort();
+        allowlist.dedup();
+
+        let now = self.clock.now();
+        let mut session = FocusSession {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            allowlist,
+            max_master: request.max_master.map(f32::from),
+            started_at: now,
+            ends_at: ends_after.map(|duration| now + duration),
+            changes: FocusChanges::default(),
+        };
+
+        let previous = self.lock_session().take();
+        if let Some(previous) = previous {
+            self.restore(&previous.changes).await;
+        }
+        self.notifier.set_suppressed(true);
+        let applied = self
+            .apply(
+                &session.allowlist,
+                session.max_master,
+                &HashSet::new(),
+                &mut session.changes,
+            )
+            .await;
+        if let Err(error) = applied {
+            self.restore(&session.changes).await;
+            self.notifier.set_suppressed(false);
+            return Err(error);
+        }
+        let status = session.status(now);
+        let replaced = self.lock_session().replace(session);
+        // Outro início concorrente perde para este
+        if let Some(replaced) = replaced {
+            self.restore(&replaced.changes).await;
+        }
+
+        let _ = self.broadcaster.broadcast(ServerEvent::FocusModeChanged {
+            active: true,
+            remaining_secs: status.remaining_secs,
+        });
+        Ok(status)
+    }
+
+    /// Desliga o modo foco e restaura o estado anterior
+    pub async fn stop(&self) -> AudioResult<FocusModeStatus> {
+        let session = self
+            .lock_session()
+            .take()
+            .ok_or(AudioError::FocusModeInactive)?;
+        self.finish(&session).await;
+        Ok(FocusModeStatus::default())
+    }
+
+    /// Silencia as sessões novas, mantém o teto e desliga o modo foco
+    /// quando o timer vence
+    ///
+    /// Retorna se o modo foco continua ligado.
+    pub async fn tick(&self) -> AudioResult<bool> {
+        let now = self.clock.now();
+        let snapshot = {
+            let mut guard = self.lock_session();
+            let Some(session) = guard.as_ref() else {
+                return Ok(false);
+            };
+            if session.ends_at.is_some_and(|ends_at| now >= ends_at) {
+                Err(guard.take())
+            } else {
+                Ok((
+                    session.id,
+                    session.allowlist.clone(),
+                    session.max_master,
+                    session.changes.seen.clone(),
+                ))
+            }
+        };
+
+        let (id, allowlist, max_master, seen) = match snapshot {
+            Ok(snapshot) => snapshot,
+            Err(expired) => {
+                if let Some(session) = expired {
+                    self.finish(&session).await;
+                }
+                return Ok(false);
+            }
+        };
+        let mut changes = FocusChanges::default();
+        let result = self
+            .apply(&allowlist, max_master, &seen, &mut changes)
+            .await;
+
+        let unclaimed = match self.lock_session().as_mut() {
+            Some(session) if session.id == id => {
+                session.changes.merge(changes);
+                None
+            }
+            _ => Some(changes),
+        };
+        // Desligado (ou trocado) durante o passo: desfaz o que ele fez
+        if let Some(changes) = unclaimed {
+            self.restore(&changes).await;
+            return Ok(false);
+        }
+        result.map(|()| true)
+    }
+
+    /// Acompanha o modo foco periodicamente até o runtime ser encerrado
+    pub async fn run(self: Arc<Self>) {
+        let mut ticker = tokio::time::interval(FOCUS_MODE_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            ticker.tick().await;
+            if self.lock_session().is_none() {
+                continue;
+            }
+
+            if let Err(error) = self.tick().await {
+                tracing::warn!("Focus mode step failed: {}", error);
+            }
+        }
+    }
+
+    /// Silencia as sessões ainda não vistas fora da `allowlist` e aplica o
+    /// teto, anotando em `changes` o que mudou
+    async fn apply(
+        &self,
+        allowlist: &[String],
+        max_master: Option<f32>,
+        seen: &HashSet<SessionKey>,
+        changes: &mut FocusChanges,
+    ) -> AudioResult<()> {
+        for device in list_all_sessions(self.audio_system.as_ref(), true).await? {
+            for group in &device.sessions {
+                let allowed = allowlist.contains(&normalize_process_name(&group.app.process_name));
+                for member in group.sessions.iter().flatten() {
+                    let key = (device.device_id.clone(), member.session_id.clone());
+                    if seen.contains(&key)
+                        || !changes.seen.insert(key.clone())
+                        || allowed
+                        || member.muted
+                    {
+                        continue;
+                    }
+                    match self
+                        .audio_system
+                        .set_session_mute(&device.device_id, &member.session_id, true)
+                        .await
+                    {
+                        Ok(()) => {
+                            changes.muted.insert(key);
+                        }
+                        Err(error) => tracing::warn!(
+                            "Focus mode failed to mute session {}: {}",
+                            member.session_id,
+                            error
+                        ),
+                    }
+                }
+            }
+        }
+
+        if let Some(cap) = max_master {
+            if let Some(device_id) = self.audio_system.default_device_id().await? {
+                let volume = self.audio_system.get_device_volume(&device_id).await?;
+                if volume > cap + LIMIT_TOLERANCE {
+                    self.audio_system.set_device_volume(&device_id, cap).await?;
+                    changes.master_volumes.entry(device_id).or_insert(volume);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn finish(&self, session: &FocusSession) {
+        self.restore(&session.changes).await;
+        self.notifier.set_suppressed(false);
+        let _ = self.broadcaster.broadcast(ServerEvent::FocusModeChanged {
+            active: false,
+            remaining_secs: None,
+        });
+    }
+
+    async fn restore(&self, changes: &FocusChanges) {
+        for (device_id, session_id) in &changes.muted {
+            // Sessões encerradas enquanto o modo foco estava ligado somem
+            if let Err(error) = self
+                .audio_system
+                .set_session_mute(device_id, session_id, false)
+                .await
+            {
+                tracing::debug!("Focus mode could not unmute {}: {}", session_id, error);
+            }
+        }
+        for (device_id, volume) in &changes.master_volumes {
+            if let Err(error) = self
+                .audio_system
+                .set_device_volume(device_id, *volume)
+                .await
+            {
+                tracing::warn!(
+                    "Focus mode failed to restore volume of {}: {}",
+                    device_id,
+                    error
+                );
+            }
+        }
+    }
+
+    fn lock_session(&self) -> MutexGuard<'_, Option<FocusSession>> {
+        self.session.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
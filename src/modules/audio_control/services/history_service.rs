@@ -0,0 +1,259 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{HistoryEntry, HistoryTarget, HistoryValue, SessionVolume},
+    platform::async_audio_system::AsyncAudioSystem,
+    types::{AudioResult, GroupId},
+};
+use crate::modules::core::{ClientId, Clock};
+
+/// Quantidade de mudanças guardadas; as mais antigas são descartadas
+const HISTORY_CAPACITY: usize = 200;
+
+#[derive(Debug, Default)]
+struct HistoryState {
+    entries: VecDeque<HistoryEntry>,
+    next_id: u64,
+}
+
+/// Histórico das mudanças de volume, mute e canais feitas pela API
+///
+/// Cada cliente desfaz e refaz só as próprias mudanças, da mais recente
+/// para a mais antiga. Mover grupos, aplicar perfis e mudar preferências de
+/// roteamento entram só como marcos que não podem ser desfeitos. Uma mudança nova do cliente descarta o que ele
+/// tinha desfeito. Desfazer volta ao valor anterior à mudança mesmo que
+/// o alvo tenha sido alterado depois por outro caminho. O histórico fica
+/// em memória.
+pub struct HistoryService {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    clock: Arc<dyn Clock>,
+    state: Mutex<HistoryState>,
+}
+
+impl HistoryService {
+    pub fn new(audio_system: Arc<dyn AsyncAudioSystem>, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            audio_system,
+            clock,
+            state: Mutex::new(HistoryState::default()),
+        }
+    }
+
+    /// Registra uma mudança aplicada pela API
+    ///
+    /// `old_value` vem do alvo que o handler já tinha lido para aplicar a
+    /// mudança e `new_value` é o que ele escreveu, então nada é relido do
+    /// backend. Mudanças que não alteraram o valor não são registradas.
+    pub fn record(
+        &self,
+        client: &ClientId,
+        target: HistoryTarget,
+        old_value: HistoryValue,
+        new_value: HistoryValue,
+    ) {
+        if new_value != old_value {
+            self.push(client, target, old_value, new_value);
+        }
+    }
+
+    /// Registra uma mudança que não pode ser desfeita
+    ///
+    /// Ela descarta o que o cliente tinha desfeito, como qualquer mudança
+    /// nova, e `undo` para nela em vez de pular para as anteriores.
+    pub fn record_irreversible(&self, client: &ClientId, action: &str) {
+        self.push(
+            client,
+            HistoryTarget::Irreversible {
+                action: action.to_string(),
+            },
+            HistoryValue::Unknown,
+            HistoryValue::Unknown,
+        );
+    }
+
+    /// Mudanças de todos os clientes, da mais recente para a mais antiga
+    pub fn list(&self) -> Vec<HistoryEntry> {
+        self.lock_state().entries.iter().rev().cloned().collect()
+    }
+
+    /// Desfaz a última mudança do cliente que ainda não foi desfeita
+    ///
+    /// A mudança é marcada antes da escrita, com o histórico solto durante
+    /// a chamada ao backend, e volta ao que era se a escrita falhar.
+    pub async fn undo(&self, client: &ClientId) -> AudioResult<HistoryEntry> {
+        let entry = {
+            let mut state = self.lock_state();
+            let entry = state
+                .entries
+                .iter_mut()
+                .rev()
+                .find(|entry| entry.client == client.as_str() && !entry.undone)
+                .ok_or(AudioError::NothingToUndo)?;
+            if let HistoryTarget::Irreversible { action } = &entry.target {
+                return Err(AudioError::NotUndoable {
+                    action: action.clone(),
+                });
+            }
+            entry.undone = true;
+            entry.clone()
+        };
+
+        if let Err(error) = self.write(&entry.target, &entry.old_value).await {
+            self.set_undone(entry.id, false);
+            return Err(error);
+        }
+        Ok(entry)
+    }
+
+    /// Refaz a última mudança desfeita pelo cliente
+    pub async fn redo(&self, client: &ClientId) -> AudioResult<HistoryEntry> {
+        let entry = {
+            let mut state = self.lock_state();
+            // As desfeitas de um cliente são sempre as últimas dele, então a
+            // mais antiga delas é a última que foi desfeita
+            let entry = state
+                .entries
+                .iter_mut()
+                .find(|entry| entry.client == client.as_str() && entry.undone)
+                .ok_or(AudioError::NothingToRedo)?;
+            entry.undone = false;
+            entry.clone()
+        };
+
+        if let Err(error) = self.write(&entry.target, &entry.new_value).await {
+            self.set_undone(entry.id, true);
+            return Err(error);
+        }
+        Ok(entry)
+    }
+
+    fn push(
+        &self,
+        client: &ClientId,
+        target: HistoryTarget,
+        old_value: HistoryValue,
+        new_value: HistoryValue,
+    ) {
+        let timestamp = self.clock.now();
+        let mut state = self.lock_state();
+        state
+            .entries
+            .retain(|entry| !(entry.undone && entry.client == client.as_str()));
+        let id = state.next_id;
+        state.next_id += 1;
+        state.entries.push_back(HistoryEntry {
+            id,
+            client: client.to_string(),
+            timestamp,
+            target,
+            old_value,
+            new_value,
+            undone: false,
+        });
+        while state.entries.len() > HISTORY_CAPACITY {
+            state.entries.pop_front();
+        }
+    }
+
+    /// Desfaz a marcação de uma escrita que falhou; a mudança pode ter
+    /// saído do histórico nesse meio tempo
+    fn set_undone(&self, id: u64, undone: bool) {
+        if let Some(entry) = self
+            .lock_state()
+            .entries
+            .iter_mut()
+            .find(|entry| entry.id == id)
+        {
+            entry.undone = undone;
+        }
+    }
+
+    async fn write(&self, target: &HistoryTarget, value: &HistoryValue) -> AudioResult<()> {
+        match (target, value) {
+            (HistoryTarget::DeviceVolume { device_id }, HistoryValue::Volume(volume)) => {
+                self.audio_system
+                    .set_device_volume(device_id, *volume)
+                    .await
+            }
+            (HistoryTarget::DeviceChannels { device_id }, HistoryValue::Levels(levels)) => {
+                self.audio_system
+                    .set_device_channel_volumes(device_id, levels)
+                    .await
+            }
+            (
+                HistoryTarget::GroupChannels {
+                    device_id,
+                    group_id,
+                },
+                HistoryValue::Levels(levels),
+            ) => {
+                self.audio_system
+                    .set_group_channel_volumes(&GroupId::new(group_id.clone()), device_id, levels)
+                    .await
+            }
+            (
+                HistoryTarget::SessionVolume {
+                    device_id,
+                    session_id,
+                },
+                HistoryValue::Volume(volume),
+            ) => {
+                self.audio_system
+                    .set_session_volume(device_id, session_id, *volume)
+                    .await
+            }
+            (
+                HistoryTarget::SessionMute {
+                    device_id,
+                    session_id,
+                },
+                HistoryValue::Muted(muted),
+            ) => {
+                self.audio_system
+                    .set_session_mute(device_id, session_id, *muted)
+                    .await
+            }
+            (
+                HistoryTarget::GroupVolume { .. } | HistoryTarget::ApplicationVolume { .. },
+                HistoryValue::Sessions(sessions),
+            ) => self.write_sessions(sessions).await,
+            _ => Err(AudioError::InvalidRequestBody {
+                message: format!("History value does not match target {:?}", target),
+            }),
+        }
+    }
+
+    /// Restaura o volume de cada sessão; sessões já encerradas são ignoradas
+    /// enquanto ao menos uma puder ser restaurada
+    async fn write_sessions(&self, sessions: &[SessionVolume]) -> AudioResult<()> {
+        let mut restored = 0;
+        let mut last_error = None;
+        for session in sessions {
+            match self
+                .audio_system
+                .set_session_volume(&session.device_id, &session.session_id, session.volume)
+                .await
+            {
+                Ok(()) => restored += 1,
+                Err(error) => {
+                    tracing::debug!(
+                        "History could not restore session {}: {}",
+                        session.session_id,
+                        error
+                    );
+                    last_error = Some(error);
+                }
+            }
+        }
+        match last_error {
+            Some(error) if restored == 0 => Err(error),
+            _ => Ok(()),
+        }
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, HistoryState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,33 @@
+use windows::{
+    core::Error,
+    Win32::UI::Input::KeyboardAndMouse::{
+        SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP,
+        VK_MEDIA_PLAY_PAUSE,
+    },
+};
+
+use crate::modules::audio_control::types::audio_result::AudioResult;
+
+/// Envia a tecla de mídia play/pause, como um teclado multimídia
+///
+/// O Windows entrega a tecla à aplicação dona da sessão de mídia do sistema
+/// (a mesma controlada pelo painel de mídia da barra de tarefas).
+pub fn send_media_play_pause() -> AudioResult<()> {
+    let key = |flags: KEYBD_EVENT_FLAGS| INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: VK_MEDIA_PLAY_PAUSE,
+                dwFlags: flags,
+                ..Default::default()
+            },
+        },
+    };
+    let inputs = [key(KEYBD_EVENT_FLAGS(0)), key(KEYEVENTF_KEYUP)];
+
+    let sent = unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+    if sent as usize != inputs.len() {
+        return Err(Error::from_win32().into());
+    }
+    Ok(())
+}
@@ -1,7 +1,66 @@
+pub mod action_executor;
+pub mod app_routing_service;
+pub mod application_service;
 pub mod audio_device_service;
+pub mod audio_meter_service;
+pub mod audio_policy_service;
 pub mod audio_session_service;
+pub mod ducking_service;
+pub mod event_loop;
+pub mod focus_mode_service;
+pub mod history_service;
+pub mod media_key_service;
+pub mod notifier;
+pub mod profile_service;
+pub mod rules_service;
+pub mod scheduler_service;
+pub mod session_monitor;
+pub mod sleep_timer_service;
+pub mod state_service;
+pub mod volume_coalescer;
+pub mod volume_limit_service;
+pub mod volume_link_service;
+pub mod volume_memory_service;
 
+pub use action_executor::ActionExecutor;
+pub use app_routing_service::AppRoutingService;
+pub use application_service::{
+    list_all_sessions, list_applications, set_application_mute, set_application_volume,
+    set_application_volume_in,
+};
 pub use audio_device_service::get_actual_volume;
+pub use audio_device_service::get_device_channel_volumes;
+pub use audio_device_service::get_device_mute;
+pub use audio_device_service::get_device_volume;
 pub use audio_device_service::list_output_devices;
+pub use audio_device_service::set_device_channel_volumes;
+pub use audio_device_service::set_device_mute;
+pub use audio_device_service::set_device_volume;
+pub use audio_meter_service::get_device_peak;
+pub use audio_meter_service::get_group_peaks;
+pub use audio_policy_service::move_group_to_device;
+pub use audio_policy_service::set_default_device;
+pub use audio_policy_service::set_process_output_device;
+pub use audio_session_service::get_group_channel_volumes;
+pub use audio_session_service::get_group_icon;
 pub use audio_session_service::get_session_for_device;
+pub use audio_session_service::set_group_channel_volumes;
+pub use audio_session_service::set_group_mute;
 pub use audio_session_service::set_group_volume;
+pub use audio_session_service::set_session_mute;
+pub use audio_session_service::set_session_volume;
+pub use ducking_service::DuckingService;
+pub use focus_mode_service::FocusModeService;
+pub use history_service::HistoryService;
+pub use media_key_service::send_media_play_pause;
+pub use notifier::Notifier;
+pub use profile_service::ProfileService;
+pub use rules_service::RulesService;
+pub use scheduler_service::SchedulerService;
+pub use session_monitor::SessionMonitor;
+pub use sleep_timer_service::SleepTimerService;
+pub use state_service::StateService;
+pub use volume_coalescer::{VolumeCoalescer, WriteOutcome};
+pub use volume_limit_service::VolumeLimitService;
+pub use volume_link_service::VolumeLinkService;
+pub use volume_memory_service::VolumeMemoryService;
@@ -0,0 +1,43 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::modules::core::{Broadcaster, ServerEvent};
+
+/// Publica os avisos (`ServerEvent::Notification`) dos serviços
+///
+/// Enquanto o modo foco está ligado os avisos são descartados aqui, antes
+/// de chegar ao broadcaster. Os clones compartilham esse estado.
+#[derive(Clone)]
+pub struct Notifier {
+    broadcaster: Broadcaster,
+    suppressed: Arc<AtomicBool>,
+}
+
+impl Notifier {
+    pub fn new(broadcaster: Broadcaster) -> Self {
+        Self {
+            broadcaster,
+            suppressed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Publica um aviso, a não ser que estejam sendo descartados
+    pub fn notify(&self, title: String, message: String) {
+        if self.is_suppressed() {
+            return;
+        }
+        // Sem clientes inscritos o envio falha, o que não é um erro aqui
+        let _ = self
+            .broadcaster
+            .broadcast(ServerEvent::Notification { title, message });
+    }
+
+    /// Liga ou desliga o descarte dos avisos
+    pub fn set_suppressed(&self, suppressed: bool) {
+        self.suppressed.store(suppressed, Ordering::Relaxed);
+    }
+
+    pub fn is_suppressed(&self) -> bool {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{
+        normalize_process_name, ApplicationSetting, DeviceVolumeSetting, GroupVolumeMode,
+        MuteState, ProfileApplyReport, SoundProfile,
+    },
+    platform::async_audio_system::AsyncAudioSystem,
+    services::{
+        application_service::list_all_sessions,
+        event_loop::{handle_events, is_session_started},
+    },
+    types::{AudioResult, GroupId},
+};
+use crate::modules::core::{broadcasting::EventReceiver, persistence::JsonStore, ServerEvent};
+
+/// Perfis de som persistidos e sua aplicação
+///
+/// Aplicações do perfil que não estão tocando ficam pendentes e são
+/// ajustadas quando uma sessão delas aparece no dispositivo salvo.
+pub struct ProfileService {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    profiles: JsonStore<BTreeMap<String, SoundProfile>>,
+    /// Aplicações do último perfil aplicado que ainda não apareceram
+    pending: Mutex<Vec<ApplicationSetting>>,
+}
+
+impl ProfileService {
+    pub fn new(
+        audio_system: Arc<dyn AsyncAudioSystem>,
+        profiles: JsonStore<BTreeMap<String, SoundProfile>>,
+    ) -> Self {
+        Self {
+            audio_system,
+            profiles,
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn list(&self) -> Vec<SoundProfile> {
+        self.profiles
+            .read(|profiles| profiles.values().cloned().collect())
+    }
+
+    pub fn get(&self, name: &str) -> AudioResult<SoundProfile> {
+        self.profiles
+            .read(|profiles| profiles.get(name.trim()).cloned())
+            .ok_or_else(|| AudioError::ProfileNotFound {
+                name: name.to_string(),
+            })
+    }
+
+    /// Captura o estado atual do áudio em um perfil, substituindo outro
+    /// perfil com o mesmo nome
+    pub async fn capture(&self, name: &str) -> AudioResult<SoundProfile> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AudioError::InvalidRequestBody {
+                message: "Profile name must not be empty".to_string(),
+            });
+        }
+
+        let profile = self.snapshot(name).await?;
+        self.profiles.update(|profiles| {
+            profiles.insert(profile.name.clone(), profile.clone());
+        })?;
+        Ok(profile)
+    }
+
+    pub fn delete(&self, name: &str) -> AudioResult<()> {
+        let removed = self
+            .profiles
+            .update(|profiles| profiles.remove(name.trim()).is_some())?;
+        if !removed {
+            return Err(AudioError::ProfileNotFound {
+                name: name.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Importa perfis exportados, substituindo os que têm o mesmo nome
+    ///
+    /// Nada é gravado se algum perfil for inválido.
+    pub fn import(&self, profiles: Vec<SoundProfile>) -> AudioResult<usize> {
+        for profile in &profiles {
+            profile.validate()?;
+        }
+
+        let count = profiles.len();
+        self.profiles.update(|stored| {
+            for mut profile in profiles {
+                profile.name = profile.name.trim().to_string();
+                for application in &mut profile.applications {
+                    application.process_name = normalize_process_name(&application.process_name);
+                }
+                stored.insert(profile.name.clone(), profile);
+            }
+        })?;
+        Ok(count)
+    }
+
+    /// Aplica um perfil: dispositivo padrão, volumes master e aplicações
+    ///
+    /// Falhas individuais não interrompem o restante; elas são listadas
+    /// no relatório.
+    pub async fn apply(&self, name: &str) -> AudioResult<ProfileApplyReport> {
+        let profile = self.get(name)?;
+        let mut failures = Vec::new();
+
+        if let Some(device_id) = &profile.default_device_id {
+            if let Err(error) = self.audio_system.set_default_device(device_id).await {
+                failures.push(format!("Default device {device_id}: {error}"));
+            }
+        }
+
+        for device in &profile.devices {
+            if let Err(error) = self
+                .audio_system
+                .set_device_volume(&device.device_id, device.volume)
+                .await
+            {
+                failures.push(format!("Device {}: {}", device.device_id, error));
+            }
+        }
+
+        let running = list_all_sessions(self.audio_system.as_ref(), false).await?;
+        let mut applied_applications = 0;
+        let mut pending = Vec::new();
+
+        for setting in &profile.applications {
+            let groups: Vec<GroupId> = running
+                .iter()
+                .filter(|device| device.device_id == setting.device_id)
+                .flat_map(|device| &device.sessions)
+                .filter(|group| {
+                    normalize_process_name(&group.app.process_name) == setting.process_name
+                })
+                .map(|group| group.id.clone())
+                .collect();
+
+            if groups.is_empty() {
+                pending.push(setting.clone());
+                continue;
+            }
+
+            for group_id in &groups {
+                match self
+                    .apply_setting(group_id, &setting.device_id, setting)
+                    .await
+                {
+                    Ok(()) => applied_applications += 1,
+                    Err(error) => {
+                        failures.push(format!("Application {}: {}", setting.process_name, error))
+                    }
+                }
+            }
+        }
+
+        let pending_applications = pending
+            .iter()
+            .map(|setting| setting.process_name.clone())
+            .collect();
+        *self.pending.lock().unwrap_or_else(|e| e.into_inner()) = pending;
+
+        Ok(ProfileApplyReport {
+            profile: profile.name,
+            applied_applications,
+            pending_applications,
+            failures,
+        })
+    }
+
+    /// Ajusta uma aplicação pendente quando uma sessão dela aparece
+    ///
+    /// Retorna se alguma configuração pendente foi aplicada.
+    pub async fn apply_pending(&self, event: &ServerEvent) -> AudioResult<bool> {
+        let ServerEvent::SessionStarted {
+            process_name,
+            device_id,
+            group_id,
+            ..
+        } = event
+        else {
+            return Ok(false);
+        };
+
+        let process_name = normalize_process_name(process_name);
+        let setting = {
+            let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(index) = pending.iter().position(|setting| {
+                setting.process_name == process_name && &setting.device_id == device_id
+            }) else {
+                return Ok(false);
+            };
+            pending.remove(index)
+        };
+
+        self.apply_setting(&GroupId::new(group_id.clone()), device_id, &setting)
+            .await?;
+        Ok(true)
+    }
+
+    /// Aplica as configurações pendentes a cada `SessionStarted` recebido
+    pub async fn run(self: Arc<Self>, events: EventReceiver) {
+        handle_events(
+            self,
+            events,
+            "Profile service",
+            is_session_started,
+            |service, event| async move { service.apply_pending(&event).await },
+        )
+        .await
+    }
+
+    async fn apply_setting(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        setting: &ApplicationSetting,
+    ) -> AudioResult<()> {
+        self.audio_system
+            .set_group_volume(
+                group_id,
+                device_id,
+                setting.volume,
+                GroupVolumeMode::Uniform,
+            )
+            .await?;
+        self.audio_system
+            .set_group_mute(group_id, device_id, setting.muted)
+            .await
+    }
+
+    async fn snapshot(&self, name: &str) -> AudioResult<SoundProfile> {
+        let devices = self.audio_system.list_output_devices(false).await?;
+        let default_device_id = self.audio_system.default_device_id().await?;
+
+        let mut device_volumes = Vec::with_capacity(devices.len());
+        for device in &devices {
+            device_volumes.push(DeviceVolumeSetting {
+                device_id: device.id.clone(),
+                volume: self.audio_system.get_device_volume(&device.id).await?,
+            });
+        }
+
+        let applications = list_all_sessions(self.audio_system.as_ref(), false)
+            .await?
+            .into_iter()
+            .flat_map(|device| {
+                let device_id = device.device_id;
+                device
+                    .sessions
+                    .into_iter()
+                    .map(move |group| ApplicationSetting {
+                        process_name: normalize_process_name(&group.app.process_name),
+                        device_id: device_id.clone(),
+                        volume: group.volume_level,
+                        muted: group.mute_state == MuteState::All,
+                    })
+            })
+            .collect();
+
+        Ok(SoundProfile {
+            name: name.to_string(),
+            default_device_id,
+            devices: device_volumes,
+            applications,
+        })
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, NaiveTime, TimeDelta, Timelike};
+
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{
+        audio_requests::RuleDryRunRequest, normalize_process_name, ApplicationView, AutomationRule,
+        RuleCondition, RuleEvaluation, RuleTrigger, ThresholdDirection,
+    },
+    platform::async_audio_system::AsyncAudioSystem,
+    services::{application_service::list_applications, ActionExecutor},
+    types::AudioResult,
+};
+use crate::modules::core::{
+    broadcasting::{next_event, EventReceiver},
+    persistence::JsonStore,
+    Clock, ServerEvent,
+};
+
+/// Intervalo de verificação dos gatilhos de horário
+const TIME_TRIGGER_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Default)]
+struct RulesState {
+    /// Último volume master visto por dispositivo, para detectar cruzamentos
+    volumes: HashMap<String, f32>,
+    /// Horário da última verificação dos gatilhos de horário
+    last_tick: Option<NaiveDateTime>,
+}
+
+/// Gatilho ocorrido, com a aplicação que o causou quando houver
+struct Fired {
+    process_name: Option<String>,
+}
+
+/// Motor de regras de automação
+///
+/// Avalia as regras persistidas a cada `ServerEvent` recebido e a cada
+/// mudança de minuto, executando as ações das regras cujas condições valem.
+pub struct RulesService {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    executor: Arc<ActionExecutor>,
+    rules: JsonStore<BTreeMap<String, AutomationRule>>,
+    clock: Arc<dyn Clock>,
+    state: Mutex<RulesState>,
+}
+
+impl RulesService {
+    pub fn new(
+        audio_system: Arc<dyn AsyncAudioSystem>,
+        executor: Arc<ActionExecutor>,
+        rules: JsonStore<BTreeMap<String, AutomationRule>>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            audio_system,
+            executor,
+            rules,
+            clock,
+            state: Mutex::new(RulesState::default()),
+        }
+    }
+
+    pub fn list(&self) -> Vec<AutomationRule> {
+        self.rules.read(|rules| rules.values().cloned().collect())
+    }
+
+    pub fn get(&self, name: &str) -> AudioResult<AutomationRule> {
+        self.rules
+            .read(|rules| rules.get(name.trim()).cloned())
+            .ok_or_else(|| AudioError::RuleNotFound {
+                name: name.to_string(),
+            })
+    }
+
+    /// Cria ou substitui uma regra
+    pub fn save(&self, mut rule: AutomationRule) -> AudioResult<AutomationRule> {
+        rule.validate()?;
+        rule.name = rule.name.trim().to_string();
+
+        self.rules.update(|rules| {
+            rules.insert(rule.name.clone(), rule.clone());
+        })?;
+        Ok(rule)
+    }
+
+    pub fn delete(&self, name: &str) -> AudioResult<()> {
+        let removed = self
+            .rules
+            .update(|rules| rules.remove(name.trim()).is_some())?;
+        if !removed {
+            return Err(AudioError::RuleNotFound {
+                name: name.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Mostra quais regras disparariam, sem executar ações nem alterar o
+    /// estado usado para detectar cruzamentos de volume
+    pub async fn dry_run(&self, request: RuleDryRunRequest) -> AudioResult<Vec<RuleEvaluation>> {
+        let now = self.clock.now();
+        let now = match request.time {
+            Some(time) => now.date().and_time(time),
+            None => now,
+        };
+
+        let fired = match &request.event {
+            Some(event) => {
+                let default_device_id = self.event_default_device(event).await;
+                let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+                self.fired_by_event(event, &state, default_device_id.as_deref())
+            }
+            None => self.fired_at(now.time()),
+        };
+        self.evaluate(fired, now, false).await
+    }
+
+    /// Avalia as regras disparadas por um evento e executa suas ações
+    pub async fn handle_event(&self, event: &ServerEvent) -> AudioResult<Vec<RuleEvaluation>> {
+        let default_device_id = self.event_default_device(event).await;
+        let fired = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let fired = self.fired_by_event(event, &state, default_device_id.as_deref());
+            if let ServerEvent::VolumeChanged { device_id, volume } = event {
+                state.volumes.insert(device_id.clone(), *volume);
+            }
+            fired
+        };
+        self.evaluate(fired, self.clock.now(), true).await
+    }
+
+    /// Dispara os gatilhos de horário vencidos desde a última verificação
+    ///
+    /// A primeira chamada apenas registra o horário de referência.
+    pub async fn tick(&self) -> AudioResult<Vec<RuleEvaluation>> {
+        let now = self.clock.now();
+        let last_tick = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.last_tick.replace(now)
+        };
+        let Some(last_tick) = last_tick else {
+            return Ok(Vec::new());
+        };
+
+        let fired = self
+            .rules
+            .read(|rules| {
+                rules
+                    .values()
+                    .filter(|rule| rule.enabled)
+                    .filter(|rule| match &rule.trigger {
+                        RuleTrigger::TimeOfDay { at } => {
+                            let mut due = now.date().and_time(minute_of(*at));
+                            if due > now {
+                                due -= TimeDelta::days(1);
+                            }
+                            last_tick < due && due <= now
+                        }
+                        _ => false,
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .into_iter()
+            .map(|rule| (rule, Fired { process_name: None }))
+            .collect();
+        self.evaluate(fired, now, true).await
+    }
+
+    /// Avalia cada evento recebido e verifica os gatilhos de horário
+    pub async fn run(self: Arc<Self>, mut events: EventReceiver) {
+        let mut ticker = tokio::time::interval(TIME_TRIGGER_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            let event = tokio::select! {
+                received = next_event(&mut events, "Rules engine") => match received {
+                    Some(event) => Some(event),
+                    None => break,
+                },
+                _ = ticker.tick() => None,
+            };
+
+            let result = match event {
+                Some(event) => self.handle_event(&event).await,
+                None => self.tick().await,
+            };
+
+            match result {
+                Ok(evaluations) => {
+                    for evaluation in evaluations.iter().filter(|e| !e.failures.is_empty()) {
+                        tracing::warn!(
+                            "Rule '{}' failed: {}",
+                            evaluation.rule,
+                            evaluation.failures.join("; ")
+                        );
+                    }
+                }
+                Err(error) => tracing::warn!("Failed to evaluate rules: {}", error),
+            }
+        }
+    }
+
+    /// Dispositivo padrão, lido só para eventos cujos gatilhos dependem dele
+    async fn event_default_device(&self, event: &ServerEvent) -> Option<String> {
+        match event {
+            ServerEvent::VolumeChanged { .. } => self.default_device_id().await,
+            _ => None,
+        }
+    }
+
+    fn fired_by_event(
+        &self,
+        event: &ServerEvent,
+        state: &RulesState,
+        default_device_id: Option<&str>,
+    ) -> Vec<(AutomationRule, Fired)> {
+        self.rules.read(|rules| {
+            rules
+                .values()
+                .filter(|rule| rule.enabled)
+                .filter_map(|rule| {
+                    trigger_matches(&rule.trigger, event, state, default_device_id)
+                        .map(|fired| (rule.clone(), fired))
+                })
+                .collect()
+        })
+    }
+
+    fn fired_at(&self, time: NaiveTime) -> Vec<(AutomationRule, Fired)> {
+        self.rules.read(|rules| {
+            rules
+                .values()
+                .filter(|rule| rule.enabled)
+                .filter(|rule| {
+                    matches!(&rule.trigger, RuleTrigger::TimeOfDay { at } if minute_of(*at) == minute_of(time))
+                })
+                .map(|rule| (rule.clone(), Fired { process_name: None }))
+                .collect()
+        })
+    }
+
+    async fn evaluate(
+        &self,
+        fired: Vec<(AutomationRule, Fired)>,
+        now: NaiveDateTime,
+        execute: bool,
+    ) -> AudioResult<Vec<RuleEvaluation>> {
+        let mut snapshot = StateSnapshot::default();
+        let mut evaluations = Vec::with_capacity(fired.len());
+
+        for (rule, fired) in fired {
+            let mut conditions_met = true;
+            for condition in &rule.conditions {
+                if !self.condition_holds(condition, now, &mut snapshot).await? {
+                    conditions_met = false;
+                    break;
+                }
+            }
+
+            let mut evaluation = RuleEvaluation {
+                rule: rule.name.clone(),
+                conditions_met,
+                actions: Vec::new(),
+                failures: Vec::new(),
+            };
+            if conditions_met {
+                evaluation.actions = rule.actions.clone();
+                if execute {
+                    for action in &rule.actions {
+                        let result = self
+                            .executor
+                            .execute(action, &rule.name, fired.process_name.as_deref())
+                            .await;
+                        if let Err(error) = result {
+                            evaluation.failures.push(error.to_string());
+                        }
+                    }
+                    // As ações podem ter mudado o estado lido pelas condições
+                    snapshot = StateSnapshot::default();
+                }
+            }
+            evaluations.push(evaluation);
+        }
+        Ok(evaluations)
+    }
+
+    async fn condition_holds(
+        &self,
+        condition: &RuleCondition,
+        now: NaiveDateTime,
+        snapshot: &mut StateSnapshot,
+    ) -> AudioResult<bool> {
+        Ok(match condition {
+            RuleCondition::ApplicationRunning { process_name } => {
+                snapshot
+                    .is_running(self.audio_system.as_ref(), process_name)
+                    .await?
+            }
+            RuleCondition::ApplicationNotRunning { process_name } => {
+                !snapshot
+                    .is_running(self.audio_system.as_ref(), process_name)
+                    .await?
+            }
+            RuleCondition::DefaultDevice { device_id } => {
+                self.default_device_id().await.as_deref() == Some(device_id.as_str())
+            }
+            RuleCondition::TimeBetween { from, to } => {
+                let time = now.time();
+                if from <= to {
+                    *from <= time && time < *to
+                } else {
+                    time >= *from || time < *to
+                }
+            }
+            RuleCondition::MasterVolume { min, max } => {
+                let volume = self.audio_system.get_actual_volume().await?;
+                min.is_none_or(|min| volume >= min) && max.is_none_or(|max| volume <= max)
+            }
+        })
+    }
+
+    async fn default_device_id(&self) -> Option<String> {
+        self.audio_system
+            .default_device_id()
+            .await
+            .unwrap_or_else(|error| {
+                tracing::warn!("Failed to read default device: {}", error);
+                None
+            })
+    }
+}
+
+/// Aplicações em execução, lidas uma única vez por avaliação
+#[derive(Default)]
+struct StateSnapshot {
+    applications: Option<Vec<ApplicationView>>,
+}
+
+impl StateSnapshot {
+    async fn is_running(
+        &mut self,
+        audio_system: &dyn AsyncAudioSystem,
+        process_name: &str,
+    ) -> AudioResult<bool> {
+        if self.applications.is_none() {
+            self.applications = Some(list_applications(audio_system).await?);
+        }
+        let process_name = normalize_process_name(process_name);
+        Ok(self
+            .applications
+            .iter()
+            .flatten()
+            .any(|application| application.process_name == process_name))
+    }
+}
+
+fn trigger_matches(
+    trigger: &RuleTrigger,
+    event: &ServerEvent,
+    state: &RulesState,
+    default_device_id: Option<&str>,
+) -> Option<Fired> {
+    let same_app = |expected: &Option<String>, actual: &str| {
+        expected.as_ref().is_none_or(|expected| {
+            normalize_process_name(expected) == normalize_process_name(actual)
+        })
+    };
+
+    match (trigger, event) {
+        (
+            RuleTrigger::SessionStarted {
+                process_name: expected,
+            },
+            ServerEvent::SessionStarted { process_name, .. },
+        )
+        | (
+            RuleTrigger::SessionEnded {
+                process_name: expected,
+            },
+            ServerEvent::SessionEnded { process_name, .. },
+        ) if same_app(expected, process_name) => Some(Fired {
+            process_name: Some(normalize_process_name(process_name)),
+        }),
+        (
+            RuleTrigger::DeviceConnected {
+                device_id: expected,
+            },
+            ServerEvent::DeviceConnected { device_id, .. },
+        ) if expected
+            .as_ref()
+            .is_none_or(|expected| expected == device_id) =>
+        {
+            Some(Fired { process_name: None })
+        }
+        (
+            RuleTrigger::VolumeThreshold {
+                device_id: expected,
+                threshold,
+                direction,
+            },
+            ServerEvent::VolumeChanged { device_id, volume },
+        ) => {
+            let watched = expected.as_deref().or(default_device_id);
+            if watched != Some(device_id.as_str()) {
+                return None;
+            }
+            let previous = *state.volumes.get(device_id)?;
+            let crossed = match direction {
+                ThresholdDirection::Above => previous < *threshold && *volume >= *threshold,
+                ThresholdDirection::Below => previous > *threshold && *volume <= *threshold,
+            };
+            crossed.then_some(Fired { process_name: None })
+        }
+        _ => None,
+    }
+}
+
+/// Descarta segundos, já que os gatilhos de horário têm precisão de minutos
+fn minute_of(time: NaiveTime) -> NaiveTime {
+    time.with_second(0)
+        .and_then(|time| time.with_nanosecond(0))
+        .unwrap_or(time)
+}
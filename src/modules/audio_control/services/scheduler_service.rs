@@ -0,0 +1,203 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, TimeDelta};
+
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{audio_requests::ScheduleTaskRequest, ScheduledTask, ScheduledTasks, TaskSchedule},
+    services::ActionExecutor,
+    types::AudioResult,
+};
+use crate::modules::core::{
+    persistence::JsonStore,
+    utils::{CronParseError, CronSchedule},
+    Broadcaster, Clock, ServerEvent,
+};
+
+/// Intervalo de verificação das tarefas vencidas
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Agendador de ações
+///
+/// Tarefas vencidas rodam na verificação seguinte, inclusive as que
+/// venceram com o servidor desligado; tarefas cron seguem para a próxima
+/// ocorrência depois do horário atual, sem repetir as perdidas.
+pub struct SchedulerService {
+    executor: Arc<ActionExecutor>,
+    broadcaster: Broadcaster,
+    tasks: JsonStore<ScheduledTasks>,
+    clock: Arc<dyn Clock>,
+}
+
+impl SchedulerService {
+    pub fn new(
+        executor: Arc<ActionExecutor>,
+        broadcaster: Broadcaster,
+        tasks: JsonStore<ScheduledTasks>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            executor,
+            broadcaster,
+            tasks,
+            clock,
+        }
+    }
+
+    /// Tarefas pendentes, na ordem de criação
+    pub fn list(&self) -> Vec<ScheduledTask> {
+        self.tasks
+            .read(|data| data.tasks.values().cloned().collect())
+    }
+
+    pub fn create(&self, request: ScheduleTaskRequest) -> AudioResult<ScheduledTask> {
+        let invalid = |message: String| AudioError::InvalidRequestBody { message };
+
+        let name = request.name.trim().to_string();
+        if name.is_empty() {
+            return Err(invalid("Task name must not be empty".to_string()));
+        }
+        if request.actions.is_empty() {
+            return Err(invalid(format!(
+                "Task '{name}': At least one action is required"
+            )));
+        }
+        for action in &request.actions {
+            action
+                .validate(false)
+                .map_err(|message| invalid(format!("Task '{name}': {message}")))?;
+        }
+
+        let now = self.clock.now();
+        let (schedule, next_run) = match request.schedule {
+            TaskSchedule::Once { at } => {
+                if at <= now {
+                    return Err(invalid(format!(
+                        "Task '{name}': Scheduled time must be in the future"
+                    )));
+                }
+                (TaskSchedule::Once { at }, at)
+            }
+            TaskSchedule::After { seconds } => {
+                let at = i64::try_from(seconds)
+                    .ok()
+                    .and_then(TimeDelta::try_seconds)
+                    .and_then(|delay| now.checked_add_signed(delay))
+                    .ok_or_else(|| invalid(format!("Task '{name}': Delay is too long")))?;
+                (TaskSchedule::Once { at }, at)
+            }
+            TaskSchedule::Cron { expression } => {
+                let next_run = next_occurrence(&expression, now)
+                    .map_err(|message| invalid(format!("Task '{name}': {message}")))?;
+                (TaskSchedule::Cron { expression }, next_run)
+            }
+        };
+
+        let task = self.tasks.update(|data| {
+            let task = ScheduledTask {
+                id: data.next_id,
+                name,
+                schedule,
+                actions: request.actions,
+                next_run,
+            };
+            data.next_id += 1;
+            data.tasks.insert(task.id, task.clone());
+            task
+        })?;
+        Ok(task)
+    }
+
+    pub fn cancel(&self, id: u64) -> AudioResult<()> {
+        let removed = self.tasks.update(|data| data.tasks.remove(&id).is_some())?;
+        if !removed {
+            return Err(AudioError::ScheduledTaskNotFound { id });
+        }
+        Ok(())
+    }
+
+    /// Executa as tarefas vencidas e devolve os ids das que rodaram
+    ///
+    /// Cada execução publica `ScheduledTaskExecuted` ou, se alguma ação
+    /// falhar, `ScheduledTaskFailed`; as demais ações rodam mesmo assim.
+    pub async fn tick(&self) -> AudioResult<Vec<u64>> {
+        let now = self.clock.now();
+        let due: Vec<ScheduledTask> = self.tasks.read(|data| {
+            data.tasks
+                .values()
+                .filter(|task| task.next_run <= now)
+                .cloned()
+                .collect()
+        });
+
+        for task in &due {
+            let mut failures = Vec::new();
+            for action in &task.actions {
+                if let Err(error) = self.executor.execute(action, &task.name, None).await {
+                    failures.push(error.to_string());
+                }
+            }
+
+            let event = if failures.is_empty() {
+                ServerEvent::ScheduledTaskExecuted {
+                    task_id: task.id,
+                    name: task.name.clone(),
+                }
+            } else {
+                tracing::warn!(
+                    "Scheduled task '{}' failed: {}",
+                    task.name,
+                    failures.join("; ")
+                );
+                ServerEvent::ScheduledTaskFailed {
+                    task_id: task.id,
+                    name: task.name.clone(),
+                    error: failures.join("; "),
+                }
+            };
+            let _ = self.broadcaster.broadcast(event);
+        }
+
+        if !due.is_empty() {
+            self.tasks.update(|data| {
+                for task in &due {
+                    let next_run = match &task.schedule {
+                        TaskSchedule::Cron { expression } => next_occurrence(expression, now).ok(),
+                        _ => None,
+                    };
+                    match (next_run, data.tasks.get_mut(&task.id)) {
+                        (Some(next_run), Some(stored)) => stored.next_run = next_run,
+                        _ => {
+                            data.tasks.remove(&task.id);
+                        }
+                    }
+                }
+            })?;
+        }
+        Ok(due.into_iter().map(|task| task.id).collect())
+    }
+
+    /// Verifica as tarefas vencidas periodicamente até o runtime ser encerrado
+    pub async fn run(self: Arc<Self>) {
+        let mut ticker = tokio::time::interval(SCHEDULER_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(error) = self.tick().await {
+                tracing::warn!("Failed to run scheduled tasks: {}", error);
+            }
+        }
+    }
+}
+
+fn next_occurrence(expression: &str, after: NaiveDateTime) -> Result<NaiveDateTime, String> {
+    let schedule: CronSchedule = expression
+        .parse()
+        .map_err(|error: CronParseError| error.to_string())?;
+    schedule
+        .next_after(after)
+        .ok_or_else(|| format!("Cron expression '{expression}' never matches"))
+}
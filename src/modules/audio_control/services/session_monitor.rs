@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::modules::audio_control::{
+    models::SessionState, platform::async_audio_system::AsyncAudioSystem, types::AudioResult,
+};
+use crate::modules::core::{Broadcaster, ServerEvent};
+
+/// Sessão observada na última leitura do monitor
+#[derive(Debug, Clone)]
+struct ObservedSession {
+    device_id: String,
+    process_name: String,
+    pid: u32,
+    volume: f32,
+    muted: bool,
+    active: bool,
+}
+
+#[derive(Debug, Default)]
+struct MonitorSnapshot {
+    initialized: bool,
+    devices: HashMap<String, String>,
+    sessions: HashMap<String, ObservedSession>,
+    default_device_id: Option<String>,
+    master_volume: Option<(String, f32)>,
+}
+
+/// Monitora dispositivos e sessões por amostragem e publica as mudanças
+///
+/// A cada leitura o estado atual é comparado com o anterior e as diferenças
+/// viram `ServerEvent`s no broadcaster. A primeira leitura apenas registra o
+/// estado inicial, sem gerar eventos.
+pub struct SessionMonitor {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    broadcaster: Broadcaster,
+    snapshot: Mutex<MonitorSnapshot>,
+}
+
+impl SessionMonitor {
+    pub fn new(audio_system: Arc<dyn AsyncAudioSystem>, broadcaster: Broadcaster) -> Self {
+        Self {
+            audio_system,
+            broadcaster,
+            snapshot: Mutex::new(MonitorSnapshot::default()),
+        }
+    }
+
+    /// Executa a amostragem periódica até o runtime ser encerrado
+    pub async fn run(self: Arc<Self>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(error) = self.poll().await {
+                tracing::warn!("Session monitor poll failed: {}", error);
+            }
+        }
+    }
+
+    /// Faz uma leitura, publica os eventos gerados e os retorna
+    pub async fn poll(&self) -> AudioResult<Vec<ServerEvent>> {
+        let events = self.collect_changes().await?;
+        for event in &events {
+            // Sem clientes inscritos o envio falha, o que não é um erro aqui
+            let _ = self.broadcaster.broadcast(event.clone());
+        }
+        Ok(events)
+    }
+
+    async fn collect_changes(&self) -> AudioResult<Vec<ServerEvent>> {
+        let devices = self.audio_system.list_output_devices(false).await?;
+
+        let mut current_devices = HashMap::new();
+        let mut current_sessions = HashMap::new();
+        let mut started = Vec::new();
+        let mut default_device_id = None;
+
+        for device in &devices {
+            current_devices.insert(device.id.clone(), device.name.clone());
+            if device.is_default {
+                default_device_id = Some(device.id.clone());
+            }
+
+            let groups = match self
+                .audio_system
+                .get_sessions_for_device(&device.id, true)
+                .await
+            {
+                Ok(groups) => groups,
+                Err(error) => {
+                    tracing::warn!("Failed to list sessions for {}: {}", device.id, error);
+                    continue;
+                }
+            };
+
+            for group in groups {
+                for member in group.sessions.unwrap_or_default() {
+                    let active = member.state == SessionState::Active;
+                    started.push(ServerEvent::SessionStarted {
+                        session_id: member.session_id.clone(),
+                        display_name: group.app.display_name.clone(),
+                        process_name: member.process_name.clone(),
+                        device_id: device.id.clone(),
+                        group_id: group.id.to_string(),
+                        pid: member.pid,
+                        active,
+                    });
+                    current_sessions.insert(
+                        member.session_id,
+                        ObservedSession {
+                            device_id: device.id.clone(),
+                            process_name: member.process_name.clone(),
+                            pid: member.pid,
+                            volume: member.volume_level,
+                            muted: member.muted,
+                            active,
+                        },
+                    );
+                }
+            }
+        }
+
+        let master_volume = match &default_device_id {
+            Some(device_id) => Some((
+                device_id.clone(),
+                self.audio_system.get_actual_volume().await?,
+            )),
+            None => None,
+        };
+
+        let mut snapshot = self.snapshot.lock().unwrap_or_else(|e| e.into_inner());
+        let mut events = Vec::new();
+
+        if snapshot.initialized {
+            for (device_id, device_name) in &current_devices {
+                if !snapshot.devices.contains_key(device_id) {
+                    events.push(ServerEvent::DeviceConnected {
+                        device_id: device_id.clone(),
+                        device_name: device_name.clone(),
+                    });
+                }
+            }
+            for device_id in snapshot.devices.keys() {
+                if !current_devices.contains_key(device_id) {
+                    events.push(ServerEvent::DeviceDisconnected {
+                        device_id: device_id.clone(),
+                    });
+                }
+            }
+
+            if let Some(device_id) = &default_device_id {
+                if snapshot.default_device_id.as_ref() != Some(device_id) {
+                    events.push(ServerEvent::DefaultDeviceChanged {
+                        device_id: device_id.clone(),
+                    });
+                }
+            }
+
+            for event in started {
+                if let ServerEvent::SessionStarted { session_id, .. } = &event {
+                    if !snapshot.sessions.contains_key(session_id) {
+                        events.push(event);
+                    }
+                }
+            }
+            for (session_id, session) in &snapshot.sessions {
+                let Some(current) = current_sessions.get(session_id) else {
+                    events.push(ServerEvent::SessionEnded {
+                        session_id: session_id.clone(),
+                        process_name: session.process_name.clone(),
+                        device_id: session.device_id.clone(),
+                        pid: session.pid,
+                    });
+                    continue;
+                };
+                if (current.volume - session.volume).abs() > f32::EPSILON
+                    || current.muted != session.muted
+                {
+                    events.push(ServerEvent::SessionVolumeChanged {
+                        session_id: session_id.clone(),
+                        process_name: current.process_name.clone(),
+                        device_id: current.device_id.clone(),
+                        volume: current.volume,
+                        muted: current.muted,
+                    });
+                }
+                if current.active != session.active {
+                    events.push(ServerEvent::SessionActivityChanged {
+                        session_id: session_id.clone(),
+                        process_name: current.process_name.clone(),
+                        device_id: current.device_id.clone(),
+                        active: current.active,
+                    });
+                }
+            }
+
+            if let Some((device_id, volume)) = &master_volume {
+                let changed = match &snapshot.master_volume {
+                    Some((previous_id, previous)) => {
+                        previous_id != device_id || (previous - volume).abs() > f32::EPSILON
+                    }
+                    None => true,
+                };
+                if changed {
+                    events.push(ServerEvent::VolumeChanged {
+                        device_id: device_id.clone(),
+                        volume: *volume,
+                    });
+                }
+            }
+        }
+
+        *snapshot = MonitorSnapshot {
+            initialized: true,
+            devices: current_devices,
+            sessions: current_sessions,
+            default_device_id,
+            master_volume,
+        };
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, TimeDelta};
+use tokio::sync::Mutex as AsyncMutex;
+
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{
+        audio_requests::StartSleepTimerRequest, normalize_process_name, GroupVolumeMode,
+        SessionState, SleepTarget, SleepTimerStatus,
+    },
+    platform::async_audio_system::AsyncAudioSystem,
+    services::{
+        application_service::{list_all_sessions, list_applications},
+        ActionExecutor,
+    },
+    types::{AudioResult, GroupId},
+};
+use crate::modules::core::{Broadcaster, Clock, ServerEvent};
+
+/// Intervalo entre os passos do timer
+const SLEEP_TIMER_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Duração padrão do fade no fim do timer
+const DEFAULT_FADE_SECS: u64 = 300;
+
+/// Volume de um alvo antes do fade
+#[derive(Debug, Clone)]
+enum Level {
+    Device {
+        device_id: String,
+        volume: f32,
+    },
+    Group {
+        device_id: String,
+        group_id: GroupId,
+        volume: f32,
+    },
+}
+
+#[derive(Debug)]
+struct SleepTimer {
+    /// Alvo com o dispositivo já resolvido e nomes normalizados
+    target: SleepTarget,
+    started_at: NaiveDateTime,
+    ends_at: NaiveDateTime,
+    fade_secs: u64,
+    pause_media: bool,
+    /// Volumes lidos quando o fade começou
+    original: Option<Vec<Level>>,
+    /// Último minuto restante publicado
+    reported_minute: u64,
+}
+
+impl SleepTimer {
+    fn remaining(&self, now: NaiveDateTime) -> TimeDelta {
+        (self.ends_at - now).max(TimeDelta::zero())
+    }
+
+    fn status(&self, now: NaiveDateTime) -> SleepTimerStatus {
+        SleepTimerStatus {
+            target: self.target.clone(),
+            started_at: self.started_at,
+            ends_at: self.ends_at,
+            fade_secs: self.fade_secs,
+            pause_media: self.pause_media,
+            remaining_secs: whole_seconds(self.remaining(now)),
+            fading: self.original.is_some(),
+        }
+    }
+}
+
+/// Timer de sono
+///
+/// Perto do fim o volume dos alvos cai linearmente até quase zero; ao
+/// terminar, os alvos são silenciados e recebem de volta os volumes de
+/// antes do fade, para que baste desmutar no dia seguinte. Cancelar
+/// restaura os volumes. Só existe um timer por vez e ele não sobrevive a
+/// reinícios do servidor.
+pub struct SleepTimerService {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    executor: Arc<ActionExecutor>,
+    broadcaster: Broadcaster,
+    clock: Arc<dyn Clock>,
+    /// Travado só enquanto o estado é lido ou alterado, nunca durante as
+    /// chamadas ao backend, para que `status` não espere por elas
+    timer: Mutex<Option<SleepTimer>>,
+    /// Serializa início, cancelamento e passos do timer, que chamam o backend
+    steps: AsyncMutex<()>,
+}
+
+impl SleepTimerService {
+    pub fn new(
+        audio_system: Arc<dyn AsyncAudioSystem>,
+        executor: Arc<ActionExecutor>,
+        broadcaster: Broadcaster,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            audio_system,
+            executor,
+            broadcaster,
+            clock,
+            timer: Mutex::new(None),
+            steps: AsyncMutex::new(()),
+        }
+    }
+
+    pub fn status(&self) -> Option<SleepTimerStatus> {
+        let now = self.clock.now();
+        self.lock_timer().as_ref().map(|timer| timer.status(now))
+    }
+
+    /// Inicia o timer, substituindo (e restaurando) o que estiver rodando
+    pub async fn start(&self, request: StartSleepTimerRequest) -> AudioResult<SleepTimerStatus> {
+        let invalid = |message: &str| AudioError::InvalidRequestBody {
+            message: message.to_string(),
+        };
+
+        if request.duration_secs == 0 {
+            return Err(invalid("duration_secs must be greater than zero"));
+        }
+        let duration = i64::try_from(request.duration_secs)
+            .ok()
+            .and_then(TimeDelta::try_seconds)
+            .ok_or_else(|| invalid("duration_secs is too long"))?;
+
+        let target = match request.target {
+            SleepTarget::Master { device_id } => {
+                let device_id = match device_id {
+                    Some(device_id) => {
+                        self.audio_system.get_device_volume(&device_id).await?;
+                        device_id
+                    }
+                    None => self
+                        .audio_system
+                        .default_device_id()
+                        .await?
+                        .ok_or_else(|| AudioError::DeviceNotFound {
+                            device_id: "default".to_string(),
+                        })?,
+                };
+                SleepTarget::Master {
+                    device_id: Some(device_id),
+                }
+            }
+            SleepTarget::Applications { process_names } => {
+                let mut process_names: Vec<String> = process_names
+                    .iter()
+                    .map(|name| normalize_process_name(name))
+                    .filter(|name| !name.is_empty())
+                    .collect();
+                process_names.sort();
+                process_names.dedup();
+                if process_names.is_empty() {
+                    return Err(invalid("At least one process_name is required"));
+                }
+                SleepTarget::Applications { process_names }
+            }
+        };
+
+        let now = self.clock.now();
+        let timer = SleepTimer {
+            target,
+            started_at: now,
+            ends_at: now + duration,
+            fade_secs: request
+                .fade_secs
+                .unwrap_or(DEFAULT_FADE_SECS)
+                .min(request.duration_secs),
+            pause_media: request.pause_media,
+            original: None,
+            reported_minute: whole_minutes(duration),
+        };
+        let status = timer.status(now);
+
+        let _step = self.steps.lock().await;
+        let previous = self.lock_timer().replace(timer);
+        if let Some(original) = previous.and_then(|previous| previous.original) {
+            self.restore(&original).await;
+        }
+
+        let _ = self.broadcaster.broadcast(ServerEvent::SleepTimerUpdated {
+            remaining_secs: status.remaining_secs,
+            fading: false,
+        });
+        Ok(status)
+    }
+
+    /// Cancela o timer e restaura os volumes, se o fade já tiver começado
+    pub async fn cancel(&self) -> AudioResult<SleepTimerStatus> {
+        let _step = self.steps.lock().await;
+        let now = self.clock.now();
+        let timer = self.lock_timer().take().ok_or(AudioError::NoSleepTimer)?;
+        if let Some(original) = &timer.original {
+            self.restore(original).await;
+        }
+
+        let _ = self
+            .broadcaster
+            .broadcast(ServerEvent::SleepTimerEnded { cancelled: true });
+        Ok(timer.status(now))
+    }
+
+    /// Aplica o volume do fade no horário atual e encerra o timer vencido
+    pub async fn tick(&self) -> AudioResult<()> {
+        let _step = self.steps.lock().await;
+        let now = self.clock.now();
+        let snapshot = {
+            let mut guard = self.lock_timer();
+            let Some(timer) = guard.as_mut() else {
+                return Ok(());
+            };
+
+            let remaining = timer.remaining(now);
+            if remaining <= TimeDelta::zero() {
+                Err(guard.take())
+            } else {
+                let fade = TimeDelta::seconds(timer.fade_secs as i64);
+                Ok((
+                    timer.target.clone(),
+                    timer.original.clone(),
+                    remaining,
+                    fade,
+                ))
+            }
+        };
+        let (target, original, remaining, fade) = match snapshot {
+            Ok(snapshot) => snapshot,
+            Err(expired) => {
+                if let Some(timer) = expired {
+                    self.finish(timer).await?;
+                }
+                return Ok(());
+            }
+        };
+
+        let mut fading = original.is_some();
+        if remaining <= fade {
+            let original = match original {
+                Some(original) => original,
+                None => self.begin_fade(&target, remaining).await?,
+            };
+            fading = true;
+
+            let factor = remaining.num_milliseconds() as f32 / fade.num_milliseconds() as f32;
+            for level in &original {
+                if let Err(error) = self.set_level(level, level.volume() * factor).await {
+                    tracing::warn!("Sleep timer failed to lower volume: {}", error);
+                }
+            }
+        }
+
+        if let Some(timer) = self.lock_timer().as_mut() {
+            let minute = whole_minutes(remaining);
+            if minute != timer.reported_minute {
+                timer.reported_minute = minute;
+                let _ = self.broadcaster.broadcast(ServerEvent::SleepTimerUpdated {
+                    remaining_secs: whole_seconds(remaining),
+                    fading,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Avança o timer periodicamente até o runtime ser encerrado
+    pub async fn run(self: Arc<Self>) {
+        let mut ticker = tokio::time::interval(SLEEP_TIMER_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            ticker.tick().await;
+            if self.lock_timer().is_none() {
+                continue;
+            }
+
+            if let Err(error) = self.tick().await {
+                tracing::warn!("Sleep timer step failed: {}", error);
+            }
+        }
+    }
+
+    /// Guarda os volumes de antes do fade e avisa que ele começou
+    async fn begin_fade(
+        &self,
+        target: &SleepTarget,
+        remaining: TimeDelta,
+    ) -> AudioResult<Vec<Level>> {
+        let original = self.capture(target).await?;
+        if let Some(timer) = self.lock_timer().as_mut() {
+            timer.original = Some(original.clone());
+        }
+
+        if let SleepTarget::Master {
+            device_id: Some(device_id),
+        } = target
+        {
+            // Um fade agendado no mesmo dispositivo brigaria com este
+            self.executor.cancel_fade(device_id);
+        }
+        let _ = self.broadcaster.broadcast(ServerEvent::SleepTimerUpdated {
+            remaining_secs: whole_seconds(remaining),
+            fading: true,
+        });
+        Ok(original)
+    }
+
+    /// Pausa a mídia (se pedido), silencia os alvos e devolve os volumes de
+    /// antes do fade
+    async fn finish(&self, timer: SleepTimer) -> AudioResult<()> {
+        let original = match timer.original {
+            Some(original) => original,
+            None => self.capture(&timer.target).await?,
+        };
+
+        // A tecla alterna a reprodução: sem nenhuma sessão tocando ela
+        // daria play, então só é enviada quando há o que pausar
+        if timer.pause_media {
+            let paused = match self.media_playing().await {
+                Ok(true) => self.audio_system.toggle_media_playback().await,
+                Ok(false) => Ok(()),
+                Err(error) => Err(error),
+            };
+            if let Err(error) = paused {
+                tracing::warn!("Sleep timer failed to pause media: {}", error);
+            }
+        }
+
+        for level in &original {
+            let muted = match level {
+                Level::Device { device_id, .. } => {
+                    self.audio_system.set_device_mute(device_id, true).await
+                }
+                Level::Group {
+                    device_id,
+                    group_id,
+                    ..
+                } => {
+                    self.audio_system
+                        .set_group_mute(group_id, device_id, true)
+                        .await
+                }
+            };
+            if let Err(error) = muted {
+                tracing::warn!("Sleep timer failed to mute: {}", error);
+            }
+        }
+        self.restore(&original).await;
+
+        let _ = self
+            .broadcaster
+            .broadcast(ServerEvent::SleepTimerEnded { cancelled: false });
+        Ok(())
+    }
+
+    /// Se alguma sessão, em qualquer dispositivo, está tocando áudio
+    async fn media_playing(&self) -> AudioResult<bool> {
+        let devices = list_all_sessions(self.audio_system.as_ref(), false).await?;
+        Ok(devices
+            .iter()
+            .flat_map(|device| &device.sessions)
+            .any(|group| group.state == SessionState::Active))
+    }
+
+    async fn capture(&self, target: &SleepTarget) -> AudioResult<Vec<Level>> {
+        match target {
+            SleepTarget::Master { device_id } => {
+                let device_id = device_id.clone().unwrap_or_default();
+                let volume = self.audio_system.get_device_volume(&device_id).await?;
+                Ok(vec![Level::Device { device_id, volume }])
+            }
+            SleepTarget::Applications { process_names } => {
+                let process_names: HashSet<&String> = process_names.iter().collect();
+                Ok(list_applications(self.audio_system.as_ref())
+                    .await?
+                    .into_iter()
+                    .filter(|application| process_names.contains(&application.process_name))
+                    .flat_map(|application| application.instances)
+                    .map(|instance| Level::Group {
+                        device_id: instance.device_id,
+                        group_id: instance.group_id,
+                        volume: instance.volume_level,
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    async fn restore(&self, original: &[Level]) {
+        for level in original {
+            if let Err(error) = self.set_level(level, level.volume()).await {
+                tracing::warn!("Sleep timer failed to restore volume: {}", error);
+            }
+        }
+    }
+
+    async fn set_level(&self, level: &Level, volume: f32) -> AudioResult<()> {
+        match level {
+            Level::Device { device_id, .. } => {
+                self.audio_system.set_device_volume(device_id, volume).await
+            }
+            // Proporcional para manter as diferenças entre as sessões do grupo
+            Level::Group {
+                device_id,
+                group_id,
+                ..
+            } => {
+                self.audio_system
+                    .set_group_volume(group_id, device_id, volume, GroupVolumeMode::Proportional)
+                    .await
+            }
+        }
+    }
+
+    fn lock_timer(&self) -> MutexGuard<'_, Option<SleepTimer>> {
+        self.timer.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Level {
+    fn volume(&self) -> f32 {
+        match self {
+            Level::Device { volume, .. } | Level::Group { volume, .. } => *volume,
+        }
+    }
+}
+
+/// Segundos restantes, arredondados para cima
+fn whole_seconds(remaining: TimeDelta) -> u64 {
+    (remaining.num_milliseconds().max(0) as u64).div_ceil(1000)
+}
+
+/// Minutos restantes, arredondados para cima
+fn whole_minutes(remaining: TimeDelta) -> u64 {
+    whole_seconds(remaining).div_ceil(60)
+}
@@ -0,0 +1,206 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use tokio::sync::{watch, Mutex as AsyncMutex};
+
+use crate::modules::audio_control::{
+    models::{AudioState, DeviceStateEntry, StateChanges, VersionedState},
+    platform::async_audio_system::AsyncAudioSystem,
+    types::AudioResult,
+};
+use crate::modules::core::utils::{json_patch, PatchOperation};
+
+/// Versões cujas mudanças ficam guardadas; clientes mais atrasados recebem
+/// o estado completo
+const TRACKED_VERSIONS: usize = 64;
+
+#[derive(Debug, Default)]
+struct StateHistory {
+    /// Zero antes da primeira leitura
+    version: u64,
+    current: Value,
+    /// Operações que levaram de `versão - 1` a cada versão, da mais antiga
+    /// à mais nova
+    patches: VecDeque<(u64, Vec<PatchOperation>)>,
+}
+
+/// Estado completo do sistema de áudio com versões
+///
+/// Cada leitura é comparada com a anterior; se algo mudou a versão avança
+/// e as diferenças são guardadas como JSON Patch, para que clientes que
+/// reconectam recebam só o que mudou desde a última versão que conheciam.
+/// As versões recomeçam a cada início do servidor, então elas vêm junto de
+/// um `epoch` e um cliente de outro `epoch` recebe o estado completo.
+pub struct StateService {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    /// Milissegundos Unix em que o serviço foi criado
+    epoch: u64,
+    history: Mutex<StateHistory>,
+    /// Mantido da leitura até o registro, para que uma leitura antiga nunca
+    /// seja registrada depois de uma mais nova
+    refreshing: AsyncMutex<()>,
+    /// Leituras bem-sucedidas; só muda com `refreshing` travado
+    reads: AtomicU64,
+    /// Última versão lida; avisa os WebSockets inscritos
+    versions: watch::Sender<u64>,
+}
+
+impl StateService {
+    pub fn new(audio_system: Arc<dyn AsyncAudioSystem>) -> Self {
+        let epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        Self {
+            audio_system,
+            epoch,
+            history: Mutex::new(StateHistory::default()),
+            refreshing: AsyncMutex::new(()),
+            reads: AtomicU64::new(0),
+            versions: watch::channel(0).0,
+        }
+    }
+
+    /// Relê o estado enquanto houver WebSockets inscritos
+    pub async fn run(self: Arc<Self>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            ticker.tick().await;
+            if self.versions.receiver_count() == 0 {
+                continue;
+            }
+
+            if let Err(error) = self.refresh().await {
+                tracing::warn!("Failed to refresh audio state: {}", error);
+            }
+        }
+    }
+
+    /// Avisa a cada versão nova do estado
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.versions.subscribe()
+    }
+
+    /// Lê o estado atual e retorna a sua versão
+    ///
+    /// As leituras são feitas uma de cada vez. Quem esperou enquanto outra
+    /// leitura começava e terminava usa o resultado dela em vez de ler de
+    /// novo, então pedidos simultâneos fazem uma única leitura.
+    pub async fn refresh(&self) -> AudioResult<u64> {
+        let requested = self.reads.load(Ordering::SeqCst);
+        let _refreshing = self.refreshing.lock().await;
+        if self.reads.load(Ordering::SeqCst) != requested {
+            return Ok(self.lock().version);
+        }
+
+        // Lido fora do lock do histórico para não segurar quem só o consulta
+        let state = serde_json::to_value(self.read_state().await?)
+            .expect("audio state is always representable as JSON");
+        self.reads.fetch_add(1, Ordering::SeqCst);
+        let mut history = self.lock();
+
+        if history.version == 0 {
+            history.version = 1;
+        } else {
+            let operations = json_patch::diff(&history.current, &state);
+            if operations.is_empty() {
+                return Ok(history.version);
+            }
+            history.version += 1;
+            let version = history.version;
+            history.patches.push_back((version, operations));
+            if history.patches.len() > TRACKED_VERSIONS {
+                history.patches.pop_front();
+            }
+        }
+        history.current = state;
+
+        let version = history.version;
+        self.versions.send_replace(version);
+        Ok(version)
+    }
+
+    /// Estado da última leitura
+    pub fn snapshot(&self) -> VersionedState {
+        let history = self.lock();
+        VersionedState {
+            epoch: self.epoch,
+            version: history.version,
+            state: history.current.clone(),
+        }
+    }
+
+    /// Mudanças entre `since` e a última leitura
+    ///
+    /// Quando as mudanças de `since` já foram descartadas, a versão não
+    /// existe ou `epoch` é de outro processo, retorna o estado completo. Sem
+    /// `epoch`, `since` é tomado como deste processo.
+    pub fn changes_since(&self, since: u64, epoch: Option<u64>) -> StateChanges {
+        let history = self.lock();
+        let first_tracked = history
+            .patches
+            .front()
+            .map_or(history.version + 1, |(version, _)| *version);
+
+        let other_epoch = epoch.is_some_and(|epoch| epoch != self.epoch);
+        if other_epoch || since == 0 || since > history.version || since + 1 < first_tracked {
+            return StateChanges::Resync {
+                epoch: self.epoch,
+                version: history.version,
+                state: history.current.clone(),
+            };
+        }
+
+        StateChanges::Patch {
+            since,
+            version: history.version,
+            operations: history
+                .patches
+                .iter()
+                .filter(|(version, _)| *version > since)
+                .flat_map(|(_, operations)| operations.iter().cloned())
+                .collect(),
+        }
+    }
+
+    async fn read_state(&self) -> AudioResult<AudioState> {
+        let mut devices = BTreeMap::new();
+        let mut default_device_id = None;
+
+        for device in self.audio_system.list_output_devices(false).await? {
+            if device.is_default {
+                default_device_id = Some(device.id.clone());
+            }
+            let sessions = self
+                .audio_system
+                .get_sessions_for_device(&device.id, false)
+                .await?
+                .into_iter()
+                .map(|group| (group.id.to_string(), group))
+                .collect();
+
+            devices.insert(
+                device.id.clone(),
+                DeviceStateEntry {
+                    volume: self.audio_system.get_device_volume(&device.id).await?,
+                    muted: self.audio_system.get_device_mute(&device.id).await?,
+                    sessions,
+                    device,
+                },
+            );
+        }
+
+        Ok(AudioState {
+            default_device_id,
+            devices,
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, StateHistory> {
+        self.history.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
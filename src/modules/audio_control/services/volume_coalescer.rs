@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+use tokio::task::JoinError;
+
+/// Escrita guardada para ser aplicada depois; o erro já vai para o log
+type PendingWrite = Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>;
+
+/// Resultado de um pedido entregue ao coalescedor
+#[derive(Debug)]
+pub enum WriteOutcome<R> {
+    /// A escrita foi aplicada agora e este é o seu resultado
+    Applied(R),
+    /// Outra escrita do mesmo alvo está em andamento; este pedido será
+    /// aplicado depois, a menos que um mais novo o substitua
+    Coalesced,
+}
+
+#[derive(Default)]
+struct TargetState {
+    busy: bool,
+    pending: Option<PendingWrite>,
+    last_write: Option<Instant>,
+}
+
+/// Junta rajadas de mudanças de volume de um mesmo alvo
+///
+/// Arrastar um slider gera dezenas de pedidos por segundo, e cada escrita no
+/// Windows enumera as sessões de novo. Por alvo, só uma
+/// escrita roda por vez e, entre duas, passa ao menos `interval`. Pedidos
+/// que chegam nesse meio tempo são respondidos na hora e só o mais recente
+/// é aplicado ao fim da escrita em andamento; os intermediários são
+/// descartados. Falhas dessas escritas adiadas vão para o log.
+pub struct VolumeCoalescer {
+    interval: Duration,
+    targets: Mutex<HashMap<String, TargetState>>,
+}
+
+impl VolumeCoalescer {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            targets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Aplica a escrita agora, se o alvo estiver livre, ou a guarda como a
+    /// próxima a ser aplicada
+    ///
+    /// A escrita roda em uma task própria, que termina mesmo se quem pediu
+    /// desistir da resposta; `key` identifica o alvo.
+    pub async fn submit<T, E, F, Fut>(
+        self: &Arc<Self>,
+        key: String,
+        write: F,
+    ) -> WriteOutcome<Result<T, E>>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Send + 'static,
+        E: Display + From<JoinError> + Send + 'static,
+    {
+        {
+            let mut targets = self.lock_targets();
+            targets.retain(|_, state| state.busy || !self.ready(state));
+            let state = targets.entry(key.clone()).or_default();
+            if state.busy {
+                state.pending = Some(deferred(key, write));
+                return WriteOutcome::Coalesced;
+            }
+            state.busy = true;
+            if !self.ready(state) {
+                state.pending = Some(deferred(key.clone(), write));
+                tokio::spawn(Arc::clone(self).drain(key));
+                return WriteOutcome::Coalesced;
+            }
+        }
+
+        let result = tokio::spawn(write())
+            .await
+            .unwrap_or_else(|error| Err(E::from(error)));
+
+        let mut targets = self.lock_targets();
+        let state = targets.entry(key.clone()).or_default();
+        state.last_write = Some(Instant::now());
+        if state.pending.is_some() {
+            tokio::spawn(Arc::clone(self).drain(key));
+        } else {
+            state.busy = false;
+        }
+        WriteOutcome::Applied(result)
+    }
+
+    /// Aplica as escritas guardadas do alvo até não sobrar nenhuma
+    async fn drain(self: Arc<Self>, key: String) {
+        loop {
+            let wait = {
+                let targets = self.lock_targets();
+                targets
+                    .get(&key)
+                    .and_then(|state| state.last_write)
+                    .map(|last_write| self.interval.saturating_sub(last_write.elapsed()))
+                    .unwrap_or_default()
+            };
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+
+            let write = {
+                let mut targets = self.lock_targets();
+                let state = targets.entry(key.clone()).or_default();
+                match state.pending.take() {
+                    Some(write) => write,
+                    None => {
+                        state.busy = false;
+                        return;
+                    }
+                }
+            };
+
+            if let Err(error) = tokio::spawn(write()).await {
+                tracing::error!("Coalesced volume write for {} panicked: {}", key, error);
+            }
+            if let Some(state) = self.lock_targets().get_mut(&key) {
+                state.last_write = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Se o intervalo mínimo desde a última escrita do alvo já passou
+    fn ready(&self, state: &TargetState) -> bool {
+        state
+            .last_write
+            .is_none_or(|last_write| last_write.elapsed() >= self.interval)
+    }
+
+    fn lock_targets(&self) -> MutexGuard<'_, HashMap<String, TargetState>> {
+        self.targets.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn deferred<T, E, F, Fut>(key: String, write: F) -> PendingWrite
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+    E: Display,
+{
+    Box::new(move || {
+        Box::pin(async move {
+            if let Err(error) = write().await {
+                tracing::warn!("Coalesced volume write for {} failed: {}", key, error);
+            }
+        })
+    })
+}
@@ -0,0 +1,415 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{
+        GroupVolumeMode, LimitPolicy, LimitReason, SessionGroup, SessionMember, VolumeLimits,
+        LIMIT_TOLERANCE,
+    },
+    platform::async_audio_system::AsyncAudioSystem,
+    services::{application_service::list_all_sessions, Notifier},
+    types::AudioResult,
+};
+use crate::modules::core::{persistence::JsonStore, Clock};
+
+/// Intervalo de verificação dos tetos contra mudanças externas
+const ENFORCE_INTERVAL: Duration = Duration::from_secs(1);
+
+const NOTIFICATION_TITLE: &str = "Volume limit";
+
+/// Limites de volume
+///
+/// Cada escrita de volume é limitada uma única vez. Os handlers passam os
+/// pedidos dos clientes por `limit_*`, que aplica todos os limites, e os
+/// escrevem direto no backend. Perfis, histórico e automações escrevem por
+/// `LimitedAudioSystem`, que aplica só os tetos (`cap_*`). Conforme a
+/// `LimitPolicy`, o volume é reduzido ao permitido ou a escrita é recusada. Em segundo plano,
+/// volumes acima dos tetos (mudados pelo Windows ou por outras aplicações)
+/// são corrigidos e avisados com `ServerEvent::Notification`.
+pub struct VolumeLimitService {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    notifier: Notifier,
+    limits: JsonStore<VolumeLimits>,
+    clock: Arc<dyn Clock>,
+    /// Evita que duas verificações corrijam e avisem o mesmo volume
+    enforcing: Mutex<()>,
+}
+
+impl VolumeLimitService {
+    pub fn new(
+        audio_system: Arc<dyn AsyncAudioSystem>,
+        notifier: Notifier,
+        limits: JsonStore<VolumeLimits>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            audio_system,
+            notifier,
+            limits,
+            clock,
+            enforcing: Mutex::new(()),
+        }
+    }
+
+    pub fn limits(&self) -> VolumeLimits {
+        self.limits.read(Clone::clone)
+    }
+
+    /// Substitui os limites; os tetos passam a valer na próxima verificação
+    pub fn set_limits(&self, limits: VolumeLimits) -> AudioResult<VolumeLimits> {
+        let limits = limits.normalized()?;
+        self.limits.update(|current| *current = limits.clone())?;
+        Ok(limits)
+    }
+
+    /// Volume a aplicar em um pedido de volume master
+    ///
+    /// `current` é o volume atual do dispositivo, lido pelo handler.
+    pub fn limit_master(&self, current: f32, requested: f32) -> AudioResult<f32> {
+        self.check_master(&self.limits(), current, requested)
+    }
+
+    /// Volume a aplicar em um pedido de volume de aplicação
+    ///
+    /// `groups` são os grupos da aplicação em todos os dispositivos; sem
+    /// nenhum o pedido passa sem limite e falha adiante com
+    /// `ApplicationNotFound`.
+    pub fn limit_application(
+        &self,
+        groups: &[&SessionGroup],
+        requested: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<f32> {
+        let Some(first) = groups.first() else {
+            return Ok(requested);
+        };
+        let limits = self.limits();
+        let cap = limits.application_cap(&first.app.process_name);
+        let current =
+            groups.iter().map(|group| group.volume_level).sum::<f32>() / groups.len() as f32;
+        let peak_ratio = groups
+            .iter()
+            .map(|group| peak_ratio(group, mode))
+            .fold(1.0, f32::max);
+        Self::resolve(
+            &limits,
+            limits.check(current, requested, peak_ratio, cap),
+            requested,
+        )
+    }
+
+    /// Volume a aplicar em um pedido de volume de grupo
+    pub fn limit_group(
+        &self,
+        group: &SessionGroup,
+        requested: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<f32> {
+        Self::check_group(&self.limits(), group, requested, mode)
+    }
+
+    /// Volume a aplicar em um pedido de volume de uma sessão
+    pub fn limit_session(&self, member: &SessionMember, requested: f32) -> AudioResult<f32> {
+        Self::check_session(&self.limits(), member, requested)
+    }
+
+    /// Níveis a aplicar em um pedido de volume dos canais de um dispositivo
+    ///
+    /// `current` são os níveis atuais dos canais; o canal mais alto é o que
+    /// os limites restringem, e os demais são reduzidos na mesma proporção.
+    pub fn limit_device_channels(
+        &self,
+        current: &[f32],
+        requested: Vec<f32>,
+    ) -> AudioResult<Vec<f32>> {
+        self.check_device_channels(&self.limits(), current, requested)
+    }
+
+    /// Níveis a aplicar em um pedido de volume dos canais de um grupo
+    pub fn limit_group_channels(
+        &self,
+        group: &SessionGroup,
+        current: &[f32],
+        requested: Vec<f32>,
+    ) -> AudioResult<Vec<f32>> {
+        Self::check_group_channels(&self.limits(), group, current, requested)
+    }
+
+    /// Verdadeiro quando há tetos por aplicação, que exigem conhecer o grupo
+    /// ou a sessão antes de escrever
+    pub fn has_application_caps(&self) -> bool {
+        !self.limits.read(|limits| limits.application_max.is_empty())
+    }
+
+    /// Volume master que pode ser escrito, só com os tetos, para as escritas
+    /// de `LimitedAudioSystem`
+    pub fn cap_master(&self, requested: f32) -> AudioResult<f32> {
+        // Sem max_step o volume atual não entra na verificação
+        self.check_master(&self.caps(), requested, requested)
+    }
+
+    /// Como `limit_group`, só com os tetos
+    pub fn cap_group(
+        &self,
+        group: &SessionGroup,
+        requested: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<f32> {
+        Self::check_group(&self.caps(), group, requested, mode)
+    }
+
+    /// Como `limit_session`, só com os tetos
+    pub fn cap_session(&self, member: &SessionMember, requested: f32) -> AudioResult<f32> {
+        Self::check_session(&self.caps(), member, requested)
+    }
+
+    /// Como `limit_device_channels`, só com os tetos
+    pub fn cap_device_channels(&self, requested: Vec<f32>) -> AudioResult<Vec<f32>> {
+        let current = requested.clone();
+        self.check_device_channels(&self.caps(), &current, requested)
+    }
+
+    /// Como `limit_group_channels`, só com os tetos
+    pub fn cap_group_channels(
+        &self,
+        group: &SessionGroup,
+        requested: Vec<f32>,
+    ) -> AudioResult<Vec<f32>> {
+        let current = requested.clone();
+        Self::check_group_channels(&self.caps(), group, &current, requested)
+    }
+
+    /// Corrige os volumes acima dos tetos
+    ///
+    /// Retorna quantos volumes foram corrigidos.
+    pub async fn enforce(&self) -> AudioResult<usize> {
+        let limits = self.limits();
+        if !limits.has_caps() {
+            return Ok(0);
+        }
+        let _enforcing = self.enforcing.lock().await;
+        let mut corrected = 0;
+
+        if let Some((cap, reason)) = limits.master_cap(self.clock.now().time()) {
+            for device in self.audio_system.list_output_devices(false).await? {
+                let volume = match self.audio_system.get_device_volume(&device.id).await {
+                    Ok(volume) => volume,
+                    Err(error) => {
+                        tracing::warn!("Failed to read volume of {}: {}", device.id, error);
+                        continue;
+                    }
+                };
+                if volume <= cap + LIMIT_TOLERANCE {
+                    continue;
+                }
+                match self.audio_system.set_device_volume(&device.id, cap).await {
+                    Ok(()) => {
+                        corrected += 1;
+                        self.notify(format!(
+                            "Master volume of '{}' lowered from {:.0}% to {:.0}% ({})",
+                            device.name, volume, cap, reason
+                        ));
+                    }
+                    Err(error) => {
+                        tracing::warn!("Failed to limit volume of {}: {}", device.id, error)
+                    }
+                }
+            }
+        }
+
+        if !limits.application_max.is_empty() {
+            for device in list_all_sessions(self.audio_system.as_ref(), false).await? {
+                for group in &device.sessions {
+                    let Some((cap, reason)) = limits.application_cap(&group.app.process_name)
+                    else {
+                        continue;
+                    };
+                    if group.volume_max <= cap + LIMIT_TOLERANCE {
+                        continue;
+                    }
+                    // Proporcional para que a sessão mais alta fique no teto
+                    // sem perder as diferenças entre as sessões
+                    let target = group.volume_level * cap / group.volume_max;
+                    let result = self
+                        .audio_system
+                        .set_group_volume(
+                            &group.id,
+                            &device.device_id,
+                            target,
+                            GroupVolumeMode::Proportional,
+                        )
+                        .await;
+                    match result {
+                        Ok(()) => {
+                            corrected += 1;
+                            self.notify(format!(
+                                "Volume of '{}' on '{}' lowered to {:.0}% ({})",
+                                group.app.display_name, device.device_name, cap, reason
+                            ));
+                        }
+                        Err(error) => tracing::warn!(
+                            "Failed to limit group {} on {}: {}",
+                            group.id,
+                            device.device_id,
+                            error
+                        ),
+                    }
+                }
+            }
+        }
+        Ok(corrected)
+    }
+
+    /// Verifica os tetos periodicamente até o runtime ser encerrado
+    pub async fn run(self: Arc<Self>) {
+        let mut ticker = tokio::time::interval(ENFORCE_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(error) = self.enforce().await {
+                tracing::warn!("Volume limit check failed: {}", error);
+            }
+        }
+    }
+
+    /// Limites sem `max_step`, que vale só para os pedidos dos clientes
+    fn caps(&self) -> VolumeLimits {
+        VolumeLimits {
+            max_step: None,
+            ..self.limits()
+        }
+    }
+
+    fn check_master(
+        &self,
+        limits: &VolumeLimits,
+        current: f32,
+        requested: f32,
+    ) -> AudioResult<f32> {
+        let cap = limits.master_cap(self.clock.now().time());
+        Self::resolve(
+            limits,
+            limits.check(current, requested, 1.0, cap),
+            requested,
+        )
+    }
+
+    fn check_group(
+        limits: &VolumeLimits,
+        group: &SessionGroup,
+        requested: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<f32> {
+        let cap = limits.application_cap(&group.app.process_name);
+        Self::resolve(
+            limits,
+            limits.check(group.volume_level, requested, peak_ratio(group, mode), cap),
+            requested,
+        )
+    }
+
+    fn check_session(
+        limits: &VolumeLimits,
+        member: &SessionMember,
+        requested: f32,
+    ) -> AudioResult<f32> {
+        let cap = limits.application_cap(&member.process_name);
+        Self::resolve(
+            limits,
+            limits.check(member.volume_level, requested, 1.0, cap),
+            requested,
+        )
+    }
+
+    fn check_device_channels(
+        &self,
+        limits: &VolumeLimits,
+        current: &[f32],
+        requested: Vec<f32>,
+    ) -> AudioResult<Vec<f32>> {
+        let cap = limits.master_cap(self.clock.now().time());
+        let peak = loudest(&requested);
+        let allowed = Self::resolve(limits, limits.check(loudest(current), peak, 1.0, cap), peak)?;
+        Ok(scale_levels(requested, peak, allowed))
+    }
+
+    fn check_group_channels(
+        limits: &VolumeLimits,
+        group: &SessionGroup,
+        current: &[f32],
+        requested: Vec<f32>,
+    ) -> AudioResult<Vec<f32>> {
+        // O volume ouvido em cada canal é o nível do canal sobre o da sessão
+        let cap = limits.application_cap(&group.app.process_name);
+        let peak = loudest(&requested);
+        let allowed = Self::resolve(
+            limits,
+            limits.check(loudest(current), peak, group.volume_max / 100.0, cap),
+            peak,
+        )?;
+        Ok(scale_levels(requested, peak, allowed))
+    }
+
+    fn resolve(
+        limits: &VolumeLimits,
+        exceeded: Option<(f32, LimitReason)>,
+        requested: f32,
+    ) -> AudioResult<f32> {
+        let Some((allowed, reason)) = exceeded else {
+            return Ok(requested);
+        };
+        match limits.policy {
+            LimitPolicy::Clamp => {
+                tracing::info!(
+                    "Volume request of {} clamped to {} ({})",
+                    requested,
+                    allowed,
+                    reason
+                );
+                Ok(allowed)
+            }
+            LimitPolicy::Reject => Err(AudioError::VolumeLimitExceeded {
+                requested,
+                allowed,
+                reason,
+            }),
+        }
+    }
+
+    fn notify(&self, message: String) {
+        tracing::info!("{}", message);
+        self.notifier
+            .notify(NOTIFICATION_TITLE.to_string(), message);
+    }
+}
+
+/// Quanto a sessão mais alta do grupo fica acima do volume do grupo depois
+/// de um ajuste em `mode`
+fn peak_ratio(group: &SessionGroup, mode: GroupVolumeMode) -> f32 {
+    if mode == GroupVolumeMode::Proportional && group.volume_level > 0.0 {
+        group.volume_max / group.volume_level
+    } else {
+        1.0
+    }
+}
+
+fn loudest(levels: &[f32]) -> f32 {
+    levels.iter().copied().fold(0.0, f32::max)
+}
+
+/// Reduz os níveis para que o mais alto passe de `peak` a `allowed`,
+/// mantendo o balanço entre os canais
+fn scale_levels(levels: Vec<f32>, peak: f32, allowed: f32) -> Vec<f32> {
+    if allowed >= peak || peak <= 0.0 {
+        return levels;
+    }
+    levels
+        .into_iter()
+        .map(|level| level * allowed / peak)
+        .collect()
+}
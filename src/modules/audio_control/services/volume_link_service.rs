@@ -0,0 +1,379 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{
+        normalize_process_name, DeviceSessions, GroupVolumeMode, LinkTarget, VolumeLink,
+        LIMIT_TOLERANCE,
+    },
+    platform::async_audio_system::AsyncAudioSystem,
+    services::application_service::{list_all_sessions, set_application_volume},
+    types::{AudioResult, GroupId},
+};
+use crate::modules::core::persistence::JsonStore;
+
+/// Intervalo de leitura dos volumes ligados
+const LINK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Alvo de um membro com o master já trocado pelo dispositivo padrão
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ResolvedTarget {
+    Device(String),
+    Group {
+        device_id: String,
+        group_id: GroupId,
+    },
+    Application(String),
+}
+
+/// Volume atual de um membro; `None` quando o alvo não existe agora
+/// (dispositivo desconectado, aplicação fechada)
+type MemberVolume = Option<(ResolvedTarget, f32)>;
+
+#[derive(Debug, Default)]
+struct LinkState {
+    /// Último volume visto (ou aplicado) de cada alvo ligado
+    observed: HashMap<ResolvedTarget, f32>,
+}
+
+/// Links de volume
+///
+/// Mudanças feitas pela API são propagadas na hora pelos handlers; as
+/// feitas por fora são detectadas comparando os volumes lidos com os
+/// últimos vistos. Os volumes aplicados pela propagação são registrados
+/// como vistos, então não voltam como mudanças e não há ida e volta entre
+/// os membros, mesmo quando um deles bate no limite de 0 ou 100.
+pub struct VolumeLinkService {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    links: JsonStore<BTreeMap<String, VolumeLink>>,
+    state: Mutex<LinkState>,
+}
+
+impl VolumeLinkService {
+    pub fn new(
+        audio_system: Arc<dyn AsyncAudioSystem>,
+        links: JsonStore<BTreeMap<String, VolumeLink>>,
+    ) -> Self {
+        Self {
+            audio_system,
+            links,
+            state: Mutex::new(LinkState::default()),
+        }
+    }
+
+    pub fn list(&self) -> Vec<VolumeLink> {
+        self.links.read(|links| links.values().cloned().collect())
+    }
+
+    /// Cria ou substitui um link
+    ///
+    /// Os membros passam a se mover juntos a partir da próxima mudança; os
+    /// volumes atuais não são alterados.
+    pub async fn save(&self, link: VolumeLink) -> AudioResult<VolumeLink> {
+        let link = link.normalized()?;
+
+        let conflict = self.links.read(|links| {
+            links
+                .values()
+                .filter(|other| other.name != link.name)
+                .find(|other| {
+                    other.members.iter().any(|member| {
+                        link.members
+                            .iter()
+                            .any(|candidate| candidate.target == member.target)
+                    })
+                })
+                .map(|other| other.name.clone())
+        });
+        if let Some(other) = conflict {
+            return Err(AudioError::InvalidRequestBody {
+                message: format!(
+                    "Link '{}': A target is already linked in '{}'",
+                    link.name, other
+                ),
+            });
+        }
+
+        self.links.update(|links| {
+            links.insert(link.name.clone(), link.clone());
+        })?;
+        self.lock_state().await.observed.clear();
+        Ok(link)
+    }
+
+    pub async fn delete(&self, name: &str) -> AudioResult<()> {
+        let removed = self
+            .links
+            .update(|links| links.remove(name.trim()).is_some())?;
+        if !removed {
+            return Err(AudioError::LinkNotFound {
+                name: name.to_string(),
+            });
+        }
+        self.lock_state().await.observed.clear();
+        Ok(())
+    }
+
+    /// Propaga um volume aplicado pela API aos outros membros do link
+    ///
+    /// Falhas ao ajustar os outros membros são registradas no log: o volume
+    /// pedido já foi aplicado. Retorna quantos membros foram ajustados.
+    pub async fn propagate(&self, target: &LinkTarget, volume: f32) -> usize {
+        let links = self.list();
+        if links.is_empty() {
+            return 0;
+        }
+        match self.try_propagate(&links, target, volume).await {
+            Ok(adjusted) => adjusted,
+            Err(error) => {
+                tracing::warn!("Failed to propagate linked volume: {}", error);
+                0
+            }
+        }
+    }
+
+    /// Lê os volumes ligados e propaga os que mudaram desde a última leitura
+    ///
+    /// Se mais de um membro de um link mudou, vale o primeiro da lista.
+    /// Retorna quantos membros foram ajustados.
+    pub async fn tick(&self) -> AudioResult<usize> {
+        let links = self.list();
+        if links.is_empty() {
+            self.lock_state().await.observed.clear();
+            return Ok(0);
+        }
+
+        let default_device_id = self.audio_system.default_device_id().await?;
+        let sessions = self.sessions_for(&links).await?;
+        let mut state = self.lock_state().await;
+        let mut adjusted = 0;
+
+        for link in &links {
+            let current = self
+                .read_link(link, default_device_id.as_deref(), &sessions)
+                .await;
+            let changed = current.iter().enumerate().find_map(|(index, member)| {
+                let (target, volume) = member.as_ref()?;
+                let previous = state.observed.get(target)?;
+                ((previous - volume).abs() > LIMIT_TOLERANCE).then_some((index, *volume))
+            });
+
+            match changed {
+                Some((source, volume)) => {
+                    adjusted += self.apply(link, source, volume, &current, &mut state).await;
+                }
+                None => {
+                    for (target, volume) in current.into_iter().flatten() {
+                        state.observed.insert(target, volume);
+                    }
+                }
+            }
+        }
+        Ok(adjusted)
+    }
+
+    /// Acompanha os volumes ligados até o runtime ser encerrado
+    pub async fn run(self: Arc<Self>) {
+        let mut ticker = tokio::time::interval(LINK_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(error) = self.tick().await {
+                tracing::warn!("Volume link step failed: {}", error);
+            }
+        }
+    }
+
+    async fn try_propagate(
+        &self,
+        links: &[VolumeLink],
+        target: &LinkTarget,
+        volume: f32,
+    ) -> AudioResult<usize> {
+        let default_device_id = self.audio_system.default_device_id().await?;
+        let Some(changed) = resolve(target, default_device_id.as_deref()) else {
+            return Ok(0);
+        };
+
+        for link in links {
+            let source = link.members.iter().position(|member| {
+                resolve(&member.target, default_device_id.as_deref()).as_ref() == Some(&changed)
+            });
+            if let Some(source) = source {
+                let sessions = self.sessions_for(std::slice::from_ref(link)).await?;
+                let current = self
+                    .read_link(link, default_device_id.as_deref(), &sessions)
+                    .await;
+                let mut state = self.lock_state().await;
+                return Ok(self.apply(link, source, volume, &current, &mut state).await);
+            }
+        }
+        Ok(0)
+    }
+
+    /// Leva os membros do link ao nível que corresponde a `volume` no
+    /// membro `source`
+    async fn apply(
+        &self,
+        link: &VolumeLink,
+        source: usize,
+        volume: f32,
+        current: &[MemberVolume],
+        state: &mut LinkState,
+    ) -> usize {
+        let level = link.members[source].level_for(volume);
+        let mut adjusted = 0;
+
+        for (index, (member, reading)) in link.members.iter().zip(current).enumerate() {
+            let Some((target, current_volume)) = reading else {
+                continue;
+            };
+            if index == source {
+                state.observed.insert(target.clone(), volume);
+                continue;
+            }
+
+            let volume = member.volume_for(level);
+            if (volume - current_volume).abs() <= LIMIT_TOLERANCE {
+                state.observed.insert(target.clone(), *current_volume);
+                continue;
+            }
+            match self.set_volume(target, volume).await {
+                Ok(()) => {
+                    adjusted += 1;
+                    state.observed.insert(target.clone(), volume);
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        "Failed to apply link '{}' to {:?}: {}",
+                        link.name,
+                        target,
+                        error
+                    );
+                    state.observed.insert(target.clone(), *current_volume);
+                }
+            }
+        }
+        adjusted
+    }
+
+    async fn read_link(
+        &self,
+        link: &VolumeLink,
+        default_device_id: Option<&str>,
+        sessions: &[DeviceSessions],
+    ) -> Vec<MemberVolume> {
+        let mut current = Vec::with_capacity(link.members.len());
+        for member in &link.members {
+            let reading = match resolve(&member.target, default_device_id) {
+                Some(target) => self
+                    .read_volume(&target, sessions)
+                    .await
+                    .map(|volume| (target, volume)),
+                None => None,
+            };
+            current.push(reading);
+        }
+        current
+    }
+
+    async fn read_volume(
+        &self,
+        target: &ResolvedTarget,
+        sessions: &[DeviceSessions],
+    ) -> Option<f32> {
+        match target {
+            ResolvedTarget::Device(device_id) => {
+                self.audio_system.get_device_volume(device_id).await.ok()
+            }
+            ResolvedTarget::Group {
+                device_id,
+                group_id,
+            } => sessions
+                .iter()
+                .filter(|device| device.device_id == *device_id)
+                .flat_map(|device| &device.sessions)
+                .find(|group| group.id == *group_id)
+                .map(|group| group.volume_level),
+            ResolvedTarget::Application(process_name) => {
+                let volumes: Vec<f32> = sessions
+                    .iter()
+                    .flat_map(|device| &device.sessions)
+                    .filter(|group| {
+                        normalize_process_name(&group.app.process_name) == *process_name
+                    })
+                    .map(|group| group.volume_level)
+                    .collect();
+                (!volumes.is_empty()).then(|| volumes.iter().sum::<f32>() / volumes.len() as f32)
+            }
+        }
+    }
+
+    async fn set_volume(&self, target: &ResolvedTarget, volume: f32) -> AudioResult<()> {
+        // Proporcional para manter as diferenças entre as sessões dos grupos
+        match target {
+            ResolvedTarget::Device(device_id) => {
+                self.audio_system.set_device_volume(device_id, volume).await
+            }
+            ResolvedTarget::Group {
+                device_id,
+                group_id,
+            } => {
+                self.audio_system
+                    .set_group_volume(group_id, device_id, volume, GroupVolumeMode::Proportional)
+                    .await
+            }
+            ResolvedTarget::Application(process_name) => set_application_volume(
+                self.audio_system.as_ref(),
+                process_name,
+                volume,
+                GroupVolumeMode::Proportional,
+            )
+            .await
+            .map(|_| ()),
+        }
+    }
+
+    /// Sessões de todos os dispositivos, lidas só se algum link tiver grupos
+    /// ou aplicações
+    async fn sessions_for(&self, links: &[VolumeLink]) -> AudioResult<Vec<DeviceSessions>> {
+        let needs_sessions = links.iter().flat_map(|link| &link.members).any(|member| {
+            matches!(
+                member.target,
+                LinkTarget::Group { .. } | LinkTarget::Application { .. }
+            )
+        });
+        if needs_sessions {
+            list_all_sessions(self.audio_system.as_ref(), false).await
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    async fn lock_state(&self) -> MutexGuard<'_, LinkState> {
+        self.state.lock().await
+    }
+}
+
+fn resolve(target: &LinkTarget, default_device_id: Option<&str>) -> Option<ResolvedTarget> {
+    match target {
+        LinkTarget::Master => default_device_id.map(|id| ResolvedTarget::Device(id.to_string())),
+        LinkTarget::Device { device_id } => Some(ResolvedTarget::Device(device_id.clone())),
+        LinkTarget::Group {
+            device_id,
+            group_id,
+        } => Some(ResolvedTarget::Group {
+            device_id: device_id.clone(),
+            group_id: GroupId::new(group_id.clone()),
+        }),
+        LinkTarget::Application { process_name } => Some(ResolvedTarget::Application(
+            normalize_process_name(process_name),
+        )),
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::modules::audio_control::{
+    errors::AudioError,
+    models::{normalize_process_name, AppVolumeMemory, RememberedVolume},
+    platform::async_audio_system::AsyncAudioSystem,
+    services::event_loop::handle_event,
+    types::AudioResult,
+};
+use crate::modules::core::{
+    broadcasting::{next_event, EventReceiver},
+    persistence::JsonStore,
+    ServerEvent,
+};
+
+/// Volume e mute observados de um executável
+type ObservedVolume = (f32, bool);
+
+/// Memória de volume por aplicação
+///
+/// Acompanha os `SessionVolumeChanged` publicados pelo monitor de sessões,
+/// o que inclui tanto as alterações feitas pela API quanto as feitas fora do
+/// servidor (mixer do Windows), e reaplica o último volume de cada
+/// executável em toda nova sessão dele.
+pub struct VolumeMemoryService {
+    audio_system: Arc<dyn AsyncAudioSystem>,
+    /// Nome normalizado do processo -> último volume gravado
+    memory: JsonStore<BTreeMap<String, RememberedVolume>>,
+    /// Volumes observados que ainda não foram gravados no disco
+    unsaved: Mutex<BTreeMap<String, ObservedVolume>>,
+    /// Tempo sem novas mudanças antes de gravar os volumes observados
+    write_delay: Duration,
+}
+
+impl VolumeMemoryService {
+    pub fn new(
+        audio_system: Arc<dyn AsyncAudioSystem>,
+        memory: JsonStore<BTreeMap<String, RememberedVolume>>,
+        write_delay: Duration,
+    ) -> Self {
+        Self {
+            audio_system,
+            memory,
+            unsaved: Mutex::new(BTreeMap::new()),
+            write_delay,
+        }
+    }
+
+    pub fn entries(&self) -> Vec<AppVolumeMemory> {
+        let mut memory = self.memory.read(|memory| memory.clone());
+        for (process_name, observed) in self.lock_unsaved().iter() {
+            remember(memory.entry(process_name.clone()).or_default(), *observed);
+        }
+
+        memory
+            .into_iter()
+            .map(|(process_name, remembered)| AppVolumeMemory {
+                process_name,
+                remembered,
+            })
+            .collect()
+    }
+
+    /// Liga ou desliga a memória de um executável
+    ///
+    /// Desligar mantém o último volume gravado, que volta a valer quando a
+    /// memória for religada.
+    pub fn set_enabled(&self, process_name: &str, enabled: bool) -> AudioResult<AppVolumeMemory> {
+        let process_name = normalize_process_name(process_name);
+        if process_name.is_empty() {
+            return Err(AudioError::InvalidRequestBody {
+                message: "Process name must not be empty".to_string(),
+            });
+        }
+
+        let observed = self.lock_unsaved().remove(&process_name);
+        let remembered = self.memory.update(|memory| {
+            let remembered = memory.entry(process_name.clone()).or_default();
+            if let Some(observed) = observed {
+                remember(remembered, observed);
+            }
+            remembered.enabled = enabled;
+            remembered.clone()
+        })?;
+
+        Ok(AppVolumeMemory {
+            process_name,
+            remembered,
+        })
+    }
+
+    /// Apaga a memória de um executável; retorna se ela existia
+    pub fn forget(&self, process_name: &str) -> AudioResult<bool> {
+        let process_name = normalize_process_name(process_name);
+        let observed = self.lock_unsaved().remove(&process_name).is_some();
+        let removed = self
+            .memory
+            .update(|memory| memory.remove(&process_name).is_some())?;
+        Ok(removed || observed)
+    }
+
+    /// Guarda o volume de uma sessão que mudou, até a próxima gravação
+    ///
+    /// Retorna se o volume foi guardado; executáveis com a memória desligada
+    /// são ignorados.
+    pub fn observe(&self, event: &ServerEvent) -> bool {
+        let ServerEvent::SessionVolumeChanged {
+            process_name,
+            volume,
+            muted,
+            ..
+        } = event
+        else {
+            return false;
+        };
+
+        let process_name = normalize_process_name(process_name);
+        let enabled = self.memory.read(|memory| {
+            memory
+                .get(&process_name)
+                .is_none_or(|remembered| remembered.enabled)
+        });
+        if enabled {
+            self.lock_unsaved().insert(process_name, (*volume, *muted));
+        }
+        enabled
+    }
+
+    /// Grava no disco os volumes guardados por `observe`
+    ///
+    /// Retorna quantos executáveis foram gravados.
+    pub fn flush(&self) -> AudioResult<usize> {
+        let unsaved = std::mem::take(&mut *self.lock_unsaved());
+        if unsaved.is_empty() {
+            return Ok(0);
+        }
+
+        let count = unsaved.len();
+        self.memory.update(|memory| {
+            for (process_name, observed) in unsaved {
+                remember(memory.entry(process_name).or_default(), observed);
+            }
+        })?;
+        Ok(count)
+    }
+
+    /// Reaplica o volume lembrado a uma sessão recém iniciada
+    ///
+    /// Retorna se a sessão foi ajustada.
+    pub async fn restore(&self, event: &ServerEvent) -> AudioResult<bool> {
+        let ServerEvent::SessionStarted {
+            session_id,
+            process_name,
+            device_id,
+            ..
+        } = event
+        else {
+            return Ok(false);
+        };
+
+        let Some(remembered) = self.remembered(&normalize_process_name(process_name)) else {
+            return Ok(false);
+        };
+        let (true, Some(volume)) = (remembered.enabled, remembered.volume) else {
+            return Ok(false);
+        };
+
+        self.audio_system
+            .set_session_volume(device_id, session_id, volume)
+            .await?;
+        self.audio_system
+            .set_session_mute(device_id, session_id, remembered.muted)
+            .await?;
+        Ok(true)
+    }
+
+    /// Acompanha as sessões até o broadcaster fechar
+    ///
+    /// Os volumes observados são gravados depois de `write_delay` sem novas
+    /// mudanças, para que arrastar um slider não grave o arquivo a cada
+    /// leitura do monitor; o fim de uma sessão grava na hora. Cada
+    /// `SessionStarted` recebe o volume lembrado.
+    pub async fn run(self: Arc<Self>, mut events: EventReceiver) {
+        let mut flush_at = None;
+        loop {
+            let flush = async {
+                match flush_at {
+                    Some(at) => tokio::time::sleep_until(at).await,
+                    None => std::future::pending().await,
+                }
+            };
+            let event = tokio::select! {
+                received = next_event(&mut events, "Volume memory") => match received {
+                    Some(event) => event,
+                    None => break,
+                },
+                _ = flush => {
+                    flush_at = None;
+                    self.flush_logged();
+                    continue;
+                }
+            };
+
+            match event {
+                ServerEvent::SessionVolumeChanged { .. } if self.observe(&event) => {
+                    flush_at = Some(tokio::time::Instant::now() + self.write_delay);
+                }
+                ServerEvent::SessionEnded { .. } if flush_at.take().is_some() => {
+                    self.flush_logged();
+                }
+                ServerEvent::SessionStarted { .. } => {
+                    handle_event(&self, event, "Volume memory", |service, event| async move {
+                        service.restore(&event).await
+                    })
+                    .await
+                }
+                _ => {}
+            }
+        }
+        self.flush_logged();
+    }
+
+    /// Estado lembrado de um executável, incluindo o que ainda não foi gravado
+    fn remembered(&self, process_name: &str) -> Option<RememberedVolume> {
+        let observed = self.lock_unsaved().get(process_name).copied();
+        let mut remembered = self.memory.read(|memory| memory.get(process_name).cloned());
+        if let Some(observed) = observed {
+            remember(remembered.get_or_insert_with(Default::default), observed);
+        }
+        remembered
+    }
+
+    fn flush_logged(&self) {
+        if let Err(error) = self.flush() {
+            tracing::warn!("Failed to save remembered volumes: {}", error);
+        }
+    }
+
+    fn lock_unsaved(&self) -> MutexGuard<'_, BTreeMap<String, ObservedVolume>> {
+        self.unsaved.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn remember(remembered: &mut RememberedVolume, (volume, muted): ObservedVolume) {
+    if remembered.enabled {
+        remembered.volume = Some(volume);
+        remembered.muted = muted;
+    }
+}
@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+use super::mocks::MOCK_GROUP_ID;
+use super::test_server::TestServer;
+use crate::modules::audio_control::models::{SessionMember, SessionState};
+use crate::modules::core::ServerEvent;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn connect_events(server: &TestServer) -> Socket {
+    let (socket, _) = connect_async(server.ws_url("/api/v1/events"))
+        .await
+        .expect("event socket should connect");
+    // Dá tempo para o handler inscrever o cliente no broadcaster
+    tokio::time::sleep(Duration::from_millis(25)).await;
+    socket
+}
+
+async fn next_event(socket: &mut Socket) -> Value {
+    let message = tokio::time::timeout(Duration::from_secs(2), socket.next())
+        .await
+        .expect("event should arrive in time")
+        .expect("socket should stay open")
+        .expect("event should be valid");
+    serde_json::from_str(message.to_text().expect("event should be text"))
+        .expect("event should be valid JSON")
+}
+
+fn game_session() -> SessionMember {
+    SessionMember {
+        session_id: "mock-game-session".to_string(),
+        pid: 4321,
+        process_name: "Game".to_string(),
+        volume_level: 100.0,
+        muted: false,
+        state: SessionState::Active,
+    }
+}
+
+#[tokio::test]
+async fn test_move_group_routes_every_process_and_emits_event() {
+    let server = TestServer::new().await;
+    let mut events = connect_events(&server).await;
+
+    let response = server
+        .post_json(
+            "/api/v1/move_group",
+            json!({
+                "device_id": "mock-device-id",
+                "group_id": MOCK_GROUP_ID,
+                "target_device_id": "mock-unplugged-id"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    assert_eq!(
+        server.audio_system().routed(),
+        vec![
+            (1234, "mock-unplugged-id".to_string()),
+            (1235, "mock-unplugged-id".to_string())
+        ]
+    );
+
+    let event = next_event(&mut events).await;
+    assert_eq!(event["event_type"], "session_routed");
+    assert_eq!(event["group_id"], MOCK_GROUP_ID);
+    assert_eq!(event["process_name"], "mock-session");
+    assert_eq!(event["from_device_id"], "mock-device-id");
+    assert_eq!(event["to_device_id"], "mock-unplugged-id");
+}
+
+#[tokio::test]
+async fn test_move_group_to_unknown_device_returns_not_found() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/move_group",
+            json!({
+                "device_id": "mock-device-id",
+                "group_id": MOCK_GROUP_ID,
+                "target_device_id": "missing-device"
+            }),
+        )
+        .await;
+
+    assert_eq!(response.status(), 404);
+    assert!(server.audio_system().routed().is_empty());
+}
+
+#[tokio::test]
+async fn test_routing_preferences_crud_normalizes_process_name() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_routing_preference",
+            json!({ "process_name": "Discord.EXE", "device_id": "mock-unplugged-id" }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["process_name"], "discord");
+
+    let body: Value = server
+        .get("/api/v1/routing_preferences")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        body["data"],
+        json!([{ "process_name": "discord", "device_id": "mock-unplugged-id" }])
+    );
+
+    let body: Value = server
+        .post_json(
+            "/api/v1/remove_routing_preference",
+            json!({ "process_name": "discord" }),
+        )
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["data"], true);
+
+    let body: Value = server
+        .get("/api/v1/routing_preferences")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["data"], json!([]));
+}
+
+#[tokio::test]
+async fn test_set_routing_preference_rejects_unknown_device() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_routing_preference",
+            json!({ "process_name": "discord", "device_id": "missing-device" }),
+        )
+        .await;
+
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_routing_preference_is_applied_when_app_starts() {
+    let server = TestServer::new().await;
+    server
+        .post_json(
+            "/api/v1/set_routing_preference",
+            json!({ "process_name": "game.exe", "device_id": "mock-unplugged-id" }),
+        )
+        .await;
+
+    let monitor = server.audio_module().session_monitor();
+    assert!(monitor.poll().await.unwrap().is_empty());
+
+    let mut events = connect_events(&server).await;
+    server.audio_system().add_session(game_session());
+    let changes = monitor.poll().await.unwrap();
+    assert!(matches!(
+        changes.as_slice(),
+        [ServerEvent::SessionStarted { pid: 4321, .. }]
+    ));
+
+    assert_eq!(
+        next_event(&mut events).await["event_type"],
+        "session_started"
+    );
+    let routed = next_event(&mut events).await;
+    assert_eq!(routed["event_type"], "session_routed");
+    assert_eq!(routed["process_name"], "game");
+    assert_eq!(routed["to_device_id"], "mock-unplugged-id");
+    assert_eq!(
+        server.audio_system().routed(),
+        vec![(4321, "mock-unplugged-id".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn test_session_monitor_reports_ended_sessions() {
+    let server = TestServer::new().await;
+    let monitor = server.audio_module().session_monitor();
+    monitor.poll().await.unwrap();
+
+    server.audio_system().remove_session("mock-session-2");
+    let changes = monitor.poll().await.unwrap();
+
+    assert!(matches!(
+        changes.as_slice(),
+        [ServerEvent::SessionEnded { session_id, .. }] if session_id == "mock-session-2"
+    ));
+    assert!(monitor.poll().await.unwrap().is_empty());
+}
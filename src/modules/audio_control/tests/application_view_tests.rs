@@ -0,0 +1,119 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use super::mocks::{headset, MOCK_GROUP_ID};
+use super::test_server::TestServer;
+
+/// Segundo dispositivo ativo; o mock expõe as mesmas sessões nele
+#[tokio::test]
+async fn test_http_list_sessions_returns_every_active_device() {
+    let server = TestServer::new().await;
+    server.audio_system().add_device(headset());
+
+    let response = server.get("/api/v1/list_sessions").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: Value = response.json().await.unwrap();
+    let devices = json["data"].as_array().unwrap();
+    assert_eq!(json["headers"]["count"], 2);
+    assert_eq!(devices[0]["device_id"], "mock-device-id");
+    assert_eq!(devices[0]["is_default"], true);
+    assert_eq!(devices[1]["device_id"], "mock-headset-id");
+    assert_eq!(devices[1]["device_name"], "Mock Headset");
+    assert_eq!(devices[1]["sessions"][0]["id"], MOCK_GROUP_ID);
+    assert!(devices[1]["sessions"][0].get("sessions").is_none());
+}
+
+#[tokio::test]
+async fn test_http_list_sessions_expanded() {
+    let server = TestServer::new().await;
+
+    let json: Value = server
+        .get("/api/v1/list_sessions?expand=true")
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    let members = json["data"][0]["sessions"][0]["sessions"]
+        .as_array()
+        .expect("expanded groups should list their sessions");
+    assert_eq!(members.len(), 2);
+}
+
+#[tokio::test]
+async fn test_http_list_applications_merges_devices() {
+    let server = TestServer::new().await;
+    server.audio_system().add_device(headset());
+
+    let json: Value = server
+        .get("/api/v1/list_applications")
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(json["headers"]["count"], 1);
+    let application = &json["data"][0];
+    assert_eq!(application["process_name"], "mock-session");
+    assert_eq!(application["display_name"], "Mock Session Player");
+    assert_eq!(application["volume_level"], 55.0);
+    assert_eq!(application["mute_state"], "none");
+    assert_eq!(
+        application["instances"],
+        json!([
+            {
+                "device_id": "mock-device-id",
+                "group_id": MOCK_GROUP_ID,
+                "volume_level": 55.0,
+                "mute_state": "none"
+            },
+            {
+                "device_id": "mock-headset-id",
+                "group_id": MOCK_GROUP_ID,
+                "volume_level": 55.0,
+                "mute_state": "none"
+            }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_http_set_application_volume_applies_everywhere() {
+    let server = TestServer::new().await;
+    server.audio_system().add_device(headset());
+
+    let response = server
+        .post_json(
+            "/api/v1/set_application_volume",
+            json!({ "process_name": "Mock-Session.exe", "volume": 30.0 }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["headers"]["count"], 2);
+
+    let json: Value = server
+        .get("/api/v1/list_applications")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json["data"][0]["volume_level"], 30.0);
+}
+
+#[tokio::test]
+async fn test_http_set_application_volume_unknown_application() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_application_volume",
+            json!({ "process_name": "missing", "volume": 30.0 }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "Application not found");
+}
@@ -0,0 +1,326 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use axum::body::to_bytes;
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use super::mocks::{MockAudioSystem, MOCK_DEVICE_VOLUME, MOCK_GROUP_ID};
+use crate::modules::audio_control::audio_handlers;
+use crate::modules::audio_control::audio_module::{AudioModule, AudioModuleConfig};
+use crate::modules::audio_control::errors::AudioError;
+use crate::modules::audio_control::models::{
+    AppInfo, DeviceSound, GroupPeak, GroupVolumeMode, SessionGroup,
+};
+use crate::modules::audio_control::platform::async_audio_system::{
+    AsyncAudioMeteringControl, AsyncAudioOutputDeviceControl, AsyncAudioProcessLookup,
+    AsyncAudioSessionControl, AsyncMediaPlaybackControl,
+};
+use crate::modules::audio_control::platform::audio_system_interface::{
+    AudioMeteringControl, AudioOutputDeviceControl, AudioProcessLookup, AudioSessionControl,
+    MediaPlaybackControl,
+};
+use crate::modules::audio_control::platform::blocking_audio_adapter::BlockingAudioAdapter;
+use crate::modules::audio_control::types::{AudioResult, GroupId};
+use crate::modules::core::executor::BackendExecutorConfig;
+use crate::modules::core::tests_support::BaseTestServer;
+
+/// Backend que responde pela rede: cada chamada espera `latency` sem ocupar
+/// uma thread e então repassa ao mock
+struct RemoteStandIn {
+    inner: MockAudioSystem,
+    latency: Duration,
+    calls: AtomicUsize,
+}
+
+impl RemoteStandIn {
+    fn new(latency: Duration) -> Self {
+        Self {
+            inner: MockAudioSystem::default(),
+            latency,
+            calls: AtomicUsize::new(0),
+        }
+    }
+
+    async fn round_trip(&self) {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(self.latency).await;
+    }
+}
+
+#[async_trait]
+impl AsyncAudioOutputDeviceControl for RemoteStandIn {
+    async fn list_output_devices(&self, include_inactive: bool) -> AudioResult<Vec<DeviceSound>> {
+        self.round_trip().await;
+        self.inner.list_output_devices(include_inactive)
+    }
+
+    async fn get_actual_volume(&self) -> AudioResult<f32> {
+        self.round_trip().await;
+        self.inner.get_actual_volume()
+    }
+
+    async fn get_device_volume(&self, device_id: &str) -> AudioResult<f32> {
+        self.round_trip().await;
+        self.inner.get_device_volume(device_id)
+    }
+
+    async fn set_device_volume(&self, device_id: &str, volume: f32) -> AudioResult<()> {
+        self.round_trip().await;
+        self.inner.set_device_volume(device_id, volume)
+    }
+
+    async fn get_device_mute(&self, device_id: &str) -> AudioResult<bool> {
+        self.round_trip().await;
+        self.inner.get_device_mute(device_id)
+    }
+
+    async fn set_device_mute(&self, device_id: &str, muted: bool) -> AudioResult<()> {
+        self.round_trip().await;
+        self.inner.set_device_mute(device_id, muted)
+    }
+
+    async fn set_default_device(&self, device_id: &str) -> AudioResult<()> {
+        self.round_trip().await;
+        self.inner.set_default_device(device_id)
+    }
+
+    async fn get_device_channel_volumes(&self, device_id: &str) -> AudioResult<Vec<f32>> {
+        self.round_trip().await;
+        self.inner.get_device_channel_volumes(device_id)
+    }
+
+    async fn set_device_channel_volumes(&self, device_id: &str, levels: &[f32]) -> AudioResult<()> {
+        self.round_trip().await;
+        self.inner.set_device_channel_volumes(device_id, levels)
+    }
+}
+
+#[async_trait]
+impl AsyncAudioSessionControl for RemoteStandIn {
+    async fn get_sessions_for_device(
+        &self,
+        device_id: &str,
+        expand: bool,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        self.round_trip().await;
+        self.inner.get_sessions_for_device(device_id, expand)
+    }
+
+    async fn set_group_volume(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        volume: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<()> {
+        self.round_trip().await;
+        self.inner
+            .set_group_volume(group_id, device_id, volume, mode)
+    }
+
+    async fn set_session_volume(
+        &self,
+        device_id: &str,
+        session_id: &str,
+        volume: f32,
+    ) -> AudioResult<()> {
+        self.round_trip().await;
+        self.inner.set_session_volume(device_id, session_id, volume)
+    }
+
+    async fn set_group_mute(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        muted: bool,
+    ) -> AudioResult<()> {
+        self.round_trip().await;
+        self.inner.set_group_mute(group_id, device_id, muted)
+    }
+
+    async fn set_session_mute(
+        &self,
+        device_id: &str,
+        session_id: &str,
+        muted: bool,
+    ) -> AudioResult<()> {
+        self.round_trip().await;
+        self.inner.set_session_mute(device_id, session_id, muted)
+    }
+
+    async fn move_group_to_device(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        target_device_id: &str,
+    ) -> AudioResult<()> {
+        self.round_trip().await;
+        self.inner
+            .move_group_to_device(group_id, device_id, target_device_id)
+    }
+
+    async fn set_process_output_device(&self, pid: u32, device_id: &str) -> AudioResult<()> {
+        self.round_trip().await;
+        self.inner.set_process_output_device(pid, device_id)
+    }
+
+    async fn get_group_icon(&self, group_id: &GroupId) -> AudioResult<Vec<u8>> {
+        self.round_trip().await;
+        self.inner.get_group_icon(group_id)
+    }
+
+    async fn get_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+    ) -> AudioResult<Vec<f32>> {
+        self.round_trip().await;
+        self.inner.get_group_channel_volumes(group_id, device_id)
+    }
+
+    async fn set_group_channel_volumes(
+        &self,
+        group_id: &GroupId,
+        device_id: &str,
+        levels: &[f32],
+    ) -> AudioResult<()> {
+        self.round_trip().await;
+        self.inner
+            .set_group_channel_volumes(group_id, device_id, levels)
+    }
+}
+
+#[async_trait]
+impl AsyncAudioMeteringControl for RemoteStandIn {
+    async fn get_device_peak(&self, device_id: &str) -> AudioResult<f32> {
+        self.round_trip().await;
+        self.inner.get_device_peak(device_id)
+    }
+
+    async fn get_group_peaks(&self, device_id: &str) -> AudioResult<Vec<GroupPeak>> {
+        self.round_trip().await;
+        self.inner.get_group_peaks(device_id)
+    }
+}
+
+#[async_trait]
+impl AsyncAudioProcessLookup for RemoteStandIn {
+    async fn get_app_info(&self, pid: u32) -> AppInfo {
+        self.round_trip().await;
+        self.inner.get_app_info(pid)
+    }
+}
+
+#[async_trait]
+impl AsyncMediaPlaybackControl for RemoteStandIn {
+    async fn toggle_media_playback(&self) -> AudioResult<()> {
+        self.round_trip().await;
+        self.inner.toggle_media_playback()
+    }
+}
+
+async fn body_json(response: axum::response::Response) -> Value {
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_handlers_await_an_async_backend_without_blocking() {
+    let latency = Duration::from_millis(50);
+    let backend = RemoteStandIn::new(latency);
+
+    // Todas as chamadas esperam juntas na única thread do runtime de teste
+    let started = Instant::now();
+    let responses =
+        futures::future::join_all((0..20).map(|_| audio_handlers::handle_get_volume(&backend)))
+            .await;
+    let elapsed = started.elapsed();
+    assert!(elapsed < latency * 5, "20 calls took {elapsed:?}");
+    for response in responses {
+        let json = body_json(response.unwrap()).await;
+        assert_eq!(json["data"], MOCK_DEVICE_VOLUME);
+    }
+
+    let json = body_json(
+        audio_handlers::handle_list_all_sessions(&backend, false)
+            .await
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(json["headers"]["count"], 1);
+    assert_eq!(json["data"][0]["sessions"][0]["id"], MOCK_GROUP_ID);
+    assert_eq!(backend.calls.load(Ordering::SeqCst), 22);
+}
+
+#[tokio::test]
+async fn test_module_serves_requests_from_an_async_backend() {
+    let backend = Arc::new(RemoteStandIn::new(Duration::from_millis(5)));
+    let config = AudioModuleConfig {
+        monitor_interval: None,
+        ..AudioModuleConfig::default()
+    };
+    let module = Arc::new(AudioModule::with_config(backend.clone(), config));
+    let server = BaseTestServer::new_with_registry(move |registry| {
+        registry.register("audio", module);
+    })
+    .await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_device_volume",
+            json!({ "device_id": "mock-device-id", "volume": 30.0 }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(backend.inner.get_actual_volume().unwrap(), 30.0);
+
+    let response = server.get("/api/v1/list_sessions").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"][0]["sessions"][0]["id"], MOCK_GROUP_ID);
+}
+
+#[tokio::test]
+async fn test_blocking_adapter_forwards_calls_to_the_sync_backend() {
+    let audio_system = Arc::new(MockAudioSystem::default());
+    let adapter = BlockingAudioAdapter::new(audio_system.clone(), BackendExecutorConfig::default());
+
+    adapter
+        .set_device_volume("mock-device-id", 20.0)
+        .await
+        .unwrap();
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 20.0);
+    assert_eq!(adapter.get_actual_volume().await.unwrap(), 20.0);
+
+    let group_id = GroupId::new(MOCK_GROUP_ID.to_string());
+    assert_eq!(
+        adapter
+            .get_group_channel_volumes(&group_id, "mock-device-id")
+            .await
+            .unwrap(),
+        vec![80.0, 40.0]
+    );
+
+    // Os erros do backend chegam intactos a quem aguarda
+    let error = adapter
+        .set_group_channel_volumes(&group_id, "mock-device-id", &[30.0])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        AudioError::ChannelCountMismatch {
+            expected: 2,
+            actual: 1
+        }
+    ));
+
+    let error = adapter
+        .set_session_mute("mock-device-id", "missing", true)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, AudioError::SessionNotFound { .. }));
+}
@@ -1,6 +1,7 @@
 use axum::http::StatusCode;
 use serde_json::{json, Value};
 
+use super::mocks::MOCK_ICON_PNG;
 use super::test_server::TestServer;
 
 async fn json_body(response: reqwest::Response) -> Value {
@@ -26,21 +27,59 @@ async fn test_http_get_volume() {
 async fn test_http_list_device() {
     let server = TestServer::new().await;
 
-    let response = server.get("/api/v1/list_device").await;
+    let response = server.get("/api/v1/list_devices").await;
     assert_eq!(response.status(), StatusCode::OK);
 
     let json = json_body(response).await;
-    assert_eq!(
-        json["data"]
-            .as_array()
-            .expect("data should be an array")
-            .len(),
-        0
-    );
-    assert_eq!(json["headers"]["count"], 0);
+    let devices = json["data"].as_array().expect("data should be an array");
+
+    assert_eq!(devices.len(), 1);
+    assert_eq!(devices[0]["id"], "mock-device-id");
+    assert_eq!(devices[0]["state"], "active");
+    assert_eq!(devices[0]["form_factor"], "speakers");
+    assert_eq!(devices[0]["is_default"], true);
+    assert_eq!(devices[0]["format"]["channels"], 2);
+    assert_eq!(devices[0]["volume_range"]["step_count"], 100);
+    assert_eq!(json["headers"]["count"], 1);
     assert!(json["headers"]["timestamp"].is_number());
 }
 
+#[tokio::test]
+async fn test_http_list_device_including_inactive() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .get("/api/v1/list_devices?include_inactive=true")
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json = json_body(response).await;
+    let devices = json["data"].as_array().expect("data should be an array");
+
+    assert_eq!(devices.len(), 2);
+    assert_eq!(devices[1]["state"], "unplugged");
+    assert_eq!(devices[1]["form_factor"], "headphones");
+    assert!(devices[1].get("format").is_none());
+    assert_eq!(json["headers"]["count"], 2);
+}
+
+#[tokio::test]
+async fn test_http_list_device_invalid_query() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .get("/api/v1/list_devices?include_inactive=maybe")
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let json = json_body(response).await;
+    assert_eq!(json["code"], 400);
+    assert!(json["message"]
+        .as_str()
+        .expect("error message should be string")
+        .contains("Invalid query parameters"));
+}
+
 #[tokio::test]
 async fn test_http_list_session_with_valid_device() {
     let server = TestServer::new().await;
@@ -53,10 +92,89 @@ async fn test_http_list_session_with_valid_device() {
 
     assert_eq!(sessions.len(), 1);
     assert_eq!(sessions[0]["id"], "11111111-1111-1111-1111-111111111111");
+    assert_eq!(sessions[0]["display_name"], "Mock Session Player");
+    assert_eq!(sessions[0]["process_name"], "mock-session");
+    assert_eq!(sessions[0]["pid"], 1234);
+    assert_eq!(
+        sessions[0]["executable_path"],
+        r"C:\Program Files\Mock\mock-session.exe"
+    );
+    assert_eq!(sessions[0]["is_system_sounds"], false);
+    assert!(sessions[0].get("sessions").is_none());
+    assert_eq!(sessions[0]["volume_level"], 55.0);
+    assert_eq!(sessions[0]["volume_min"], 50.0);
+    assert_eq!(sessions[0]["volume_max"], 60.0);
+    assert_eq!(sessions[0]["member_volumes"], json!([60.0, 50.0]));
+    assert_eq!(sessions[0]["mute_state"], "none");
+    assert_eq!(sessions[0]["state"], "active");
     assert_eq!(json["headers"]["count"], 1);
     assert!(json["headers"]["timestamp"].is_number());
 }
 
+#[tokio::test]
+async fn test_http_list_session_expanded() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .get("/api/v1/list_session/mock-device-id?expand=true")
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json = json_body(response).await;
+    let members = json["data"][0]["sessions"]
+        .as_array()
+        .expect("expanded group should list its sessions");
+
+    assert_eq!(members.len(), 2);
+    assert_eq!(members[0]["session_id"], "mock-session-1");
+    assert_eq!(members[0]["pid"], 1234);
+    assert_eq!(members[0]["process_name"], "mock-session");
+    assert_eq!(members[0]["volume_level"], 60.0);
+    assert_eq!(members[0]["muted"], false);
+    assert_eq!(members[1]["state"], "inactive");
+}
+
+#[tokio::test]
+async fn test_http_set_session_volume() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_session_volume",
+            json!({
+                "device_id": "mock-device-id",
+                "session_id": "mock-session-2",
+                "volume": 30.0
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json = json_body(response).await;
+    assert_eq!(json["data"], "Session volume set successfully");
+}
+
+#[tokio::test]
+async fn test_http_set_session_mute_unknown_session() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_session_mute",
+            json!({
+                "device_id": "mock-device-id",
+                "session_id": "missing-session",
+                "muted": true
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let json = json_body(response).await;
+    assert_eq!(json["code"], 404);
+    assert_eq!(json["message"], "Session not found");
+}
+
 #[tokio::test]
 async fn test_http_set_group_volume_with_valid_request() {
     let server = TestServer::new().await;
@@ -177,3 +295,263 @@ async fn test_http_not_found() {
     assert_eq!(json["code"], 404);
     assert_eq!(json["message"], "Resource not found");
 }
+
+#[tokio::test]
+async fn test_http_get_device_channels() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .get("/api/v1/get_device_channels/mock-device-id")
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json = json_body(response).await;
+    assert_eq!(json["data"]["channels"], json!([55.0, 55.0]));
+    assert_eq!(json["data"]["balance"], 0.0);
+}
+
+#[tokio::test]
+async fn test_http_set_device_channels_with_balance() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_device_channels",
+            json!({
+                "device_id": "mock-device-id",
+                "balance": 0.5
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json = json_body(response).await;
+    assert_eq!(json["data"]["channels"], json!([27.5, 55.0]));
+    assert_eq!(json["data"]["balance"], 0.5);
+}
+
+#[tokio::test]
+async fn test_http_set_device_channels_channel_count_mismatch() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_device_channels",
+            json!({
+                "device_id": "mock-device-id",
+                "channels": [10.0, 20.0, 30.0]
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let json = json_body(response).await;
+    assert_eq!(json["message"], "Channel count mismatch");
+    assert_eq!(json["details"], "Expected 2 channel levels, got 3");
+}
+
+#[tokio::test]
+async fn test_http_set_device_channels_requires_single_adjustment() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_device_channels",
+            json!({
+                "device_id": "mock-device-id",
+                "channels": [10.0, 20.0],
+                "balance": 0.2
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let json = json_body(response).await;
+    assert_eq!(
+        json["message"],
+        "Exactly one of 'channels' or 'balance' must be provided"
+    );
+}
+
+#[tokio::test]
+async fn test_http_set_device_channels_invalid_channel_volume() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_device_channels",
+            json!({
+                "device_id": "mock-device-id",
+                "channels": [10.0, 120.0]
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let json = json_body(response).await;
+    assert_eq!(json["message"], "Volume must be between 0.0 and 100.0");
+}
+
+#[tokio::test]
+async fn test_http_get_group_channels() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .get("/api/v1/get_group_channels/mock-device-id/11111111-1111-1111-1111-111111111111")
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json = json_body(response).await;
+    assert_eq!(json["data"]["channels"], json!([80.0, 40.0]));
+    assert_eq!(json["data"]["balance"], -0.5);
+}
+
+#[tokio::test]
+async fn test_http_set_group_channels_invalid_balance() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_group_channels",
+            json!({
+                "device_id": "mock-device-id",
+                "group_id": "11111111-1111-1111-1111-111111111111",
+                "balance": 1.5
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let json = json_body(response).await;
+    assert_eq!(json["message"], "Balance must be between -1.0 and 1.0");
+}
+
+async fn first_group(server: &TestServer) -> Value {
+    let response = server.get("/api/v1/list_session/mock-device-id").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    json_body(response).await["data"][0].clone()
+}
+
+#[tokio::test]
+async fn test_http_group_reports_partial_and_full_mute() {
+    let server = TestServer::new().await;
+
+    for (session_id, expected_state, expected_muted) in [
+        ("mock-session-1", "partial", false),
+        ("mock-session-2", "all", true),
+    ] {
+        let response = server
+            .post_json(
+                "/api/v1/set_session_mute",
+                json!({
+                    "device_id": "mock-device-id",
+                    "session_id": session_id,
+                    "muted": true
+                }),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let group = first_group(&server).await;
+        assert_eq!(group["mute_state"], expected_state);
+        assert_eq!(group["muted"], expected_muted);
+    }
+}
+
+#[tokio::test]
+async fn test_http_set_group_volume_uniform_flattens_members() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_group_volume",
+            json!({
+                "device_id": "mock-device-id",
+                "group_id": "11111111-1111-1111-1111-111111111111",
+                "volume": 40.0
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let group = first_group(&server).await;
+    assert_eq!(group["member_volumes"], json!([40.0, 40.0]));
+    assert_eq!(group["volume_level"], 40.0);
+}
+
+#[tokio::test]
+async fn test_http_set_group_volume_proportional_preserves_differences() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_group_volume",
+            json!({
+                "device_id": "mock-device-id",
+                "group_id": "11111111-1111-1111-1111-111111111111",
+                "volume": 82.5,
+                "mode": "proportional"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let group = first_group(&server).await;
+    assert_eq!(group["member_volumes"], json!([90.0, 75.0]));
+    assert_eq!(group["volume_level"], 82.5);
+}
+
+#[tokio::test]
+async fn test_http_set_group_volume_invalid_mode() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_group_volume",
+            json!({
+                "device_id": "mock-device-id",
+                "group_id": "11111111-1111-1111-1111-111111111111",
+                "volume": 50.0,
+                "mode": "loudest"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_http_group_icon_served_as_png_with_cache_headers() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .get("/api/v1/sessions/11111111-1111-1111-1111-111111111111/icon")
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/png");
+    assert_eq!(response.headers()["cache-control"], "public, max-age=86400");
+    assert!(response.headers().contains_key("etag"));
+
+    let body = response.bytes().await.unwrap();
+    assert_eq!(&body[..], MOCK_ICON_PNG);
+}
+
+#[tokio::test]
+async fn test_http_group_icon_not_modified_when_etag_matches() {
+    let server = TestServer::new().await;
+    let path = "/api/v1/sessions/11111111-1111-1111-1111-111111111111/icon";
+
+    let response = server.get(path).await;
+    let etag = response.headers()["etag"].to_str().unwrap().to_string();
+
+    let response = server.get_with_header(path, "if-none-match", &etag).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert!(response.bytes().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_http_group_icon_unknown_group() {
+    let server = TestServer::new().await;
+
+    let response = server.get("/api/v1/sessions/unknown-group/icon").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+use super::test_server::TestServer;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn connect(server: &TestServer) -> Socket {
+    let (socket, _) = connect_async(server.ws_url("/api/v1/meters"))
+        .await
+        .expect("meter socket should connect");
+    socket
+}
+
+async fn send_json(socket: &mut Socket, value: Value) {
+    socket
+        .send(Message::text(value.to_string()))
+        .await
+        .expect("command should be sent");
+}
+
+async fn next_json(socket: &mut Socket) -> Value {
+    let message = tokio::time::timeout(Duration::from_secs(2), socket.next())
+        .await
+        .expect("frame should arrive in time")
+        .expect("socket should stay open")
+        .expect("frame should be valid");
+    serde_json::from_str(message.to_text().expect("frame should be text"))
+        .expect("frame should be valid JSON")
+}
+
+#[tokio::test]
+async fn test_ws_meters_streams_frames_after_subscribe() {
+    let server = TestServer::new().await;
+    let mut socket = connect(&server).await;
+
+    send_json(
+        &mut socket,
+        json!({ "action": "subscribe", "device_id": "mock-device-id", "rate_hz": 50 }),
+    )
+    .await;
+
+    let frame = next_json(&mut socket).await;
+    assert!(frame["t"].is_number());
+    assert_eq!(frame["d"], 0.5);
+    assert_eq!(
+        frame["g"],
+        json!([["11111111-1111-1111-1111-111111111111", 0.25]])
+    );
+}
+
+#[tokio::test]
+async fn test_ws_meters_idle_until_subscribed() {
+    let server = TestServer::new().await;
+    let mut socket = connect(&server).await;
+
+    let idle = tokio::time::timeout(Duration::from_millis(150), socket.next()).await;
+    assert!(idle.is_err(), "no frames should be sent before subscribe");
+
+    send_json(
+        &mut socket,
+        json!({
+            "action": "subscribe",
+            "device_id": "mock-device-id",
+            "include_groups": false
+        }),
+    )
+    .await;
+
+    let frame = next_json(&mut socket).await;
+    assert_eq!(frame["d"], 0.5);
+    assert!(frame.get("g").is_none());
+}
+
+#[tokio::test]
+async fn test_ws_meters_stops_after_unsubscribe() {
+    let server = TestServer::new().await;
+    let mut socket = connect(&server).await;
+
+    send_json(
+        &mut socket,
+        json!({ "action": "subscribe", "device_id": "mock-device-id", "rate_hz": 60 }),
+    )
+    .await;
+    next_json(&mut socket).await;
+
+    send_json(&mut socket, json!({ "action": "unsubscribe" })).await;
+
+    // Descarta quadros que já estavam em trânsito antes do unsubscribe
+    while tokio::time::timeout(Duration::from_millis(100), socket.next())
+        .await
+        .is_ok()
+    {}
+
+    let idle = tokio::time::timeout(Duration::from_millis(150), socket.next()).await;
+    assert!(idle.is_err(), "no frames should be sent after unsubscribe");
+}
+
+#[tokio::test]
+async fn test_ws_meters_invalid_command() {
+    let server = TestServer::new().await;
+    let mut socket = connect(&server).await;
+
+    send_json(&mut socket, json!({ "action": "dance" })).await;
+
+    let frame = next_json(&mut socket).await;
+    assert!(frame["error"]
+        .as_str()
+        .expect("error should be string")
+        .contains("Invalid meter command"));
+}
+
+#[tokio::test]
+async fn test_ws_meters_unknown_device() {
+    let server = TestServer::new().await;
+    let mut socket = connect(&server).await;
+
+    send_json(
+        &mut socket,
+        json!({ "action": "subscribe", "device_id": "missing-device" }),
+    )
+    .await;
+
+    let frame = next_json(&mut socket).await;
+    assert_eq!(frame["error"], "Device not found: missing-device");
+}
@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use chrono::TimeDelta;
+use serde_json::{json, Value};
+
+use super::test_server::TestServer;
+use crate::modules::core::audit::{AuditEntry, AuditLog, AuditQuery, AuditRotation};
+use crate::modules::core::tests_support::ManualClock;
+
+const CLIENT_HEADER: &str = "X-Client-Id";
+
+async fn audit(server: &TestServer, query: &str) -> Value {
+    let response = server.get(&format!("/api/v1/audit{query}")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_mutating_requests_are_audited_with_client_and_result() {
+    let server = TestServer::new().await;
+
+    server
+        .post_json_with_header(
+            "/api/v1/set_session_mute",
+            json!({ "device_id": "mock-device-id", "session_id": "mock-session-1", "muted": true }),
+            CLIENT_HEADER,
+            "meeting-room-panel",
+        )
+        .await;
+    server
+        .post_json(
+            "/api/v1/set_session_mute",
+            json!({ "device_id": "mock-device-id", "session_id": "missing", "muted": true }),
+        )
+        .await;
+    server.get("/api/v1/get_volume").await;
+
+    let json = audit(&server, "").await;
+    assert_eq!(json["headers"]["count"], 2);
+    assert_eq!(json["data"][0]["client"], "anonymous");
+    assert_eq!(json["data"][0]["status"], 404);
+    assert_eq!(
+        json["data"][1],
+        json!({
+            "timestamp": "2025-01-17T12:00:00",
+            "client": "meeting-room-panel",
+            "remote_addr": "127.0.0.1",
+            "method": "POST",
+            "route": "/api/v1/set_session_mute",
+            "params": { "device_id": "mock-device-id", "session_id": "mock-session-1", "muted": true },
+            "status": 200
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_audit_query_filters_by_time_and_client() {
+    let server = TestServer::new().await;
+    let set_volume = |client: &'static str, volume: f64| {
+        server.post_json_with_header(
+            "/api/v1/set_device_volume",
+            json!({ "device_id": "mock-device-id", "volume": volume }),
+            CLIENT_HEADER,
+            client,
+        )
+    };
+
+    set_volume("phone", 10.0).await;
+    server.clock().advance(TimeDelta::minutes(10));
+    set_volume("phone", 20.0).await;
+    set_volume("laptop", 30.0).await;
+    server.clock().advance(TimeDelta::minutes(10));
+    set_volume("phone", 40.0).await;
+
+    let json = audit(&server, "?client=phone").await;
+    assert_eq!(json["headers"]["count"], 3);
+    assert_eq!(json["data"][0]["params"]["volume"], 40.0);
+
+    let json = audit(
+        &server,
+        "?client=phone&since=2025-01-17T12:05:00&until=2025-01-17T12:15:00",
+    )
+    .await;
+    assert_eq!(json["headers"]["count"], 1);
+    assert_eq!(json["data"][0]["params"]["volume"], 20.0);
+
+    let json = audit(&server, "?limit=2").await;
+    assert_eq!(json["headers"]["count"], 2);
+    assert_eq!(json["data"][1]["client"], "laptop");
+
+    let response = server.get("/api/v1/audit?since=yesterday").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_audit_file_is_appended_and_rotated() {
+    let dir = std::env::temp_dir().join(format!("pc-controller-audit-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let clock = Arc::new(ManualClock::at_noon());
+    let rotation = AuditRotation {
+        max_file_bytes: 400,
+        max_files: 2,
+    };
+    let open = || AuditLog::open(dir.join("audit.log"), rotation, clock.clone());
+
+    let log = open();
+    for index in 0..12 {
+        log.record(AuditEntry {
+            timestamp: log.now(),
+            client: format!("client-{index}"),
+            remote_addr: None,
+            method: "POST".to_string(),
+            route: "/api/v1/set_device_volume".to_string(),
+            params: Some(json!({ "volume": index })),
+            status: 200,
+        })
+        .unwrap();
+    }
+    log.flush();
+
+    let mut files: Vec<String> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    files.sort();
+    assert_eq!(files, vec!["audit.1.log", "audit.2.log", "audit.log"]);
+    for file in &files {
+        assert!(std::fs::metadata(dir.join(file)).unwrap().len() <= 400);
+    }
+
+    // Um log reaberto lê o que já foi gravado, do mais recente ao mais antigo
+    let reopened = open();
+    let entries = reopened.query(&AuditQuery::default());
+    assert_eq!(entries[0].client, "client-11");
+    assert!(entries.len() < 12, "the oldest file must have been removed");
+    let clients: Vec<&str> = entries.iter().map(|entry| entry.client.as_str()).collect();
+    let expected: Vec<String> = (12 - entries.len()..12)
+        .rev()
+        .map(|index| format!("client-{index}"))
+        .collect();
+    assert_eq!(clients, expected);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
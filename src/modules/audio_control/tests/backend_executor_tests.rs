@@ -0,0 +1,122 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use super::test_server::TestServer;
+use crate::modules::audio_control::platform::audio_system_interface::AudioOutputDeviceControl;
+use crate::modules::core::executor::{BackendError, BackendExecutor, BackendExecutorConfig};
+
+/// Estado de thread que conta quantas vezes foi descartado
+struct ThreadState(Arc<AtomicUsize>);
+
+impl Drop for ThreadState {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn config(timeout: Duration) -> BackendExecutorConfig {
+    BackendExecutorConfig {
+        threads: 1,
+        timeout,
+    }
+}
+
+#[tokio::test]
+async fn test_slow_backend_call_returns_gateway_timeout() {
+    let server = TestServer::with_config(|module_config| {
+        module_config.backend = config(Duration::from_millis(50));
+    })
+    .await;
+    server
+        .audio_system()
+        .set_write_delay(Duration::from_millis(300));
+
+    let response = server
+        .post_json(
+            "/api/v1/set_device_volume",
+            json!({ "device_id": "mock-device-id", "volume": 35.0 }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["code"], 504);
+    assert_eq!(json["message"], "Audio backend timed out");
+
+    // A escrita lenta ocupa a thread do backend; a leitura espera atrás dela
+    let response = server.get("/api/v1/get_volume").await;
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+
+    // A chamada que estourou o tempo não é interrompida e acaba aplicada
+    let started = Instant::now();
+    while server.audio_system().get_actual_volume().unwrap() != 35.0 {
+        assert!(started.elapsed() < Duration::from_secs(2));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let response = server.get("/api/v1/get_volume").await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_calls_share_a_long_lived_backend_thread() {
+    let initialized = Arc::new(AtomicUsize::new(0));
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let executor = {
+        let initialized = Arc::clone(&initialized);
+        let dropped = Arc::clone(&dropped);
+        BackendExecutor::spawn_with_init(
+            "test-backend",
+            Arc::new(AtomicUsize::new(0)),
+            config(Duration::from_secs(1)),
+            move || {
+                initialized.fetch_add(1, Ordering::SeqCst);
+                ThreadState(Arc::clone(&dropped))
+            },
+        )
+    };
+
+    for expected in 1..=5 {
+        let (calls, thread) = executor
+            .call(|backend: &AtomicUsize| {
+                let calls = backend.fetch_add(1, Ordering::SeqCst) + 1;
+                (calls, std::thread::current().name().map(str::to_string))
+            })
+            .await
+            .unwrap();
+        assert_eq!(calls, expected);
+        assert_eq!(thread.as_deref(), Some("test-backend-0"));
+    }
+    assert_eq!(initialized.load(Ordering::SeqCst), 1);
+    assert_eq!(dropped.load(Ordering::SeqCst), 0);
+
+    // Descartar o executor encerra a thread e libera o seu estado
+    drop(executor);
+    let started = Instant::now();
+    while dropped.load(Ordering::SeqCst) == 0 {
+        assert!(started.elapsed() < Duration::from_secs(2));
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_panicking_call_keeps_the_backend_thread_alive() {
+    let executor = BackendExecutor::spawn(
+        "test-backend",
+        Arc::new(AtomicUsize::new(0)),
+        config(Duration::from_secs(1)),
+    );
+
+    let result = executor
+        .call(|_: &AtomicUsize| -> usize { panic!("backend failure") })
+        .await;
+    assert!(matches!(result, Err(BackendError::Unavailable)));
+
+    let calls = executor
+        .call(|backend: &AtomicUsize| backend.fetch_add(1, Ordering::SeqCst) + 1)
+        .await
+        .unwrap();
+    assert_eq!(calls, 1);
+}
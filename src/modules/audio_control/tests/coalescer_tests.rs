@@ -0,0 +1,117 @@
+use std::time::{Duration, Instant};
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use super::mocks::MOCK_GROUP_ID;
+use super::test_server::TestServer;
+use crate::modules::audio_control::platform::audio_system_interface::{
+    AudioOutputDeviceControl, AudioSessionControl,
+};
+
+const SLOW_WRITE: Duration = Duration::from_millis(100);
+
+async fn set_group_volume(server: &TestServer, volume: f64) -> (Duration, Value) {
+    let started = Instant::now();
+    let response = server
+        .post_json(
+            "/api/v1/set_group_volume",
+            json!({ "device_id": "mock-device-id", "group_id": MOCK_GROUP_ID, "volume": volume }),
+        )
+        .await;
+    let elapsed = started.elapsed();
+    assert_eq!(response.status(), StatusCode::OK);
+    (elapsed, response.json().await.unwrap())
+}
+
+fn member_volumes(server: &TestServer) -> Vec<f32> {
+    server
+        .audio_system()
+        .get_sessions_for_device("mock-device-id", false)
+        .unwrap()
+        .remove(0)
+        .member_volumes
+}
+
+/// Espera o volume final chegar ao backend e retorna quanto demorou
+async fn wait_for_volumes(server: &TestServer, expected: Vec<f32>) -> Duration {
+    let started = Instant::now();
+    while member_volumes(server) != expected {
+        assert!(
+            started.elapsed() < Duration::from_secs(2),
+            "the latest value was never applied"
+        );
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    started.elapsed()
+}
+
+#[tokio::test]
+async fn test_slider_burst_is_acknowledged_immediately_and_collapsed() {
+    let server = TestServer::with_volume_write_interval(Duration::from_millis(50)).await;
+    server.audio_system().set_write_delay(SLOW_WRITE);
+
+    let (elapsed, json) = set_group_volume(&server, 1.0).await;
+    assert!(elapsed >= SLOW_WRITE, "the first write is applied in place");
+    assert_eq!(json["data"], "Group volume set successfully");
+
+    let mut slowest = Duration::ZERO;
+    for volume in 2..=30 {
+        let (elapsed, json) = set_group_volume(&server, f64::from(volume)).await;
+        assert_eq!(json["data"], "Volume update coalesced");
+        slowest = slowest.max(elapsed);
+    }
+    assert!(
+        slowest < SLOW_WRITE / 2,
+        "coalesced requests took {slowest:?}"
+    );
+
+    wait_for_volumes(&server, vec![30.0, 30.0]).await;
+    let writes = server.audio_system().group_volume_writes();
+    assert!(writes <= 5, "30 requests caused {writes} backend writes");
+}
+
+#[tokio::test]
+async fn test_writes_to_the_same_target_are_rate_limited() {
+    let interval = Duration::from_millis(200);
+    let server = TestServer::with_volume_write_interval(interval).await;
+
+    let (_, json) = set_group_volume(&server, 10.0).await;
+    assert_eq!(json["data"], "Group volume set successfully");
+    let first_write = Instant::now();
+    for volume in [20.0, 30.0, 40.0] {
+        let (_, json) = set_group_volume(&server, volume).await;
+        assert_eq!(json["data"], "Volume update coalesced");
+    }
+    assert_eq!(member_volumes(&server), vec![10.0, 10.0]);
+
+    wait_for_volumes(&server, vec![40.0, 40.0]).await;
+    assert!(first_write.elapsed() >= interval - Duration::from_millis(20));
+    assert_eq!(server.audio_system().group_volume_writes(), 2);
+}
+
+#[tokio::test]
+async fn test_targets_are_independent_and_errors_still_surface() {
+    let server = TestServer::with_volume_write_interval(Duration::from_millis(50)).await;
+    server.audio_system().set_write_delay(SLOW_WRITE);
+
+    let (group, device) = tokio::join!(
+        set_group_volume(&server, 20.0),
+        server.post_json(
+            "/api/v1/set_device_volume",
+            json!({ "device_id": "mock-device-id", "volume": 35.0 }),
+        )
+    );
+    assert_eq!(group.1["data"], "Group volume set successfully");
+    let device: Value = device.json().await.unwrap();
+    assert_eq!(device["data"], 35.0);
+    assert_eq!(server.audio_system().get_actual_volume().unwrap(), 35.0);
+
+    let response = server
+        .post_json(
+            "/api/v1/set_group_volume",
+            json!({ "device_id": "mock-device-id", "group_id": "unknown-group", "volume": 10.0 }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
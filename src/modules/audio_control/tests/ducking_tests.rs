@@ -0,0 +1,197 @@
+use axum::http::StatusCode;
+use chrono::TimeDelta;
+use serde_json::{json, Value};
+
+use super::test_server::TestServer;
+use crate::modules::audio_control::models::{AppInfo, SessionMember, SessionState};
+
+const CALL_GROUP_ID: &str = "22222222-2222-2222-2222-222222222222";
+
+fn start_call(server: &TestServer) {
+    server.audio_system().add_group(
+        CALL_GROUP_ID,
+        AppInfo {
+            display_name: "Discord".to_string(),
+            process_name: "Discord".to_string(),
+            pid: 4321,
+            executable_path: None,
+            is_system_sounds: false,
+        },
+        vec![SessionMember {
+            session_id: "discord-session".to_string(),
+            pid: 4321,
+            process_name: "Discord".to_string(),
+            volume_level: 100.0,
+            muted: false,
+            state: SessionState::Active,
+        }],
+    );
+}
+
+/// Faz o monitor ler as sessões e repassa os eventos ao ducking
+async fn poll_sessions(server: &TestServer) {
+    let module = server.audio_module();
+    for event in module.session_monitor().poll().await.unwrap() {
+        module.ducking().observe(&event);
+    }
+}
+
+async fn player_volumes(server: &TestServer) -> Vec<Value> {
+    let json: Value = server
+        .get("/api/v1/list_session/mock-device-id?expand=true")
+        .await
+        .json()
+        .await
+        .unwrap();
+    json["data"][0]["sessions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| member["volume_level"].clone())
+        .collect()
+}
+
+async fn enable_ducking(server: &TestServer, config: Value) {
+    let response = server.post_json("/api/v1/set_ducking", config).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_active_call_ducks_other_groups_and_restores_them() {
+    let server = TestServer::new().await;
+    enable_ducking(
+        &server,
+        json!({ "enabled": true, "reduction": 50.0, "ramp_down_ms": 1000, "ramp_up_ms": 2000 }),
+    )
+    .await;
+
+    let ducking = server.audio_module().ducking();
+    let clock = server.clock();
+    poll_sessions(&server).await;
+    assert!(!ducking.tick().await.unwrap());
+
+    start_call(&server);
+    poll_sessions(&server).await;
+    assert!(ducking.tick().await.unwrap());
+    clock.advance(TimeDelta::milliseconds(500));
+    ducking.tick().await.unwrap();
+    assert_eq!(
+        player_volumes(&server).await,
+        vec![json!(45.0), json!(37.5)]
+    );
+
+    clock.advance(TimeDelta::milliseconds(500));
+    ducking.tick().await.unwrap();
+    assert_eq!(
+        player_volumes(&server).await,
+        vec![json!(30.0), json!(25.0)]
+    );
+
+    let json: Value = server.get("/api/v1/ducking").await.json().await.unwrap();
+    assert_eq!(json["data"]["active"], true);
+    assert_eq!(json["data"]["ducked_groups"], 1);
+
+    server.audio_system().remove_group(CALL_GROUP_ID);
+    poll_sessions(&server).await;
+    assert!(!ducking.tick().await.unwrap());
+    clock.advance(TimeDelta::milliseconds(1000));
+    ducking.tick().await.unwrap();
+    assert_eq!(
+        player_volumes(&server).await,
+        vec![json!(45.0), json!(37.5)]
+    );
+
+    clock.advance(TimeDelta::milliseconds(1000));
+    ducking.tick().await.unwrap();
+    assert_eq!(
+        player_volumes(&server).await,
+        vec![json!(60.0), json!(50.0)]
+    );
+    assert_eq!(ducking.status().ducked_groups, 0);
+}
+
+#[tokio::test]
+async fn test_excluded_apps_are_not_ducked() {
+    let server = TestServer::new().await;
+    enable_ducking(
+        &server,
+        json!({ "enabled": true, "exclusions": ["Mock-Session.exe"], "ramp_down_ms": 0 }),
+    )
+    .await;
+
+    let json: Value = server.get("/api/v1/ducking").await.json().await.unwrap();
+    assert_eq!(json["data"]["exclusions"], json!(["mock-session"]));
+
+    poll_sessions(&server).await;
+    start_call(&server);
+    poll_sessions(&server).await;
+    assert!(server.audio_module().ducking().tick().await.unwrap());
+    assert_eq!(
+        player_volumes(&server).await,
+        vec![json!(60.0), json!(50.0)]
+    );
+}
+
+#[tokio::test]
+async fn test_disabled_or_unconfigured_ducking_does_nothing() {
+    let server = TestServer::new().await;
+    start_call(&server);
+    let ducking = server.audio_module().ducking();
+    ducking.load_sessions().await.unwrap();
+
+    // Desligado por padrão
+    assert!(!ducking.tick().await.unwrap());
+
+    enable_ducking(
+        &server,
+        json!({ "enabled": true, "communication_apps": ["zoom"] }),
+    )
+    .await;
+    assert!(!ducking.tick().await.unwrap());
+    assert_eq!(
+        player_volumes(&server).await,
+        vec![json!(60.0), json!(50.0)]
+    );
+}
+
+#[tokio::test]
+async fn test_call_that_goes_quiet_restores_groups() {
+    let server = TestServer::new().await;
+    start_call(&server);
+    enable_ducking(
+        &server,
+        json!({ "enabled": true, "ramp_down_ms": 0, "ramp_up_ms": 0 }),
+    )
+    .await;
+
+    // Chamada aberta antes do servidor: o monitor não publica a primeira leitura
+    let ducking = server.audio_module().ducking();
+    poll_sessions(&server).await;
+    ducking.load_sessions().await.unwrap();
+    assert!(ducking.tick().await.unwrap());
+    assert_eq!(
+        player_volumes(&server).await,
+        vec![json!(30.0), json!(25.0)]
+    );
+
+    server
+        .audio_system()
+        .set_group_state(CALL_GROUP_ID, SessionState::Inactive);
+    poll_sessions(&server).await;
+    assert!(!ducking.tick().await.unwrap());
+    assert_eq!(
+        player_volumes(&server).await,
+        vec![json!(60.0), json!(50.0)]
+    );
+}
+
+#[tokio::test]
+async fn test_set_ducking_validates_reduction() {
+    let server = TestServer::new().await;
+    let response = server
+        .post_json("/api/v1/set_ducking", json!({ "reduction": 120.0 }))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "reduction must be between 0.0 and 100.0");
+}
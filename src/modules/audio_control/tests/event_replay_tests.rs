@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+use super::test_server::{event_body, TestServer};
+use crate::modules::core::broadcasting::{Broadcaster, Replay};
+use crate::modules::core::ServerEvent;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn connect_events(server: &TestServer, query: &str) -> Socket {
+    let (socket, _) = connect_async(server.ws_url(&format!("/api/v1/events{query}")))
+        .await
+        .expect("event socket should connect");
+    // Dá tempo para o handler inscrever o cliente no broadcaster
+    tokio::time::sleep(Duration::from_millis(25)).await;
+    socket
+}
+
+async fn next_event(socket: &mut Socket) -> Value {
+    let message = tokio::time::timeout(Duration::from_secs(2), socket.next())
+        .await
+        .expect("event should arrive in time")
+        .expect("socket should stay open")
+        .expect("event should be valid");
+    serde_json::from_str(message.to_text().expect("event should be text"))
+        .expect("event should be valid JSON")
+}
+
+/// Liga ou desliga o modo foco; cada troca publica um evento
+async fn toggle_focus_mode(server: &TestServer, active: bool) {
+    let path = if active {
+        "/api/v1/start_focus_mode"
+    } else {
+        "/api/v1/stop_focus_mode"
+    };
+    let response = server.post_json(path, json!({})).await;
+    assert_eq!(response.status(), 200);
+}
+
+fn focus_event(active: bool) -> Value {
+    json!({ "event_type": "focus_mode_changed", "active": active })
+}
+
+#[tokio::test]
+async fn test_reconnecting_client_receives_missed_events_in_order() {
+    let server = TestServer::new().await;
+    let mut events = connect_events(&server, "").await;
+
+    toggle_focus_mode(&server, true).await;
+    let first = next_event(&mut events).await;
+    assert_eq!(first["id"], 1);
+    assert!(first["timestamp"].as_u64().unwrap() > 0);
+    assert_eq!(event_body(first), focus_event(true));
+    drop(events);
+
+    // Publicados enquanto o cliente estava desconectado
+    toggle_focus_mode(&server, false).await;
+    toggle_focus_mode(&server, true).await;
+
+    let mut events = connect_events(&server, "?last_event_id=1").await;
+    toggle_focus_mode(&server, false).await;
+
+    let mut received = Vec::new();
+    for _ in 0..3 {
+        received.push(next_event(&mut events).await);
+    }
+    let ids: Vec<_> = received.iter().map(|event| event["id"].clone()).collect();
+    assert_eq!(ids, vec![json!(2), json!(3), json!(4)]);
+    assert_eq!(
+        received.into_iter().map(event_body).collect::<Vec<_>>(),
+        vec![focus_event(false), focus_event(true), focus_event(false)]
+    );
+
+    // Quem já está em dia não recebe nada repetido
+    let mut events = connect_events(&server, "?last_event_id=4").await;
+    toggle_focus_mode(&server, true).await;
+    assert_eq!(next_event(&mut events).await["id"], 5);
+}
+
+#[tokio::test]
+async fn test_reconnecting_client_is_told_to_resync_when_events_are_gone() {
+    let server = TestServer::with_config(|config| config.event_replay = 2).await;
+
+    for active in [true, false, true] {
+        toggle_focus_mode(&server, active).await;
+    }
+
+    // O evento 1 já saiu do buffer
+    let mut events = connect_events(&server, "?last_event_id=0").await;
+    assert_eq!(
+        next_event(&mut events).await,
+        json!({ "event_type": "resync", "last_event_id": 3 })
+    );
+    toggle_focus_mode(&server, false).await;
+    assert_eq!(next_event(&mut events).await["id"], 4);
+
+    // Ids de antes de o servidor reiniciar não são conhecidos
+    let mut events = connect_events(&server, "?last_event_id=900").await;
+    assert_eq!(
+        next_event(&mut events).await,
+        json!({ "event_type": "resync", "last_event_id": 4 })
+    );
+
+    // Ainda guardado: repete só o que faltou
+    let mut events = connect_events(&server, "?last_event_id=2").await;
+    assert_eq!(next_event(&mut events).await["id"], 3);
+    assert_eq!(next_event(&mut events).await["id"], 4);
+}
+
+#[test]
+fn test_transient_events_are_delivered_but_not_kept_for_replay() {
+    let broadcaster = Broadcaster::with_replay(16, 2);
+    let mut events = broadcaster.subscribe();
+    assert_eq!(broadcaster.receiver_count(), 1);
+
+    for active in [true, false] {
+        broadcaster
+            .broadcast(ServerEvent::FocusModeChanged {
+                active,
+                remaining_secs: None,
+            })
+            .unwrap();
+    }
+    for remaining_secs in (0..10).rev() {
+        broadcaster
+            .broadcast(ServerEvent::SleepTimerUpdated {
+                remaining_secs,
+                fading: true,
+            })
+            .unwrap();
+    }
+
+    // Os avisos do timer não empurram o modo foco para fora do buffer
+    let Replay::Events(missed) = broadcaster.replay_since(0) else {
+        panic!("focus mode events should still be kept");
+    };
+    let ids: Vec<_> = missed.iter().map(|event| event.id).collect();
+    assert_eq!(ids, vec![1, 2]);
+    assert!(matches!(
+        broadcaster.replay_since(2),
+        Replay::Events(missed) if missed.is_empty()
+    ));
+
+    // Quem está inscrito recebe todos, em ordem
+    let ids: Vec<_> = std::iter::from_fn(|| events.try_recv().ok())
+        .map(|event| event.id)
+        .collect();
+    assert_eq!(ids, (1..=12).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_ducking_changes_are_kept_for_replay() {
+    let broadcaster = Broadcaster::with_replay(16, 4);
+    let _events = broadcaster.subscribe();
+
+    for active in [true, false] {
+        broadcaster
+            .broadcast(ServerEvent::DuckingChanged { active })
+            .unwrap();
+    }
+
+    // Quem perdeu o fim do ducking precisa recebê-lo ao reconectar
+    let Replay::Events(missed) = broadcaster.replay_since(1) else {
+        panic!("ducking events should be kept");
+    };
+    assert_eq!(missed.len(), 1);
+    assert!(matches!(
+        missed[0].event,
+        ServerEvent::DuckingChanged { active: false }
+    ));
+}
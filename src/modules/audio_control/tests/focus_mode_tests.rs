@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use chrono::TimeDelta;
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio_tungstenite::connect_async;
+
+use super::test_server::{event_body, TestServer};
+use crate::modules::audio_control::{
+    models::{AppInfo, RuleAction, SessionMember, SessionState},
+    platform::audio_system_interface::{AudioOutputDeviceControl, AudioSessionControl},
+};
+
+const CALL_GROUP_ID: &str = "22222222-2222-2222-2222-222222222222";
+
+fn start_call(server: &TestServer) {
+    server.audio_system().add_group(
+        CALL_GROUP_ID,
+        AppInfo {
+            display_name: "Discord".to_string(),
+            process_name: "Discord".to_string(),
+            pid: 4321,
+            executable_path: None,
+            is_system_sounds: false,
+        },
+        vec![SessionMember {
+            session_id: "discord-session".to_string(),
+            pid: 4321,
+            process_name: "Discord".to_string(),
+            volume_level: 100.0,
+            muted: false,
+            state: SessionState::Active,
+        }],
+    );
+}
+
+/// Estado de mute de cada sessão, na ordem dos grupos
+fn muted_sessions(server: &TestServer) -> Vec<bool> {
+    server
+        .audio_system()
+        .get_sessions_for_device("mock-device-id", true)
+        .unwrap()
+        .into_iter()
+        .flat_map(|group| group.sessions.unwrap_or_default())
+        .map(|member| member.muted)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_focus_mode_mutes_all_but_allowlist_and_restores_exact_state() {
+    let server = TestServer::new().await;
+    server
+        .audio_system()
+        .set_session_mute("mock-device-id", "mock-session-2", true)
+        .unwrap();
+    start_call(&server);
+
+    let response = server
+        .post_json(
+            "/api/v1/start_focus_mode",
+            json!({ "allowlist": ["Discord.exe"] }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"]["active"], true);
+    assert_eq!(json["data"]["allowlist"], json!(["discord"]));
+    assert_eq!(json["data"]["muted_sessions"], 1);
+    assert_eq!(muted_sessions(&server), vec![true, true, false]);
+
+    let response = server.post_json("/api/v1/stop_focus_mode", json!({})).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    // A sessão que já estava muda continua muda
+    assert_eq!(muted_sessions(&server), vec![false, true, false]);
+
+    let json: Value = server.get("/api/v1/focus_mode").await.json().await.unwrap();
+    assert_eq!(json["data"]["active"], false);
+
+    let response = server.post_json("/api/v1/stop_focus_mode", json!({})).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "Focus mode is not active");
+}
+
+#[tokio::test]
+async fn test_focus_mode_suppresses_notifications() {
+    let server = TestServer::new().await;
+    let (mut events, _) = connect_async(server.ws_url("/api/v1/events"))
+        .await
+        .expect("event socket should connect");
+    tokio::time::sleep(Duration::from_millis(25)).await;
+
+    let executor = server.audio_module().executor();
+    let notify = |message: &str| {
+        let action = RuleAction::Notify {
+            title: Some("Reminder".to_string()),
+            message: message.to_string(),
+        };
+        async move { executor.execute(&action, "test", None).await.unwrap() }
+    };
+
+    server
+        .post_json("/api/v1/start_focus_mode", json!({}))
+        .await;
+    notify("hidden").await;
+    server.post_json("/api/v1/stop_focus_mode", json!({})).await;
+    notify("visible").await;
+
+    let mut received = Vec::new();
+    for _ in 0..3 {
+        let message = tokio::time::timeout(Duration::from_secs(2), events.next())
+            .await
+            .expect("event should arrive in time")
+            .unwrap()
+            .unwrap();
+        received.push(event_body(
+            serde_json::from_str(message.to_text().unwrap()).unwrap(),
+        ));
+    }
+    assert_eq!(
+        received,
+        vec![
+            json!({ "event_type": "focus_mode_changed", "active": true }),
+            json!({ "event_type": "focus_mode_changed", "active": false }),
+            json!({ "event_type": "notification", "title": "Reminder", "message": "visible" }),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_focus_mode_timer_caps_master_and_mutes_new_sessions() {
+    let server = TestServer::new().await;
+    let audio_system = server.audio_system();
+
+    let response = server
+        .post_json(
+            "/api/v1/start_focus_mode",
+            json!({ "max_master": 30.0, "duration_secs": 600 }),
+        )
+        .await;
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"]["remaining_secs"], 600);
+    assert_eq!(json["data"]["ends_at"], "2025-01-17T12:10:00");
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 30.0);
+
+    let focus_mode = server.audio_module().focus_mode();
+    audio_system
+        .set_device_volume("mock-device-id", 80.0)
+        .unwrap();
+    start_call(&server);
+    assert!(focus_mode.tick().await.unwrap());
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 30.0);
+    assert_eq!(muted_sessions(&server), vec![true, true, true]);
+
+    server.clock().advance(TimeDelta::minutes(10));
+    assert!(!focus_mode.tick().await.unwrap());
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 55.0);
+    assert_eq!(muted_sessions(&server), vec![false, false, false]);
+    assert!(!focus_mode.status().active);
+}
+
+#[tokio::test]
+async fn test_start_focus_mode_validation() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json("/api/v1/start_focus_mode", json!({ "duration_secs": 0 }))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "duration_secs must be greater than zero");
+
+    let response = server
+        .post_json("/api/v1/start_focus_mode", json!({ "max_master": 150.0 }))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(muted_sessions(&server), vec![false, false]);
+}
@@ -0,0 +1,177 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use super::mocks::MOCK_GROUP_ID;
+use super::test_server::TestServer;
+use crate::modules::audio_control::platform::audio_system_interface::{
+    AudioOutputDeviceControl, AudioSessionControl,
+};
+
+const CLIENT_HEADER: &str = "X-Client-Id";
+
+async fn post_as(server: &TestServer, client: &str, path: &str, body: Value) -> reqwest::Response {
+    server
+        .post_json_with_header(path, body, CLIENT_HEADER, client)
+        .await
+}
+
+fn member_volumes(server: &TestServer) -> Vec<f32> {
+    server
+        .audio_system()
+        .get_sessions_for_device("mock-device-id", false)
+        .unwrap()
+        .remove(0)
+        .member_volumes
+}
+
+#[tokio::test]
+async fn test_slipped_slider_is_undone_and_redone() {
+    let server = TestServer::new().await;
+    let audio_system = server.audio_system();
+
+    post_as(
+        &server,
+        "phone",
+        "/api/v1/set_device_volume",
+        json!({ "device_id": "mock-device-id", "volume": 100.0 }),
+    )
+    .await;
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 100.0);
+
+    let json: Value = server.get("/api/v1/history").await.json().await.unwrap();
+    assert_eq!(json["headers"]["count"], 1);
+    assert_eq!(
+        json["data"][0],
+        json!({
+            "id": 0,
+            "client": "phone",
+            "timestamp": "2025-01-17T12:00:00",
+            "target": { "type": "device_volume", "device_id": "mock-device-id" },
+            "old_value": 55.0,
+            "new_value": 100.0,
+            "undone": false
+        })
+    );
+
+    let response = post_as(&server, "phone", "/api/v1/undo", json!({})).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"]["undone"], true);
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 55.0);
+
+    let response = post_as(&server, "phone", "/api/v1/redo", json!({})).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 100.0);
+}
+
+#[tokio::test]
+async fn test_undo_is_per_client() {
+    let server = TestServer::new().await;
+
+    post_as(
+        &server,
+        "phone",
+        "/api/v1/set_session_volume",
+        json!({ "device_id": "mock-device-id", "session_id": "mock-session-1", "volume": 10.0 }),
+    )
+    .await;
+    post_as(
+        &server,
+        "panel",
+        "/api/v1/set_session_mute",
+        json!({ "device_id": "mock-device-id", "session_id": "mock-session-2", "muted": true }),
+    )
+    .await;
+
+    post_as(&server, "phone", "/api/v1/undo", json!({})).await;
+    assert_eq!(member_volumes(&server), vec![60.0, 50.0]);
+    let sessions = server
+        .audio_system()
+        .get_sessions_for_device("mock-device-id", true)
+        .unwrap()
+        .remove(0)
+        .sessions
+        .unwrap();
+    assert!(sessions[1].muted, "the panel's change must stay in place");
+
+    let response = post_as(&server, "phone", "/api/v1/undo", json!({})).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "Nothing to undo");
+
+    // Sem header o cliente é anônimo e não tem mudanças
+    let response = server.post_json("/api/v1/undo", json!({})).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_group_undo_restores_member_differences() {
+    let server = TestServer::new().await;
+    let set_group = |volume: f64| {
+        post_as(
+            &server,
+            "phone",
+            "/api/v1/set_group_volume",
+            json!({ "device_id": "mock-device-id", "group_id": MOCK_GROUP_ID, "volume": volume }),
+        )
+    };
+
+    set_group(20.0).await;
+    assert_eq!(member_volumes(&server), vec![20.0, 20.0]);
+    post_as(&server, "phone", "/api/v1/undo", json!({})).await;
+    assert_eq!(member_volumes(&server), vec![60.0, 50.0]);
+
+    // Uma mudança nova descarta o que foi desfeito
+    set_group(30.0).await;
+    let response = post_as(&server, "phone", "/api/v1/redo", json!({})).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "Nothing to redo");
+
+    let json: Value = server.get("/api/v1/history").await.json().await.unwrap();
+    assert_eq!(json["headers"]["count"], 1);
+    assert_eq!(json["data"][0]["old_value"][1]["volume"], 50.0);
+}
+
+#[tokio::test]
+async fn test_undo_stops_at_changes_it_cannot_revert() {
+    let server = TestServer::new().await;
+    let audio_system = server.audio_system();
+
+    post_as(
+        &server,
+        "phone",
+        "/api/v1/set_device_volume",
+        json!({ "device_id": "mock-device-id", "volume": 80.0 }),
+    )
+    .await;
+    post_as(
+        &server,
+        "phone",
+        "/api/v1/capture_profile",
+        json!({ "name": "Work" }),
+    )
+    .await;
+    let response = post_as(
+        &server,
+        "phone",
+        "/api/v1/apply_profile",
+        json!({ "name": "Work" }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: Value = server.get("/api/v1/history").await.json().await.unwrap();
+    assert_eq!(
+        json["data"][0]["target"],
+        json!({ "type": "irreversible", "action": "apply_profile" })
+    );
+    assert_eq!(json["data"][0]["old_value"], Value::Null);
+
+    // O volume de antes do perfil não volta escondido atrás dele
+    let response = post_as(&server, "phone", "/api/v1/undo", json!({})).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["details"], "apply_profile cannot be undone");
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 80.0);
+}
@@ -0,0 +1,187 @@
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use chrono::TimeDelta;
+use serde_json::{json, Value};
+
+use super::mocks::headset;
+use super::test_server::{wait_until, TestServer};
+use crate::modules::audio_control::platform::{
+    audio_system_interface::AudioOutputDeviceControl, cached_audio_system::LookupCacheConfig,
+};
+use crate::modules::core::ServerEvent;
+
+async fn cached_server() -> TestServer {
+    TestServer::with_config(|config| {
+        config.lookup_cache = LookupCacheConfig {
+            device_ttl: Duration::from_secs(5),
+            process_ttl: Duration::from_secs(60),
+        };
+    })
+    .await
+}
+
+async fn device_ids(server: &TestServer) -> Vec<String> {
+    let json: Value = server
+        .get("/api/v1/list_devices")
+        .await
+        .json()
+        .await
+        .unwrap();
+    json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|device| device["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+async fn default_device(server: &TestServer) -> Option<String> {
+    let json: Value = server
+        .get("/api/v1/list_devices")
+        .await
+        .json()
+        .await
+        .unwrap();
+    json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|device| device["is_default"] == true)
+        .map(|device| device["id"].as_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn test_process_names_are_resolved_once_per_ttl() {
+    let server = cached_server().await;
+
+    for _ in 0..3 {
+        let response = server.get("/api/v1/list_session/mock-device-id").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let json: Value = response.json().await.unwrap();
+        assert_eq!(json["data"][0]["process_name"], "mock-session");
+    }
+    assert_eq!(server.audio_system().process_lookups(), 1);
+
+    // Volumes continuam vindo do backend a cada leitura
+    let response = server
+        .post_json(
+            "/api/v1/set_session_volume",
+            json!({
+                "device_id": "mock-device-id",
+                "session_id": "mock-session-1",
+                "volume": 20.0
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = server
+        .get("/api/v1/list_session/mock-device-id?expand=true")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json["data"][0]["sessions"][0]["volume_level"], 20.0);
+    assert_eq!(server.audio_system().process_lookups(), 1);
+
+    server.clock().advance(TimeDelta::seconds(61));
+    server.get("/api/v1/list_session/mock-device-id").await;
+    assert_eq!(server.audio_system().process_lookups(), 2);
+
+    let json: Value = server
+        .get("/api/v1/lookup_cache")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let processes = &json["data"]["processes"];
+    assert_eq!(processes["misses"], 2);
+    assert_eq!(processes["entries"], 1);
+    assert!(processes["hits"].as_u64().unwrap() >= 3);
+}
+
+#[tokio::test]
+async fn test_device_list_is_dropped_when_the_monitor_sees_a_new_device() {
+    let server = cached_server().await;
+    let monitor = server.audio_module().session_monitor();
+    monitor.poll().await.unwrap();
+
+    assert_eq!(device_ids(&server).await, vec!["mock-device-id"]);
+    let listings = server.audio_system().device_listings();
+    server.audio_system().add_device(headset());
+
+    // Até o monitor notar, a lista em cache é reaproveitada
+    assert_eq!(device_ids(&server).await, vec!["mock-device-id"]);
+    assert_eq!(server.audio_system().device_listings(), listings);
+
+    monitor.poll().await.unwrap();
+    // A invalidação roda na tarefa em segundo plano
+    wait_until("cached device list was not dropped", || async {
+        device_ids(&server).await.len() == 2
+    })
+    .await;
+    let json: Value = server
+        .get("/api/v1/lookup_cache")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json["data"]["devices"]["entries"], 1);
+}
+
+#[tokio::test]
+async fn test_device_list_is_dropped_when_the_default_device_changes_outside_the_server() {
+    let server = cached_server().await;
+    let monitor = server.audio_module().session_monitor();
+    server.audio_system().add_device(headset());
+    monitor.poll().await.unwrap();
+
+    assert_eq!(
+        default_device(&server).await.as_deref(),
+        Some("mock-device-id")
+    );
+
+    // Mudança feita direto no backend, como pelo painel do Windows
+    server
+        .audio_system()
+        .set_default_device(&headset().id)
+        .unwrap();
+    assert_eq!(
+        default_device(&server).await.as_deref(),
+        Some("mock-device-id")
+    );
+
+    let events = monitor.poll().await.unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        ServerEvent::DefaultDeviceChanged { device_id } if *device_id == headset().id
+    )));
+    wait_until("cached device list kept the old default", || async {
+        default_device(&server).await == Some(headset().id)
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_ended_session_evicts_only_its_own_process() {
+    let server = cached_server().await;
+    let monitor = server.audio_module().session_monitor();
+    monitor.poll().await.unwrap();
+    let lookups = server.audio_system().process_lookups();
+
+    // Outra sessão do mesmo executável, com outro pid, termina
+    server.audio_system().remove_session("mock-session-2");
+    server.audio_system().add_device(headset());
+    let events = monitor.poll().await.unwrap();
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, ServerEvent::SessionEnded { pid: 1235, .. })));
+
+    // O dispositivo novo é invalidado depois do fim da sessão
+    wait_until("cached device list was not dropped", || async {
+        device_ids(&server).await.len() == 2
+    })
+    .await;
+    server.get("/api/v1/list_session/mock-device-id").await;
+    assert_eq!(server.audio_system().process_lookups(), lookups);
+}
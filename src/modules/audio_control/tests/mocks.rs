@@ -1,46 +1,542 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
 use crate::modules::audio_control::{
-    models::{SessionGroup, SessionState},
-    platform::audio_system_interface::{AudioOutputDeviceControl, AudioSessionControl},
-    types::GroupId,
+    errors::AudioError,
+    models::{
+        AppInfo, DeviceFormat, DeviceSound, DeviceState, FormFactor, GroupPeak, GroupVolumeMode,
+        SessionGroup, SessionMember, SessionState, VolumeRange,
+    },
+    platform::audio_system_interface::{
+        AudioMeteringControl, AudioOutputDeviceControl, AudioProcessLookup, AudioSessionControl,
+        MediaPlaybackControl,
+    },
+    types::{AudioResult, GroupId},
 };
 
-#[derive(Default)]
-pub struct MockAudioSystem;
+/// Backend em memória usado pelos testes; sessões mantêm estado entre chamadas
+pub struct MockAudioSystem {
+    members: Mutex<Vec<SessionMember>>,
+    /// Chamadas de roteamento recebidas, como (pid, dispositivo de destino)
+    routed: Mutex<Vec<(u32, String)>>,
+    /// Dispositivos adicionados pelos testes além dos padrões
+    extra_devices: Mutex<Vec<DeviceSound>>,
+    default_device_id: Mutex<String>,
+    /// Volume master por dispositivo; ausente vale `MOCK_DEVICE_VOLUME`
+    device_volumes: Mutex<HashMap<String, f32>>,
+    /// Dispositivos com o volume master mudo
+    muted_devices: Mutex<HashSet<String>>,
+    /// Grupos de outras aplicações adicionados pelos testes; só aparecem
+    /// na listagem
+    extra_groups: Mutex<Vec<(GroupId, AppInfo, Vec<SessionMember>)>>,
+    /// Tempo gasto por cada escrita de volume, simulando um backend lento
+    write_delay: Mutex<Duration>,
+    /// Tempo gasto por cada listagem de dispositivos
+    listing_delay: Mutex<Duration>,
+    /// Escritas de volume de grupo recebidas
+    group_volume_writes: AtomicUsize,
+    /// Listagens de dispositivos atendidas
+    device_listings: AtomicUsize,
+    /// Listagens de sessões atendidas
+    session_listings: AtomicUsize,
+    /// Processos consultados para identificar a aplicação
+    process_lookups: AtomicUsize,
+    /// Teclas de play/pause enviadas
+    media_toggles: AtomicUsize,
+}
+
+impl Default for MockAudioSystem {
+    fn default() -> Self {
+        Self {
+            members: Mutex::new(mock_session_members()),
+            routed: Mutex::new(Vec::new()),
+            extra_devices: Mutex::new(Vec::new()),
+            default_device_id: Mutex::new("mock-device-id".to_string()),
+            device_volumes: Mutex::new(HashMap::new()),
+            muted_devices: Mutex::new(HashSet::new()),
+            extra_groups: Mutex::new(Vec::new()),
+            write_delay: Mutex::new(Duration::ZERO),
+            listing_delay: Mutex::new(Duration::ZERO),
+            group_volume_writes: AtomicUsize::new(0),
+            device_listings: AtomicUsize::new(0),
+            session_listings: AtomicUsize::new(0),
+            process_lookups: AtomicUsize::new(0),
+            media_toggles: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl MockAudioSystem {
+    pub fn add_session(&self, member: SessionMember) {
+        self.members.lock().unwrap().push(member);
+    }
+
+    pub fn remove_session(&self, session_id: &str) {
+        self.members
+            .lock()
+            .unwrap()
+            .retain(|member| member.session_id != session_id);
+    }
+
+    /// Adiciona o grupo de outra aplicação, listado em todos os dispositivos
+    pub fn add_group(&self, group_id: &str, app: AppInfo, members: Vec<SessionMember>) {
+        self.extra_groups
+            .lock()
+            .unwrap()
+            .push((GroupId::new(group_id.to_string()), app, members));
+    }
+
+    pub fn remove_group(&self, group_id: &str) {
+        self.extra_groups
+            .lock()
+            .unwrap()
+            .retain(|(id, _, _)| id.as_ref() != group_id);
+    }
+
+    /// Muda o estado de todas as sessões de um grupo adicionado por `add_group`
+    pub fn set_group_state(&self, group_id: &str, state: SessionState) {
+        for (id, _, members) in self.extra_groups.lock().unwrap().iter_mut() {
+            if id.as_ref() == group_id {
+                for member in members.iter_mut() {
+                    member.state = state;
+                }
+            }
+        }
+    }
+
+    /// Faz cada escrita de volume (master, grupo e sessão) levar `delay`
+    pub fn set_write_delay(&self, delay: Duration) {
+        *self.write_delay.lock().unwrap() = delay;
+    }
+
+    /// Faz cada listagem de dispositivos levar `delay`
+    pub fn set_listing_delay(&self, delay: Duration) {
+        *self.listing_delay.lock().unwrap() = delay;
+    }
+
+    pub fn group_volume_writes(&self) -> usize {
+        self.group_volume_writes.load(Ordering::SeqCst)
+    }
+
+    pub fn device_listings(&self) -> usize {
+        self.device_listings.load(Ordering::SeqCst)
+    }
+
+    pub fn session_listings(&self) -> usize {
+        self.session_listings.load(Ordering::SeqCst)
+    }
+
+    pub fn process_lookups(&self) -> usize {
+        self.process_lookups.load(Ordering::SeqCst)
+    }
+
+    pub fn media_toggles(&self) -> usize {
+        self.media_toggles.load(Ordering::SeqCst)
+    }
+
+    fn simulate_write(&self) {
+        let delay = *self.write_delay.lock().unwrap();
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+    }
+
+    /// Adiciona um dispositivo; as sessões do mock aparecem em todos eles
+    pub fn add_device(&self, device: DeviceSound) {
+        self.extra_devices.lock().unwrap().push(device);
+    }
+
+    fn devices(&self) -> Vec<DeviceSound> {
+        let default_device_id = self.default_device_id.lock().unwrap().clone();
+        let mut devices = mock_devices();
+        devices.extend(self.extra_devices.lock().unwrap().iter().cloned());
+        for device in &mut devices {
+            device.is_default = device.id == default_device_id;
+        }
+        devices
+    }
+
+    fn check_device(&self, device_id: &str) -> AudioResult<()> {
+        if !self.devices().iter().any(|device| device.id == device_id) {
+            return Err(AudioError::DeviceNotFound {
+                device_id: device_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn routed(&self) -> Vec<(u32, String)> {
+        self.routed.lock().unwrap().clone()
+    }
+
+    fn with_session<F>(&self, session_id: &str, update: F) -> AudioResult<()>
+    where
+        F: FnOnce(&mut SessionMember),
+    {
+        let mut members = self.members.lock().unwrap();
+        if let Some(member) = members
+            .iter_mut()
+            .find(|member| member.session_id == session_id)
+        {
+            update(member);
+            return Ok(());
+        }
+
+        let mut extra_groups = self.extra_groups.lock().unwrap();
+        let member = extra_groups
+            .iter_mut()
+            .flat_map(|(_, _, members)| members.iter_mut())
+            .find(|member| member.session_id == session_id)
+            .ok_or_else(|| AudioError::SessionNotFound {
+                session_id: session_id.to_string(),
+            })?;
+        update(member);
+        Ok(())
+    }
+}
+
+pub fn mock_devices() -> Vec<DeviceSound> {
+    vec![
+        DeviceSound {
+            id: "mock-device-id".to_string(),
+            name: "Mock Speakers".to_string(),
+            state: DeviceState::Active,
+            form_factor: FormFactor::Speakers,
+            is_default: true,
+            format: Some(DeviceFormat {
+                channels: 2,
+                sample_rate: 48000,
+                bits_per_sample: 32,
+            }),
+            volume_range: Some(VolumeRange {
+                min_db: -65.25,
+                max_db: 0.0,
+                increment_db: 0.03125,
+                step_count: 100,
+            }),
+        },
+        DeviceSound {
+            id: "mock-unplugged-id".to_string(),
+            name: "Mock Headphones".to_string(),
+            state: DeviceState::Unplugged,
+            form_factor: FormFactor::Headphones,
+            is_default: false,
+            format: None,
+            volume_range: None,
+        },
+    ]
+}
+
+/// Segundo dispositivo de saída, que não é o padrão
+pub fn headset() -> DeviceSound {
+    DeviceSound {
+        id: "mock-headset-id".to_string(),
+        name: "Mock Headset".to_string(),
+        is_default: false,
+        ..mock_devices().remove(0)
+    }
+}
 
 impl AudioOutputDeviceControl for MockAudioSystem {
-    fn list_output_devices(
-        &self,
-    ) -> crate::modules::audio_control::types::AudioResult<
-        Vec<crate::modules::audio_control::models::DeviceSound>,
-    > {
-        Ok(vec![])
+    fn list_output_devices(&self, include_inactive: bool) -> AudioResult<Vec<DeviceSound>> {
+        self.device_listings.fetch_add(1, Ordering::SeqCst);
+        let delay = *self.listing_delay.lock().unwrap();
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+        Ok(self
+            .devices()
+            .into_iter()
+            .filter(|device| include_inactive || device.state == DeviceState::Active)
+            .collect())
+    }
+
+    fn get_actual_volume(&self) -> AudioResult<f32> {
+        let default_device_id = self.default_device_id.lock().unwrap().clone();
+        self.get_device_volume(&default_device_id)
+    }
+
+    fn get_device_volume(&self, device_id: &str) -> AudioResult<f32> {
+        self.check_device(device_id)?;
+        Ok(self
+            .device_volumes
+            .lock()
+            .unwrap()
+            .get(device_id)
+            .copied()
+            .unwrap_or(MOCK_DEVICE_VOLUME))
+    }
+
+    fn set_device_volume(&self, device_id: &str, volume: f32) -> AudioResult<()> {
+        self.check_device(device_id)?;
+        self.simulate_write();
+        self.device_volumes
+            .lock()
+            .unwrap()
+            .insert(device_id.to_string(), volume);
+        Ok(())
+    }
+
+    fn get_device_mute(&self, device_id: &str) -> AudioResult<bool> {
+        self.check_device(device_id)?;
+        Ok(self.muted_devices.lock().unwrap().contains(device_id))
+    }
+
+    fn set_device_mute(&self, device_id: &str, muted: bool) -> AudioResult<()> {
+        self.check_device(device_id)?;
+        let mut muted_devices = self.muted_devices.lock().unwrap();
+        if muted {
+            muted_devices.insert(device_id.to_string());
+        } else {
+            muted_devices.remove(device_id);
+        }
+        Ok(())
+    }
+
+    fn set_default_device(&self, device_id: &str) -> AudioResult<()> {
+        self.check_device(device_id)?;
+        *self.default_device_id.lock().unwrap() = device_id.to_string();
+        Ok(())
     }
 
-    fn get_actual_volume(&self) -> crate::modules::audio_control::types::AudioResult<f32> {
-        Ok(55.0)
+    fn get_device_channel_volumes(&self, _device_id: &str) -> AudioResult<Vec<f32>> {
+        Ok(vec![55.0, 55.0])
+    }
+
+    fn set_device_channel_volumes(&self, _device_id: &str, levels: &[f32]) -> AudioResult<()> {
+        check_channel_count(levels)
     }
 }
 
 impl AudioSessionControl for MockAudioSystem {
     fn get_sessions_for_device(
+        &self,
+        device_id: &str,
+        expand: bool,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        self.get_sessions_for_device_with(device_id, expand, self)
+    }
+
+    /// Como no Windows, cada grupo identifica a aplicação pelo processo dono
+    fn get_sessions_for_device_with(
         &self,
         _device_id: &str,
-    ) -> crate::modules::audio_control::types::AudioResult<Vec<SessionGroup>> {
-        Ok(vec![SessionGroup {
-            id: GroupId::new("11111111-1111-1111-1111-111111111111".to_string()),
-            display_name: "mock-session".to_string(),
-            volume_level: 55.0,
-            state: SessionState::Active,
-            muted: false,
-        }])
+        expand: bool,
+        processes: &dyn AudioProcessLookup,
+    ) -> AudioResult<Vec<SessionGroup>> {
+        self.session_listings.fetch_add(1, Ordering::SeqCst);
+        let mut groups = vec![SessionGroup::from_members(
+            GroupId::new(MOCK_GROUP_ID.to_string()),
+            processes.get_app_info(mock_app_info().pid),
+            self.members.lock().unwrap().clone(),
+            expand,
+        )];
+        let extra_groups = self.extra_groups.lock().unwrap().clone();
+        for (group_id, app, members) in extra_groups {
+            groups.push(SessionGroup::from_members(
+                group_id,
+                processes.get_app_info(app.pid),
+                members,
+                expand,
+            ));
+        }
+        Ok(groups)
+    }
+
+    fn set_session_volume(
+        &self,
+        _device_id: &str,
+        session_id: &str,
+        volume: f32,
+    ) -> AudioResult<()> {
+        self.simulate_write();
+        self.with_session(session_id, |member| member.volume_level = volume)
+    }
+
+    fn set_group_mute(&self, group_id: &GroupId, _device_id: &str, muted: bool) -> AudioResult<()> {
+        if group_id.as_ref() != MOCK_GROUP_ID {
+            return Err(AudioError::NoSessionsFound);
+        }
+        for member in self.members.lock().unwrap().iter_mut() {
+            member.muted = muted;
+        }
+        Ok(())
+    }
+
+    fn set_session_mute(&self, _device_id: &str, session_id: &str, muted: bool) -> AudioResult<()> {
+        self.with_session(session_id, |member| member.muted = muted)
     }
 
     fn set_group_volume(
+        &self,
+        group_id: &GroupId,
+        _device_id: &str,
+        volume: f32,
+        mode: GroupVolumeMode,
+    ) -> AudioResult<()> {
+        if group_id.as_ref() != MOCK_GROUP_ID {
+            return Err(AudioError::NoSessionsFound);
+        }
+        self.simulate_write();
+        self.group_volume_writes.fetch_add(1, Ordering::SeqCst);
+
+        let mut members = self.members.lock().unwrap();
+        let current: Vec<f32> = members.iter().map(|member| member.volume_level).collect();
+        for (member, target) in members
+            .iter_mut()
+            .zip(mode.member_targets(&current, volume))
+        {
+            member.volume_level = target;
+        }
+        Ok(())
+    }
+
+    fn move_group_to_device(
+        &self,
+        group_id: &GroupId,
+        _device_id: &str,
+        target_device_id: &str,
+    ) -> AudioResult<()> {
+        if group_id.as_ref() != MOCK_GROUP_ID {
+            return Err(AudioError::NoSessionsFound);
+        }
+
+        let pids: Vec<u32> = self
+            .members
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|member| member.pid)
+            .collect();
+        for pid in pids {
+            self.set_process_output_device(pid, target_device_id)?;
+        }
+        Ok(())
+    }
+
+    fn set_process_output_device(&self, pid: u32, device_id: &str) -> AudioResult<()> {
+        self.check_device(device_id)?;
+        self.routed
+            .lock()
+            .unwrap()
+            .push((pid, device_id.to_string()));
+        Ok(())
+    }
+
+    fn get_group_icon(&self, group_id: &GroupId) -> AudioResult<Vec<u8>> {
+        if group_id.as_ref() != MOCK_GROUP_ID {
+            return Err(AudioError::NoSessionsFound);
+        }
+        Ok(MOCK_ICON_PNG.to_vec())
+    }
+
+    fn get_group_channel_volumes(
+        &self,
+        _group_id: &GroupId,
+        _device_id: &str,
+    ) -> AudioResult<Vec<f32>> {
+        Ok(vec![80.0, 40.0])
+    }
+
+    fn set_group_channel_volumes(
         &self,
         _group_id: &GroupId,
         _device_id: &str,
-        _volume: f32,
-    ) -> crate::modules::audio_control::types::AudioResult<()> {
+        levels: &[f32],
+    ) -> AudioResult<()> {
+        check_channel_count(levels)
+    }
+}
+
+impl AudioProcessLookup for MockAudioSystem {
+    fn get_app_info(&self, pid: u32) -> AppInfo {
+        self.process_lookups.fetch_add(1, Ordering::SeqCst);
+        if pid == mock_app_info().pid {
+            return mock_app_info();
+        }
+        self.extra_groups
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(_, app, _)| app.pid == pid)
+            .map(|(_, app, _)| app.clone())
+            .unwrap_or_else(|| AppInfo::unresolved(pid))
+    }
+}
+
+impl AudioMeteringControl for MockAudioSystem {
+    fn get_device_peak(&self, device_id: &str) -> AudioResult<f32> {
+        if device_id != "mock-device-id" {
+            return Err(AudioError::DeviceNotFound {
+                device_id: device_id.to_string(),
+            });
+        }
+        Ok(0.5)
+    }
+
+    fn get_group_peaks(&self, _device_id: &str) -> AudioResult<Vec<GroupPeak>> {
+        Ok(vec![GroupPeak {
+            group_id: GroupId::new(MOCK_GROUP_ID.to_string()),
+            peak: 0.25,
+        }])
+    }
+}
+
+impl MediaPlaybackControl for MockAudioSystem {
+    fn toggle_media_playback(&self) -> AudioResult<()> {
+        self.media_toggles.fetch_add(1, Ordering::SeqCst);
         Ok(())
     }
 }
+
+pub const MOCK_DEVICE_VOLUME: f32 = 55.0;
+
+pub const MOCK_GROUP_ID: &str = "11111111-1111-1111-1111-111111111111";
+
+/// Assinatura PNG seguida do início do chunk IHDR
+pub const MOCK_ICON_PNG: &[u8] = &[
+    0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52,
+];
+
+fn mock_app_info() -> AppInfo {
+    AppInfo {
+        display_name: "Mock Session Player".to_string(),
+        process_name: "mock-session".to_string(),
+        pid: 1234,
+        executable_path: Some(r"C:\Program Files\Mock\mock-session.exe".to_string()),
+        is_system_sounds: false,
+    }
+}
+
+fn mock_session_members() -> Vec<SessionMember> {
+    vec![
+        SessionMember {
+            session_id: "mock-session-1".to_string(),
+            pid: 1234,
+            process_name: "mock-session".to_string(),
+            volume_level: 60.0,
+            muted: false,
+            state: SessionState::Active,
+        },
+        SessionMember {
+            session_id: "mock-session-2".to_string(),
+            pid: 1235,
+            process_name: "mock-session".to_string(),
+            volume_level: 50.0,
+            muted: false,
+            state: SessionState::Inactive,
+        },
+    ]
+}
+
+fn check_channel_count(levels: &[f32]) -> AudioResult<()> {
+    if levels.len() != 2 {
+        return Err(AudioError::ChannelCountMismatch {
+            expected: 2,
+            actual: levels.len(),
+        });
+    }
+    Ok(())
+}
@@ -1,3 +1,24 @@
+mod app_routing_tests;
+mod application_view_tests;
+mod async_backend_tests;
 pub mod audio_control_integration_tests;
+mod audio_meter_socket_tests;
+mod audit_tests;
+mod backend_executor_tests;
+mod coalescer_tests;
+mod ducking_tests;
+mod event_replay_tests;
+mod focus_mode_tests;
+mod history_tests;
+mod lookup_cache_tests;
 mod mocks;
+mod profile_tests;
+mod rules_tests;
+mod scheduler_tests;
+mod session_state_tests;
+mod sleep_timer_tests;
+mod state_tests;
 pub mod test_server;
+mod volume_limit_tests;
+mod volume_link_tests;
+mod volume_memory_tests;
@@ -0,0 +1,220 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use super::mocks::{headset, MOCK_DEVICE_VOLUME, MOCK_GROUP_ID};
+use super::test_server::{wait_until, TestServer};
+use crate::modules::audio_control::models::{SessionMember, SessionState};
+use crate::modules::audio_control::platform::audio_system_interface::{
+    AudioOutputDeviceControl, AudioSessionControl,
+};
+use crate::modules::audio_control::types::GroupId;
+
+fn game_profile() -> Value {
+    json!({
+        "name": "Gaming",
+        "default_device_id": "mock-device-id",
+        "devices": [{ "device_id": "mock-device-id", "volume": 30.0 }],
+        "applications": [{
+            "process_name": "Game.exe",
+            "device_id": "mock-device-id",
+            "volume": 15.0,
+            "muted": false
+        }]
+    })
+}
+
+async fn group_state(server: &TestServer) -> Value {
+    let json: Value = server
+        .get("/api/v1/list_session/mock-device-id")
+        .await
+        .json()
+        .await
+        .unwrap();
+    json["data"][0].clone()
+}
+
+#[tokio::test]
+async fn test_capture_list_get_and_delete_profile() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json("/api/v1/capture_profile", json!({ "name": " Work " }))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"]["name"], "Work");
+    assert_eq!(json["data"]["default_device_id"], "mock-device-id");
+    assert_eq!(
+        json["data"]["devices"],
+        json!([{ "device_id": "mock-device-id", "volume": MOCK_DEVICE_VOLUME }])
+    );
+    assert_eq!(
+        json["data"]["applications"],
+        json!([{
+            "process_name": "mock-session",
+            "device_id": "mock-device-id",
+            "volume": 55.0,
+            "muted": false
+        }])
+    );
+
+    let json: Value = server.get("/api/v1/profiles").await.json().await.unwrap();
+    assert_eq!(json["headers"]["count"], 1);
+    assert_eq!(json["data"][0]["name"], "Work");
+
+    let response = server.get("/api/v1/profiles/Work").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = server
+        .post_json("/api/v1/delete_profile", json!({ "name": "Work" }))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: Value = server.get("/api/v1/profiles").await.json().await.unwrap();
+    assert_eq!(json["data"], json!([]));
+}
+
+#[tokio::test]
+async fn test_apply_profile_restores_captured_state() {
+    let server = TestServer::new().await;
+    server
+        .post_json("/api/v1/capture_profile", json!({ "name": "Work" }))
+        .await;
+
+    let audio_system = server.audio_system();
+    audio_system.add_device(headset());
+    audio_system.set_default_device("mock-headset-id").unwrap();
+    audio_system
+        .set_device_volume("mock-device-id", 90.0)
+        .unwrap();
+    audio_system
+        .set_group_mute(
+            &GroupId::new(MOCK_GROUP_ID.to_string()),
+            "mock-device-id",
+            true,
+        )
+        .unwrap();
+
+    let response = server
+        .post_json("/api/v1/apply_profile", json!({ "name": "Work" }))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"]["applied_applications"], 1);
+    assert_eq!(json["data"]["pending_applications"], json!([]));
+    assert_eq!(json["data"]["failures"], json!([]));
+
+    assert_eq!(
+        audio_system.get_actual_volume().unwrap(),
+        MOCK_DEVICE_VOLUME
+    );
+    let group = group_state(&server).await;
+    assert_eq!(group["volume_level"], 55.0);
+    assert_eq!(group["mute_state"], "none");
+}
+
+#[tokio::test]
+async fn test_pending_application_is_applied_when_session_starts() {
+    let server = TestServer::new().await;
+    let monitor = server.audio_module().session_monitor();
+    monitor.poll().await.unwrap();
+
+    let response = server
+        .post_json("/api/v1/import_profiles", json!([game_profile()]))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: Value = server
+        .post_json("/api/v1/apply_profile", json!({ "name": "Gaming" }))
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json["data"]["applied_applications"], 0);
+    assert_eq!(json["data"]["pending_applications"], json!(["game"]));
+
+    server.audio_system().add_session(SessionMember {
+        session_id: "mock-game-session".to_string(),
+        pid: 4321,
+        process_name: "Game".to_string(),
+        volume_level: 100.0,
+        muted: false,
+        state: SessionState::Active,
+    });
+    monitor.poll().await.unwrap();
+
+    // A configuração pendente é aplicada pela tarefa em segundo plano
+    wait_until("pending profile entry was not applied", || async {
+        group_state(&server).await["volume_level"] == 15.0
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_export_and_import_profiles_round_trip() {
+    let server = TestServer::new().await;
+    server
+        .post_json("/api/v1/import_profiles", json!([game_profile()]))
+        .await;
+
+    let response = server.get("/api/v1/export_profiles").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"sound_profiles.json\""
+    );
+    let body = response.bytes().await.unwrap();
+    let exported: Value = serde_json::from_slice(&body[..]).unwrap();
+    assert_eq!(exported[0]["name"], "Gaming");
+    assert_eq!(exported[0]["applications"][0]["process_name"], "game");
+
+    let other = TestServer::new().await;
+    let response = other.post_json("/api/v1/import_profiles", exported).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"], "Profiles imported successfully");
+    assert_eq!(json["headers"]["count"], 1);
+
+    let json: Value = other
+        .get("/api/v1/profiles/Gaming")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json["data"]["devices"][0]["volume"], 30.0);
+}
+
+#[tokio::test]
+async fn test_import_rejects_out_of_range_volume() {
+    let server = TestServer::new().await;
+    let mut profile = game_profile();
+    profile["applications"][0]["volume"] = json!(120.0);
+
+    let response = server
+        .post_json("/api/v1/import_profiles", json!([profile]))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["message"],
+        "Profile 'Gaming': Volume must be between 0.0 and 100.0"
+    );
+
+    let json: Value = server.get("/api/v1/profiles").await.json().await.unwrap();
+    assert_eq!(json["data"], json!([]));
+}
+
+#[tokio::test]
+async fn test_unknown_profile_returns_not_found() {
+    let server = TestServer::new().await;
+
+    let response = server.get("/api/v1/profiles/missing").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "Profile not found");
+
+    let response = server
+        .post_json("/api/v1/apply_profile", json!({ "name": "missing" }))
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,261 @@
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio_tungstenite::connect_async;
+
+use super::test_server::{event_body, wait_until, TestServer};
+use crate::modules::audio_control::models::{SessionMember, SessionState};
+use crate::modules::audio_control::platform::audio_system_interface::AudioOutputDeviceControl;
+use crate::modules::core::ServerEvent;
+
+fn at(hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 1, 17)
+        .unwrap()
+        .and_hms_opt(hour, minute, second)
+        .unwrap()
+}
+
+fn mute_on_start_rule() -> Value {
+    json!({
+        "name": "Mute player",
+        "trigger": { "type": "session_started", "process_name": "Mock-Session.exe" },
+        "actions": [{ "type": "mute_application" }]
+    })
+}
+
+async fn save_rule(server: &TestServer, rule: Value) -> reqwest::Response {
+    server.post_json("/api/v1/save_rule", rule).await
+}
+
+async fn group_mute_state(server: &TestServer) -> Value {
+    let json: Value = server
+        .get("/api/v1/list_session/mock-device-id")
+        .await
+        .json()
+        .await
+        .unwrap();
+    json["data"][0]["mute_state"].clone()
+}
+
+#[tokio::test]
+async fn test_rules_crud() {
+    let server = TestServer::new().await;
+
+    let response = save_rule(&server, mute_on_start_rule()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"]["enabled"], true);
+    assert_eq!(json["data"]["conditions"], json!([]));
+    assert_eq!(json["data"]["actions"][0]["muted"], true);
+
+    let json: Value = server.get("/api/v1/rules").await.json().await.unwrap();
+    assert_eq!(json["headers"]["count"], 1);
+
+    let response = server.get("/api/v1/rules/Mute%20player").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = server
+        .post_json("/api/v1/delete_rule", json!({ "name": "Mute player" }))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = server.get("/api/v1/rules/Mute%20player").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "Rule not found");
+}
+
+#[tokio::test]
+async fn test_save_rule_validates_volumes_and_targets() {
+    let server = TestServer::new().await;
+
+    let response = save_rule(
+        &server,
+        json!({
+            "name": "Loud",
+            "trigger": { "type": "device_connected" },
+            "actions": [{ "type": "set_master_volume", "volume": 150.0 }]
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["message"],
+        "Rule 'Loud': Volume must be between 0.0 and 100.0"
+    );
+
+    let response = save_rule(
+        &server,
+        json!({
+            "name": "Night",
+            "trigger": { "type": "time_of_day", "at": "22:00:00" },
+            "actions": [{ "type": "mute_application" }]
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_session_started_rule_acts_on_triggering_app() {
+    let server = TestServer::new().await;
+    save_rule(&server, mute_on_start_rule()).await;
+
+    let monitor = server.audio_module().session_monitor();
+    monitor.poll().await.unwrap();
+    server.audio_system().add_session(SessionMember {
+        session_id: "mock-session-3".to_string(),
+        pid: 1240,
+        process_name: "mock-session".to_string(),
+        volume_level: 100.0,
+        muted: false,
+        state: SessionState::Active,
+    });
+    monitor.poll().await.unwrap();
+
+    // As regras rodam na tarefa em segundo plano
+    wait_until("rule was not executed", || async {
+        group_mute_state(&server).await == "all"
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_unmet_condition_skips_actions() {
+    let server = TestServer::new().await;
+    let mut rule = mute_on_start_rule();
+    rule["conditions"] = json!([{ "type": "application_running", "process_name": "discord" }]);
+    save_rule(&server, rule).await;
+
+    let event = ServerEvent::SessionStarted {
+        session_id: "mock-session-3".to_string(),
+        display_name: "Mock Session Player".to_string(),
+        process_name: "mock-session".to_string(),
+        device_id: "mock-device-id".to_string(),
+        group_id: "11111111-1111-1111-1111-111111111111".to_string(),
+        pid: 1240,
+        active: true,
+    };
+    let evaluations = server
+        .audio_module()
+        .rules()
+        .handle_event(&event)
+        .await
+        .unwrap();
+
+    assert_eq!(evaluations.len(), 1);
+    assert!(!evaluations[0].conditions_met);
+    assert!(evaluations[0].actions.is_empty());
+    assert_eq!(group_mute_state(&server).await, "none");
+}
+
+#[tokio::test]
+async fn test_time_of_day_rule_fires_once() {
+    let server = TestServer::new().await;
+    save_rule(
+        &server,
+        json!({
+            "name": "Night",
+            "trigger": { "type": "time_of_day", "at": "22:00:00" },
+            "conditions": [{ "type": "time_between", "from": "21:00:00", "to": "06:00:00" }],
+            "actions": [{ "type": "set_master_volume", "volume": 20.0 }]
+        }),
+    )
+    .await;
+
+    let rules = server.audio_module().rules();
+    let clock = server.clock();
+    clock.set(at(21, 59, 30));
+    rules.tick().await.unwrap();
+    clock.set(at(22, 0, 0));
+    let fired = rules.tick().await.unwrap();
+    assert_eq!(fired.len(), 1);
+    assert!(fired[0].conditions_met);
+    clock.advance(TimeDelta::seconds(1));
+    assert!(rules.tick().await.unwrap().is_empty());
+
+    assert_eq!(server.audio_system().get_actual_volume().unwrap(), 20.0);
+}
+
+#[tokio::test]
+async fn test_volume_threshold_rule_sends_notification() {
+    let server = TestServer::new().await;
+    save_rule(
+        &server,
+        json!({
+            "name": "Too loud",
+            "trigger": { "type": "volume_threshold", "threshold": 80.0, "direction": "above" },
+            "actions": [{ "type": "notify", "message": "Volume above 80%" }]
+        }),
+    )
+    .await;
+
+    let (mut events, _) = connect_async(server.ws_url("/api/v1/events"))
+        .await
+        .expect("event socket should connect");
+    tokio::time::sleep(Duration::from_millis(25)).await;
+
+    let rules = server.audio_module().rules();
+    let volume = |volume| ServerEvent::VolumeChanged {
+        device_id: "mock-device-id".to_string(),
+        volume,
+    };
+    assert!(rules.handle_event(&volume(50.0)).await.unwrap().is_empty());
+    assert_eq!(rules.handle_event(&volume(85.0)).await.unwrap().len(), 1);
+    // Continuar acima do limite não dispara de novo
+    assert!(rules.handle_event(&volume(90.0)).await.unwrap().is_empty());
+
+    let message = tokio::time::timeout(Duration::from_secs(2), events.next())
+        .await
+        .expect("notification should arrive in time")
+        .unwrap()
+        .unwrap();
+    let event: Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+    assert_eq!(
+        event_body(event),
+        json!({
+            "event_type": "notification",
+            "title": "Too loud",
+            "message": "Volume above 80%"
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_dry_run_reports_without_executing() {
+    let server = TestServer::new().await;
+    save_rule(&server, mute_on_start_rule()).await;
+
+    let response = server
+        .post_json(
+            "/api/v1/dry_run_rules",
+            json!({
+                "event": {
+                    "event_type": "session_started",
+                    "session_id": "mock-session-3",
+                    "display_name": "Mock Session Player",
+                    "process_name": "mock-session",
+                    "device_id": "mock-device-id",
+                    "group_id": "11111111-1111-1111-1111-111111111111",
+                    "pid": 1240
+                }
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["data"],
+        json!([{
+            "rule": "Mute player",
+            "conditions_met": true,
+            "actions": [{ "type": "mute_application", "muted": true }]
+        }])
+    );
+    assert_eq!(group_mute_state(&server).await, "none");
+}
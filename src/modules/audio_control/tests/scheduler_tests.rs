@@ -0,0 +1,253 @@
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio_tungstenite::connect_async;
+
+use super::test_server::{event_body, TestServer};
+use crate::modules::audio_control::platform::audio_system_interface::AudioOutputDeviceControl;
+use crate::modules::core::CronSchedule;
+
+fn on(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 1, day)
+        .unwrap()
+        .and_hms_opt(hour, minute, 0)
+        .unwrap()
+}
+
+async fn schedule_task(server: &TestServer, task: Value) -> reqwest::Response {
+    server.post_json("/api/v1/schedule_task", task).await
+}
+
+async fn scheduled_tasks(server: &TestServer) -> Value {
+    let json: Value = server
+        .get("/api/v1/scheduled_tasks")
+        .await
+        .json()
+        .await
+        .unwrap();
+    json["data"].clone()
+}
+
+#[test]
+fn test_cron_schedule_next_after() {
+    // 2025-01-17 é uma sexta-feira
+    let weekdays: CronSchedule = "0 23 * * mon-fri".parse().unwrap();
+    assert_eq!(weekdays.next_after(on(17, 12, 0)), Some(on(17, 23, 0)));
+    assert_eq!(weekdays.next_after(on(17, 23, 0)), Some(on(20, 23, 0)));
+
+    let quarter_hours: CronSchedule = "*/15 9-17 * * *".parse().unwrap();
+    assert_eq!(
+        quarter_hours.next_after(on(17, 12, 7)),
+        Some(on(17, 12, 15))
+    );
+    assert_eq!(quarter_hours.next_after(on(17, 17, 45)), Some(on(18, 9, 0)));
+
+    // Dia do mês e dia da semana restritos: basta um coincidir
+    let first_or_sunday: CronSchedule = "0 0 1 * 7".parse().unwrap();
+    assert_eq!(
+        first_or_sunday.next_after(on(17, 12, 0)),
+        Some(on(19, 0, 0))
+    );
+
+    // `*/1` cobre todos os dias do mês, então só a segunda-feira restringe
+    let mondays: CronSchedule = "0 8 */1 * 1".parse().unwrap();
+    assert_eq!(mondays.next_after(on(17, 12, 0)), Some(on(20, 8, 0)));
+    assert_eq!(
+        "0 8 * * 1".parse::<CronSchedule>().unwrap(),
+        "0 8 1-31 * 1".parse::<CronSchedule>().unwrap()
+    );
+
+    for invalid in [
+        "0 23 * *",
+        "60 * * * *",
+        "* * * * 8",
+        "*/0 * * * *",
+        "5-1 * * * *",
+    ] {
+        assert!(invalid.parse::<CronSchedule>().is_err(), "{invalid}");
+    }
+}
+
+#[tokio::test]
+async fn test_weekday_cron_task_mutes_application() {
+    let server = TestServer::new().await;
+    let response = schedule_task(
+        &server,
+        json!({
+            "name": "Quiet player",
+            "schedule": { "type": "cron", "expression": "0 23 * * mon-fri" },
+            "actions": [{ "type": "mute_application", "process_name": "Mock-Session.exe" }]
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    let id = json["data"]["id"].as_u64().unwrap();
+    assert_eq!(json["data"]["next_run"], "2025-01-17T23:00:00");
+
+    let scheduler = server.audio_module().scheduler();
+    assert!(scheduler.tick().await.unwrap().is_empty());
+    server.clock().set(on(17, 23, 0));
+    assert_eq!(scheduler.tick().await.unwrap(), vec![id]);
+
+    let json: Value = server
+        .get("/api/v1/list_session/mock-device-id")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json["data"][0]["mute_state"], "all");
+
+    // Sexta às 23:00 já passou; a próxima ocorrência é na segunda
+    assert_eq!(
+        scheduled_tasks(&server).await[0]["next_run"],
+        "2025-01-20T23:00:00"
+    );
+}
+
+#[tokio::test]
+async fn test_delayed_fade_runs_once_and_progresses_with_clock() {
+    let server = TestServer::new().await;
+    server
+        .audio_system()
+        .set_device_volume("mock-device-id", 80.0)
+        .unwrap();
+
+    let response = schedule_task(
+        &server,
+        json!({
+            "name": "Wind down",
+            "schedule": { "type": "after", "seconds": 1800 },
+            "actions": [{ "type": "fade_master_volume", "volume": 0.0, "duration_secs": 600 }]
+        }),
+    )
+    .await;
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["data"]["schedule"],
+        json!({ "type": "once", "at": "2025-01-17T12:30:00" })
+    );
+
+    let scheduler = server.audio_module().scheduler();
+    let executor = server.audio_module().executor();
+    let clock = server.clock();
+    clock.advance(TimeDelta::minutes(30));
+    assert_eq!(scheduler.tick().await.unwrap().len(), 1);
+    assert_eq!(scheduled_tasks(&server).await, json!([]));
+
+    clock.advance(TimeDelta::minutes(5));
+    executor.advance_fades().await;
+    assert_eq!(server.audio_system().get_actual_volume().unwrap(), 40.0);
+
+    clock.advance(TimeDelta::minutes(5));
+    executor.advance_fades().await;
+    assert_eq!(server.audio_system().get_actual_volume().unwrap(), 0.0);
+}
+
+#[tokio::test]
+async fn test_cancel_scheduled_task() {
+    let server = TestServer::new().await;
+    let json: Value = schedule_task(
+        &server,
+        json!({
+            "name": "Night profile",
+            "schedule": { "type": "once", "at": "2025-01-17T22:00:00" },
+            "actions": [{ "type": "apply_profile", "name": "Night" }]
+        }),
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    let id = json["data"]["id"].clone();
+
+    let response = server
+        .post_json("/api/v1/cancel_scheduled_task", json!({ "id": id }))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(scheduled_tasks(&server).await, json!([]));
+
+    let response = server
+        .post_json("/api/v1/cancel_scheduled_task", json!({ "id": id }))
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "Scheduled task not found");
+}
+
+#[tokio::test]
+async fn test_failed_task_emits_event() {
+    let server = TestServer::new().await;
+    schedule_task(
+        &server,
+        json!({
+            "name": "Night profile",
+            "schedule": { "type": "after", "seconds": 60 },
+            "actions": [{ "type": "apply_profile", "name": "Night" }]
+        }),
+    )
+    .await;
+
+    let (mut events, _) = connect_async(server.ws_url("/api/v1/events"))
+        .await
+        .expect("event socket should connect");
+    tokio::time::sleep(Duration::from_millis(25)).await;
+
+    server.clock().advance(TimeDelta::minutes(1));
+    assert_eq!(
+        server.audio_module().scheduler().tick().await.unwrap(),
+        vec![1]
+    );
+
+    let message = tokio::time::timeout(Duration::from_secs(2), events.next())
+        .await
+        .expect("event should arrive in time")
+        .unwrap()
+        .unwrap();
+    let event: Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+    assert_eq!(
+        event_body(event),
+        json!({
+            "event_type": "scheduled_task_failed",
+            "task_id": 1,
+            "name": "Night profile",
+            "error": "Profile not found: Night"
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_schedule_task_validation() {
+    let server = TestServer::new().await;
+    let cases = [
+        (
+            json!({ "type": "cron", "expression": "0 25 * * *" }),
+            json!([{ "type": "set_master_volume", "volume": 20.0 }]),
+            "Task 'Check': Invalid cron expression: '25' is outside 0-23",
+        ),
+        (
+            json!({ "type": "once", "at": "2025-01-17T11:00:00" }),
+            json!([{ "type": "set_master_volume", "volume": 20.0 }]),
+            "Task 'Check': Scheduled time must be in the future",
+        ),
+        (
+            json!({ "type": "after", "seconds": 60 }),
+            json!([{ "type": "mute_application" }]),
+            "Task 'Check': process_name is required without a session trigger",
+        ),
+    ];
+
+    for (schedule, actions, message) in cases {
+        let response = schedule_task(
+            &server,
+            json!({ "name": "Check", "schedule": schedule, "actions": actions }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let json: Value = response.json().await.unwrap();
+        assert_eq!(json["message"], message);
+    }
+}
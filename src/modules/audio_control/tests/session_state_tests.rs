@@ -0,0 +1,65 @@
+use windows::Win32::Media::Audio::AudioSessionState;
+
+use crate::modules::audio_control::{
+    models::{AppInfo, SessionGroup, SessionMember, SessionState},
+    types::GroupId,
+};
+
+fn member(session_id: &str, state: SessionState) -> SessionMember {
+    SessionMember {
+        session_id: session_id.to_string(),
+        pid: 1234,
+        process_name: "player".to_string(),
+        volume_level: 50.0,
+        muted: false,
+        state,
+    }
+}
+
+fn group_state(states: &[SessionState]) -> SessionState {
+    let members = states
+        .iter()
+        .enumerate()
+        .map(|(index, state)| member(&format!("session-{index}"), *state))
+        .collect();
+
+    SessionGroup::from_members(
+        GroupId::new("group".to_string()),
+        AppInfo::unresolved(1234),
+        members,
+        false,
+    )
+    .state
+}
+
+#[test]
+fn test_session_state_follows_windows_values() {
+    assert_eq!(
+        SessionState::from(AudioSessionState(0)),
+        SessionState::Inactive
+    );
+    assert_eq!(
+        SessionState::from(AudioSessionState(1)),
+        SessionState::Active
+    );
+    assert_eq!(
+        SessionState::from(AudioSessionState(2)),
+        SessionState::Expired
+    );
+}
+
+#[test]
+fn test_group_state_prefers_active_and_expires_only_when_all_expired() {
+    use SessionState::{Active, Expired, Inactive};
+
+    assert_eq!(group_state(&[Inactive, Active, Expired]), Active);
+    assert_eq!(group_state(&[Expired, Inactive]), Inactive);
+    assert_eq!(group_state(&[Expired, Expired]), Expired);
+    assert_eq!(
+        group_state(&[
+            SessionState::from(AudioSessionState(0)),
+            SessionState::from(AudioSessionState(1)),
+        ]),
+        Active
+    );
+}
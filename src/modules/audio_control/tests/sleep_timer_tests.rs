@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use chrono::TimeDelta;
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio_tungstenite::connect_async;
+
+use super::test_server::{event_body, TestServer};
+use crate::modules::audio_control::platform::audio_system_interface::AudioOutputDeviceControl;
+
+async fn start_sleep_timer(server: &TestServer, request: Value) -> reqwest::Response {
+    server.post_json("/api/v1/start_sleep_timer", request).await
+}
+
+async fn session_group(server: &TestServer) -> Value {
+    let json: Value = server
+        .get("/api/v1/list_session/mock-device-id?expand=true")
+        .await
+        .json()
+        .await
+        .unwrap();
+    json["data"][0].clone()
+}
+
+#[tokio::test]
+async fn test_master_fades_then_mutes_with_volume_restored() {
+    let server = TestServer::new().await;
+    let audio_system = server.audio_system();
+    audio_system
+        .set_device_volume("mock-device-id", 80.0)
+        .unwrap();
+
+    let response =
+        start_sleep_timer(&server, json!({ "duration_secs": 600, "fade_secs": 300 })).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["data"]["target"],
+        json!({ "type": "master", "device_id": "mock-device-id" })
+    );
+    assert_eq!(json["data"]["ends_at"], "2025-01-17T12:10:00");
+
+    let sleep_timer = server.audio_module().sleep_timer();
+    let clock = server.clock();
+    clock.advance(TimeDelta::minutes(5));
+    sleep_timer.tick().await.unwrap();
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 80.0);
+
+    clock.advance(TimeDelta::seconds(150));
+    sleep_timer.tick().await.unwrap();
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 40.0);
+    let json: Value = server
+        .get("/api/v1/sleep_timer")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json["data"]["remaining_secs"], 150);
+    assert_eq!(json["data"]["fading"], true);
+
+    clock.advance(TimeDelta::seconds(150));
+    sleep_timer.tick().await.unwrap();
+    assert!(audio_system.get_device_mute("mock-device-id").unwrap());
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 80.0);
+    assert!(sleep_timer.status().is_none());
+}
+
+#[tokio::test]
+async fn test_cancel_restores_application_volumes() {
+    let server = TestServer::new().await;
+    start_sleep_timer(
+        &server,
+        json!({
+            "duration_secs": 120,
+            "target": { "type": "applications", "process_names": ["Mock-Session.exe"] }
+        }),
+    )
+    .await;
+
+    // Sem `fade_secs`, o fade ocupa todo o timer curto
+    server.clock().advance(TimeDelta::seconds(60));
+    server.audio_module().sleep_timer().tick().await.unwrap();
+    let group = session_group(&server).await;
+    assert_eq!(group["sessions"][0]["volume_level"], 30.0);
+    assert_eq!(group["sessions"][1]["volume_level"], 25.0);
+
+    let response = server
+        .post_json("/api/v1/cancel_sleep_timer", json!({}))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let group = session_group(&server).await;
+    assert_eq!(group["sessions"][0]["volume_level"], 60.0);
+    assert_eq!(group["sessions"][1]["volume_level"], 50.0);
+    assert_eq!(group["mute_state"], "none");
+
+    let response = server
+        .post_json("/api/v1/cancel_sleep_timer", json!({}))
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "No sleep timer running");
+}
+
+#[tokio::test]
+async fn test_pause_media_sends_play_pause_key_at_the_end() {
+    let server = TestServer::new().await;
+    let response = start_sleep_timer(
+        &server,
+        json!({ "duration_secs": 60, "fade_secs": 0, "pause_media": true }),
+    )
+    .await;
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"]["pause_media"], true);
+
+    let sleep_timer = server.audio_module().sleep_timer();
+    sleep_timer.tick().await.unwrap();
+    assert_eq!(server.audio_system().media_toggles(), 0);
+
+    server.clock().advance(TimeDelta::seconds(60));
+    sleep_timer.tick().await.unwrap();
+    assert_eq!(server.audio_system().media_toggles(), 1);
+    assert!(server
+        .audio_system()
+        .get_device_mute("mock-device-id")
+        .unwrap());
+}
+
+/// Sem sessão tocando, a tecla de play/pause daria play em vez de pausar
+#[tokio::test]
+async fn test_pause_media_is_skipped_when_nothing_is_playing() {
+    let server = TestServer::new().await;
+    server.audio_system().remove_session("mock-session-1");
+    start_sleep_timer(
+        &server,
+        json!({ "duration_secs": 60, "fade_secs": 0, "pause_media": true }),
+    )
+    .await;
+
+    server.clock().advance(TimeDelta::seconds(60));
+    server.audio_module().sleep_timer().tick().await.unwrap();
+    assert_eq!(server.audio_system().media_toggles(), 0);
+    assert!(server
+        .audio_system()
+        .get_device_mute("mock-device-id")
+        .unwrap());
+}
+
+#[tokio::test]
+async fn test_status_does_not_wait_for_backend_calls() {
+    let server = TestServer::new().await;
+    start_sleep_timer(&server, json!({ "duration_secs": 60 })).await;
+    server.clock().advance(TimeDelta::seconds(30));
+    server
+        .audio_system()
+        .set_write_delay(Duration::from_millis(300));
+
+    let sleep_timer = server.audio_module().sleep_timer();
+    let ask = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // O passo está no meio da escrita do volume do fade
+        let asked_at = std::time::Instant::now();
+        assert!(sleep_timer.status().unwrap().fading);
+        assert!(asked_at.elapsed() < Duration::from_millis(150));
+    };
+    let (stepped, ()) = tokio::join!(sleep_timer.tick(), ask);
+    stepped.unwrap();
+}
+
+#[tokio::test]
+async fn test_sleep_timer_pushes_remaining_time() {
+    let server = TestServer::new().await;
+    let (mut events, _) = connect_async(server.ws_url("/api/v1/events"))
+        .await
+        .expect("event socket should connect");
+    tokio::time::sleep(Duration::from_millis(25)).await;
+
+    start_sleep_timer(&server, json!({ "duration_secs": 1800 })).await;
+    server.clock().advance(TimeDelta::seconds(61));
+    server.audio_module().sleep_timer().tick().await.unwrap();
+
+    let mut received = Vec::new();
+    for _ in 0..2 {
+        let message = tokio::time::timeout(Duration::from_secs(2), events.next())
+            .await
+            .expect("event should arrive in time")
+            .unwrap()
+            .unwrap();
+        received.push(event_body(
+            serde_json::from_str(message.to_text().unwrap()).unwrap(),
+        ));
+    }
+    assert_eq!(
+        received,
+        vec![
+            json!({ "event_type": "sleep_timer_updated", "remaining_secs": 1800, "fading": false }),
+            json!({ "event_type": "sleep_timer_updated", "remaining_secs": 1739, "fading": false }),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_start_sleep_timer_validation() {
+    let server = TestServer::new().await;
+
+    let response = start_sleep_timer(&server, json!({ "duration_secs": 0 })).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "duration_secs must be greater than zero");
+
+    let response = start_sleep_timer(
+        &server,
+        json!({ "duration_secs": 60, "target": { "type": "applications", "process_names": [] } }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let json: Value = server
+        .get("/api/v1/sleep_timer")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json["data"], Value::Null);
+}
@@ -0,0 +1,254 @@
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+use super::mocks::{MOCK_DEVICE_VOLUME, MOCK_GROUP_ID};
+use super::test_server::TestServer;
+use crate::modules::audio_control::models::{AppInfo, SessionMember, SessionState};
+use crate::modules::core::executor::BackendExecutorConfig;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn get_data(server: &TestServer, path: &str) -> Value {
+    let response = server.get(path).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    json["data"].clone()
+}
+
+async fn set_device_volume(server: &TestServer, volume: f32) {
+    let response = server
+        .post_json(
+            "/api/v1/set_device_volume",
+            json!({ "device_id": "mock-device-id", "volume": volume }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+fn add_chat_app(server: &TestServer) {
+    server.audio_system().add_group(
+        "22222222-2222-2222-2222-222222222222",
+        AppInfo {
+            display_name: "Chat".to_string(),
+            process_name: "chat".to_string(),
+            pid: 4321,
+            executable_path: None,
+            is_system_sounds: false,
+        },
+        vec![SessionMember {
+            session_id: "chat-session".to_string(),
+            pid: 4321,
+            process_name: "chat".to_string(),
+            volume_level: 80.0,
+            muted: false,
+            state: SessionState::Active,
+        }],
+    );
+}
+
+async fn next_json(socket: &mut Socket) -> Value {
+    let message = tokio::time::timeout(Duration::from_secs(2), socket.next())
+        .await
+        .expect("message should arrive in time")
+        .expect("socket should stay open")
+        .expect("message should be valid");
+    serde_json::from_str(message.to_text().expect("message should be text"))
+        .expect("message should be valid JSON")
+}
+
+#[tokio::test]
+async fn test_state_returns_every_device_with_a_version() {
+    let server = TestServer::new().await;
+
+    let data = get_data(&server, "/api/v1/state").await;
+    assert_eq!(data["version"], 1);
+    let state = &data["state"];
+    assert_eq!(state["default_device_id"], "mock-device-id");
+    let device = &state["devices"]["mock-device-id"];
+    assert_eq!(device["name"], "Mock Speakers");
+    assert_eq!(device["volume"], MOCK_DEVICE_VOLUME);
+    assert_eq!(device["muted"], false);
+    assert_eq!(
+        device["sessions"][MOCK_GROUP_ID]["process_name"],
+        "mock-session"
+    );
+
+    // Sem mudanças a versão não avança
+    let data = get_data(&server, "/api/v1/state").await;
+    assert_eq!(data["version"], 1);
+}
+
+#[tokio::test]
+async fn test_simultaneous_state_requests_share_one_read() {
+    let server = TestServer::with_config(|config| {
+        config.backend = BackendExecutorConfig {
+            threads: 8,
+            ..BackendExecutorConfig::default()
+        };
+    })
+    .await;
+    server
+        .audio_system()
+        .set_listing_delay(Duration::from_millis(100));
+
+    let responses =
+        futures::future::join_all((0..6).map(|_| get_data(&server, "/api/v1/state"))).await;
+    for data in responses {
+        assert_eq!(data["version"], 1);
+    }
+    // A primeira leitura e, no máximo, uma feita por quem chegou durante ela
+    assert!(server.audio_system().device_listings() <= 2);
+}
+
+#[tokio::test]
+async fn test_state_changes_returns_json_patch_since_a_version() {
+    let server = TestServer::new().await;
+    get_data(&server, "/api/v1/state").await;
+
+    set_device_volume(&server, 30.0).await;
+    let data = get_data(&server, "/api/v1/state/changes?since=1").await;
+    assert_eq!(
+        data,
+        json!({
+            "type": "patch",
+            "since": 1,
+            "version": 2,
+            "operations": [
+                { "op": "replace", "path": "/devices/mock-device-id/volume", "value": 30.0 }
+            ]
+        })
+    );
+
+    add_chat_app(&server);
+    let data = get_data(&server, "/api/v1/state/changes?since=1").await;
+    assert_eq!(data["version"], 3);
+    let operations = data["operations"].as_array().unwrap();
+    assert_eq!(operations.len(), 2);
+    assert_eq!(operations[1]["op"], "add");
+    assert_eq!(
+        operations[1]["path"],
+        "/devices/mock-device-id/sessions/22222222-2222-2222-2222-222222222222"
+    );
+    assert_eq!(operations[1]["value"]["display_name"], "Chat");
+
+    // Já atualizado: nada a aplicar
+    let data = get_data(&server, "/api/v1/state/changes?since=3").await;
+    assert_eq!(data["operations"], json!([]));
+}
+
+#[tokio::test]
+async fn test_state_changes_asks_for_resync_when_version_is_unknown() {
+    let server = TestServer::new().await;
+    set_device_volume(&server, 30.0).await;
+
+    for since in [0, 7] {
+        let data = get_data(&server, &format!("/api/v1/state/changes?since={since}")).await;
+        assert_eq!(data["type"], "resync");
+        assert_eq!(data["version"], 1);
+        assert_eq!(data["state"]["devices"]["mock-device-id"]["volume"], 30.0);
+    }
+
+    let response = server.get("/api/v1/state/changes?since=abc").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+/// Depois de um reinício as versões recomeçam; um `since` de antes dele
+/// não pode virar patch sobre o estado novo
+#[tokio::test]
+async fn test_state_changes_resyncs_clients_from_another_epoch() {
+    let server = TestServer::new().await;
+    let data = get_data(&server, "/api/v1/state").await;
+    let epoch = data["epoch"].as_u64().expect("state should carry an epoch");
+    set_device_volume(&server, 30.0).await;
+
+    let data = get_data(
+        &server,
+        &format!("/api/v1/state/changes?since=1&epoch={}", epoch + 1),
+    )
+    .await;
+    assert_eq!(data["type"], "resync");
+    assert_eq!(data["epoch"], epoch);
+    assert_eq!(data["version"], 2);
+    assert_eq!(data["state"]["devices"]["mock-device-id"]["volume"], 30.0);
+
+    let data = get_data(
+        &server,
+        &format!("/api/v1/state/changes?since=1&epoch={epoch}"),
+    )
+    .await;
+    assert_eq!(data["type"], "patch");
+    assert_eq!(data["version"], 2);
+}
+
+#[tokio::test]
+async fn test_state_changes_resyncs_clients_too_far_behind() {
+    let server = TestServer::new().await;
+    get_data(&server, "/api/v1/state").await;
+
+    for step in 0..70 {
+        set_device_volume(&server, step as f32).await;
+        get_data(&server, "/api/v1/state").await;
+    }
+
+    let data = get_data(&server, "/api/v1/state/changes?since=1").await;
+    assert_eq!(data["type"], "resync");
+    assert_eq!(data["version"], 71);
+
+    let data = get_data(&server, "/api/v1/state/changes?since=60").await;
+    assert_eq!(data["type"], "patch");
+    assert_eq!(data["operations"].as_array().unwrap().len(), 11);
+}
+
+#[tokio::test]
+async fn test_ws_state_stream_pushes_patches_after_subscribe() {
+    let server = TestServer::new().await;
+    let (mut socket, _) = connect_async(server.ws_url("/api/v1/state/stream"))
+        .await
+        .expect("state socket should connect");
+
+    socket
+        .send(Message::text(json!({ "action": "subscribe" }).to_string()))
+        .await
+        .unwrap();
+    let message = next_json(&mut socket).await;
+    assert_eq!(message["type"], "resync");
+    assert_eq!(message["version"], 1);
+
+    // Qualquer leitura que encontre mudanças avisa os inscritos
+    set_device_volume(&server, 30.0).await;
+    get_data(&server, "/api/v1/state").await;
+    let message = next_json(&mut socket).await;
+    assert_eq!(
+        message,
+        json!({
+            "type": "patch",
+            "since": 1,
+            "version": 2,
+            "operations": [
+                { "op": "replace", "path": "/devices/mock-device-id/volume", "value": 30.0 }
+            ]
+        })
+    );
+
+    // Reconectar com a última versão recebida traz só o que faltou
+    drop(socket);
+    set_device_volume(&server, 40.0).await;
+    let (mut socket, _) = connect_async(server.ws_url("/api/v1/state/stream"))
+        .await
+        .expect("state socket should connect");
+    socket
+        .send(Message::text(
+            json!({ "action": "subscribe", "since": 2 }).to_string(),
+        ))
+        .await
+        .unwrap();
+    let message = next_json(&mut socket).await;
+    assert_eq!(message["type"], "patch");
+    assert_eq!(message["version"], 3);
+    assert_eq!(message["operations"][0]["value"], 40.0);
+}
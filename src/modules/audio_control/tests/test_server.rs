@@ -1,35 +1,138 @@
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use serde_json::Value;
 
 use super::mocks::MockAudioSystem;
-use crate::modules::audio_control::audio_module::AudioModule;
-use crate::modules::core::tests_support::base_test_server::BaseTestServer;
+use crate::modules::audio_control::audio_module::{AudioModule, AudioModuleConfig};
+use crate::modules::audio_control::platform::cached_audio_system::LookupCacheConfig;
+use crate::modules::core::audit::AuditLog;
+use crate::modules::core::tests_support::{BaseTestServer, ManualClock};
 
 pub struct TestServer {
     inner: BaseTestServer,
+    audio_system: Arc<MockAudioSystem>,
+    audio_module: Arc<AudioModule>,
+    clock: Arc<ManualClock>,
 }
 
 impl TestServer {
     pub async fn new() -> Self {
-        let inner = BaseTestServer::new_with_registry(|registry| {
-            let audio_module = AudioModule::new(Arc::new(MockAudioSystem));
-            registry.register("audio", Arc::new(audio_module));
-        })
+        // Sem intervalo entre escritas, pedidos em sequência são aplicados na hora
+        Self::with_volume_write_interval(Duration::ZERO).await
+    }
+
+    pub async fn with_volume_write_interval(volume_write_interval: Duration) -> Self {
+        Self::with_config(|config| config.volume_write_interval = volume_write_interval).await
+    }
+
+    /// Servidor com a configuração padrão dos testes ajustada por `configure`
+    pub async fn with_config(configure: impl FnOnce(&mut AudioModuleConfig)) -> Self {
+        let audio_system = Arc::new(MockAudioSystem::default());
+        let clock = Arc::new(ManualClock::at_noon());
+        // Sem monitor em segundo plano: os testes disparam as leituras
+        let mut config = AudioModuleConfig {
+            data_dir: None,
+            monitor_interval: None,
+            clock: clock.clone(),
+            volume_write_interval: Duration::ZERO,
+            lookup_cache: LookupCacheConfig {
+                device_ttl: Duration::ZERO,
+                process_ttl: Duration::ZERO,
+            },
+            ..AudioModuleConfig::default()
+        };
+        configure(&mut config);
+        let audio_module = Arc::new(AudioModule::blocking(audio_system.clone(), config));
+
+        let audit = Arc::new(AuditLog::in_memory(clock.clone()));
+
+        let module = Arc::clone(&audio_module);
+        let inner = BaseTestServer::new_with_audit(
+            move |registry| {
+                registry.register("audio", module);
+            },
+            audit,
+        )
         .await;
 
-        Self { inner }
+        Self {
+            inner,
+            audio_system,
+            audio_module,
+            clock,
+        }
+    }
+
+    pub fn audio_system(&self) -> &MockAudioSystem {
+        &self.audio_system
+    }
+
+    pub fn audio_module(&self) -> &AudioModule {
+        &self.audio_module
+    }
+
+    pub fn clock(&self) -> &ManualClock {
+        &self.clock
+    }
+
+    pub fn ws_url(&self, path: &str) -> String {
+        self.inner.ws_url(path)
     }
 
     pub async fn get(&self, path: &str) -> reqwest::Response {
         self.inner.get(path).await
     }
 
+    pub async fn get_with_header(&self, path: &str, name: &str, value: &str) -> reqwest::Response {
+        self.inner.get_with_header(path, name, value).await
+    }
+
     pub async fn post_json(&self, path: &str, body: Value) -> reqwest::Response {
         self.inner.post_json(path, body).await
     }
 
+    pub async fn post_json_with_header(
+        &self,
+        path: &str,
+        body: Value,
+        name: &str,
+        value: &str,
+    ) -> reqwest::Response {
+        self.inner
+            .post_json_with_header(path, body, name, value)
+            .await
+    }
+
     pub async fn post_raw(&self, path: &str, body: &str) -> reqwest::Response {
         self.inner.post_raw(path, body).await
     }
 }
+
+/// Aguarda até `condition` valer, para efeitos das tarefas em segundo plano
+///
+/// Desiste depois de cerca de um segundo, falhando o teste com `failure`.
+pub async fn wait_until<F, Fut>(failure: &str, mut condition: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    for _ in 0..40 {
+        if condition().await {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(25)).await;
+    }
+    panic!("{failure}");
+}
+
+/// Evento recebido pelo WebSocket sem `id` e `timestamp`, que dependem da
+/// ordem e do horário da execução
+pub fn event_body(mut event: Value) -> Value {
+    if let Some(fields) = event.as_object_mut() {
+        assert!(fields.remove("id").is_some_and(|id| id.is_u64()));
+        assert!(fields.remove("timestamp").is_some_and(|ts| ts.is_u64()));
+    }
+    event
+}
@@ -0,0 +1,331 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use chrono::TimeDelta;
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio_tungstenite::connect_async;
+
+use super::mocks::MockAudioSystem;
+use super::test_server::{event_body, TestServer};
+use crate::modules::audio_control::models::GroupVolumeMode;
+use crate::modules::audio_control::platform::{
+    async_audio_system::AsyncAudioSessionControl,
+    audio_system_interface::{AudioOutputDeviceControl, AudioSessionControl},
+    blocking_audio_adapter::BlockingAudioAdapter,
+    limited_audio_system::LimitedAudioSystem,
+};
+use crate::modules::audio_control::services::{Notifier, VolumeLimitService};
+use crate::modules::audio_control::types::GroupId;
+use crate::modules::core::executor::BackendExecutorConfig;
+use crate::modules::core::persistence::JsonStore;
+use crate::modules::core::tests_support::ManualClock;
+use crate::modules::core::Broadcaster;
+
+async fn set_limits(server: &TestServer, limits: Value) {
+    let response = server.post_json("/api/v1/set_volume_limits", limits).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+async fn set_master(server: &TestServer, volume: f32) -> reqwest::Response {
+    server
+        .post_json(
+            "/api/v1/set_device_volume",
+            json!({ "device_id": "mock-device-id", "volume": volume }),
+        )
+        .await
+}
+
+async fn session_volumes(server: &TestServer) -> Vec<f64> {
+    let json: Value = server
+        .get("/api/v1/list_session/mock-device-id?expand=true")
+        .await
+        .json()
+        .await
+        .unwrap();
+    json["data"][0]["sessions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| member["volume_level"].as_f64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_master_request_is_clamped_or_rejected_by_policy() {
+    let server = TestServer::new().await;
+    set_limits(&server, json!({ "max_master": 70.0 })).await;
+
+    let response = set_master(&server, 100.0).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"], 70.0);
+    assert_eq!(server.audio_system().get_actual_volume().unwrap(), 70.0);
+
+    set_limits(&server, json!({ "policy": "reject", "max_master": 70.0 })).await;
+    let response = set_master(&server, 90.0).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "Volume limit exceeded");
+    assert_eq!(json["details"], "max_master: requested 90, allowed 70");
+    assert_eq!(server.audio_system().get_actual_volume().unwrap(), 70.0);
+
+    // Abaixo do teto o pedido passa inalterado
+    assert_eq!(set_master(&server, 40.0).await.status(), StatusCode::OK);
+    assert_eq!(server.audio_system().get_actual_volume().unwrap(), 40.0);
+}
+
+#[tokio::test]
+async fn test_max_step_and_quiet_hours_limit_master_increases() {
+    let server = TestServer::new().await;
+    set_limits(
+        &server,
+        json!({
+            "max_step": 10.0,
+            "quiet_hours": { "from": "22:00:00", "to": "07:00:00", "max_volume": 30.0 }
+        }),
+    )
+    .await;
+
+    let json: Value = set_master(&server, 100.0).await.json().await.unwrap();
+    assert_eq!(json["data"], 65.0);
+    // Baixar não é limitado pelo passo
+    let json: Value = set_master(&server, 10.0).await.json().await.unwrap();
+    assert_eq!(json["data"], 10.0);
+
+    server.clock().advance(TimeDelta::hours(11));
+    let json: Value = set_master(&server, 35.0).await.json().await.unwrap();
+    assert_eq!(json["data"], 20.0);
+    let json: Value = set_master(&server, 35.0).await.json().await.unwrap();
+    assert_eq!(json["data"], 30.0);
+}
+
+#[tokio::test]
+async fn test_application_cap_limits_group_session_and_application_requests() {
+    let server = TestServer::new().await;
+    set_limits(
+        &server,
+        json!({ "application_max": { "Mock-Session.exe": 40.0 } }),
+    )
+    .await;
+
+    let json: Value = server
+        .get("/api/v1/volume_limits")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        json["data"]["application_max"],
+        json!({ "mock-session": 40.0 })
+    );
+
+    let response = server
+        .post_json(
+            "/api/v1/set_group_volume",
+            json!({
+                "device_id": "mock-device-id",
+                "group_id": "11111111-1111-1111-1111-111111111111",
+                "volume": 100.0
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(session_volumes(&server).await, vec![40.0, 40.0]);
+
+    server
+        .post_json(
+            "/api/v1/set_session_volume",
+            json!({ "device_id": "mock-device-id", "session_id": "mock-session-2", "volume": 20.0 }),
+        )
+        .await;
+    assert_eq!(session_volumes(&server).await, vec![40.0, 20.0]);
+
+    // No modo proporcional o teto vale para a sessão mais alta
+    let response = server
+        .post_json(
+            "/api/v1/set_application_volume",
+            json!({ "process_name": "mock-session", "volume": 90.0, "mode": "proportional" }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let volumes = session_volumes(&server).await;
+    assert!((volumes[0] - 40.0).abs() < 0.01, "{volumes:?}");
+    assert!((volumes[1] - 20.0).abs() < 0.01, "{volumes:?}");
+}
+
+#[tokio::test]
+async fn test_caps_apply_to_profiles_history_and_channels() {
+    let server = TestServer::new().await;
+    let audio_system = server.audio_system();
+    set_master(&server, 100.0).await;
+    server
+        .post_json(
+            "/api/v1/import_profiles",
+            json!([{
+                "name": "Loud",
+                "devices": [{ "device_id": "mock-device-id", "volume": 95.0 }],
+                "applications": []
+            }]),
+        )
+        .await;
+    set_limits(&server, json!({ "max_master": 60.0 })).await;
+
+    server.post_json("/api/v1/undo", json!({})).await;
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 55.0);
+    let response = server.post_json("/api/v1/redo", json!({})).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 60.0);
+
+    audio_system
+        .set_device_volume("mock-device-id", 20.0)
+        .unwrap();
+    let response = server
+        .post_json("/api/v1/apply_profile", json!({ "name": "Loud" }))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 60.0);
+
+    // O canal mais alto fica no teto, mantendo o balanço
+    let response = server
+        .post_json(
+            "/api/v1/set_device_channels",
+            json!({ "device_id": "mock-device-id", "channels": [90.0, 45.0] }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"]["channels"], json!([60.0, 30.0]));
+}
+
+#[tokio::test]
+async fn test_enforcer_corrects_external_changes_and_notifies() {
+    let server = TestServer::new().await;
+    set_limits(&server, json!({ "max_master": 60.0 })).await;
+
+    let (mut events, _) = connect_async(server.ws_url("/api/v1/events"))
+        .await
+        .expect("event socket should connect");
+    tokio::time::sleep(Duration::from_millis(25)).await;
+
+    let audio_system = server.audio_system();
+    audio_system
+        .set_device_volume("mock-device-id", 95.0)
+        .unwrap();
+    server
+        .audio_module()
+        .volume_limits()
+        .enforce()
+        .await
+        .unwrap();
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 60.0);
+    assert_eq!(
+        server
+            .audio_module()
+            .volume_limits()
+            .enforce()
+            .await
+            .unwrap(),
+        0
+    );
+
+    let message = tokio::time::timeout(Duration::from_secs(2), events.next())
+        .await
+        .expect("notification should arrive in time")
+        .unwrap()
+        .unwrap();
+    let event: Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+    assert_eq!(
+        event_body(event),
+        json!({
+            "event_type": "notification",
+            "title": "Volume limit",
+            "message": "Master volume of 'Mock Speakers' lowered from 95% to 60% (max_master)"
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_set_volume_limits_validates_values() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json("/api/v1/set_volume_limits", json!({ "max_master": 120.0 }))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["message"],
+        "Volume limits must be between 0.0 and 100.0"
+    );
+
+    let response = server
+        .post_json("/api/v1/set_volume_limits", json!({ "max_step": 0.0 }))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_capped_writes_reuse_the_last_listing() {
+    let mock = Arc::new(MockAudioSystem::default());
+    let backend = Arc::new(BlockingAudioAdapter::new(
+        mock.clone(),
+        BackendExecutorConfig::default(),
+    ));
+    let limits = Arc::new(VolumeLimitService::new(
+        backend.clone(),
+        Notifier::new(Broadcaster::new(16)),
+        JsonStore::in_memory(),
+        Arc::new(ManualClock::at_noon()),
+    ));
+    limits
+        .set_limits(
+            serde_json::from_value(json!({ "application_max": { "mock-session": 40.0 } })).unwrap(),
+        )
+        .unwrap();
+    let audio_system = LimitedAudioSystem::new(backend, limits);
+    let group_id = GroupId::new("11111111-1111-1111-1111-111111111111".to_string());
+
+    audio_system
+        .get_sessions_for_device("mock-device-id", true)
+        .await
+        .unwrap();
+    let listings = mock.session_listings();
+    for volume in [90.0, 80.0, 70.0] {
+        audio_system
+            .set_group_volume(
+                &group_id,
+                "mock-device-id",
+                volume,
+                GroupVolumeMode::Uniform,
+            )
+            .await
+            .unwrap();
+    }
+    audio_system
+        .set_session_volume("mock-device-id", "mock-session-1", 90.0)
+        .await
+        .unwrap();
+    assert_eq!(mock.session_listings(), listings);
+    assert_eq!(session_levels(&mock), vec![40.0, 40.0]);
+
+    // A sessão desconhecida custa uma única listagem
+    let listings = mock.session_listings();
+    assert!(audio_system
+        .set_session_volume("mock-device-id", "missing", 90.0)
+        .await
+        .is_err());
+    assert_eq!(mock.session_listings(), listings + 1);
+}
+
+fn session_levels(mock: &MockAudioSystem) -> Vec<f32> {
+    mock.get_sessions_for_device("mock-device-id", true)
+        .unwrap()
+        .remove(0)
+        .sessions
+        .unwrap()
+        .iter()
+        .map(|member| member.volume_level)
+        .collect()
+}
@@ -0,0 +1,193 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use super::mocks::MOCK_GROUP_ID;
+use super::test_server::TestServer;
+use crate::modules::audio_control::{
+    models::GroupVolumeMode,
+    platform::audio_system_interface::{AudioOutputDeviceControl, AudioSessionControl},
+    types::GroupId,
+};
+
+async fn save_link(server: &TestServer, link: Value) -> reqwest::Response {
+    server.post_json("/api/v1/save_volume_link", link).await
+}
+
+fn dual_speakers() -> Value {
+    json!({
+        "name": "Dual speakers",
+        "members": [
+            { "target": { "type": "device", "device_id": "mock-device-id" } },
+            { "target": { "type": "device", "device_id": "mock-unplugged-id" }, "ratio": 2.0 }
+        ]
+    })
+}
+
+#[tokio::test]
+async fn test_api_change_propagates_to_linked_devices() {
+    let server = TestServer::new().await;
+    assert_eq!(
+        save_link(&server, dual_speakers()).await.status(),
+        StatusCode::OK
+    );
+    let audio_system = server.audio_system();
+
+    server
+        .post_json(
+            "/api/v1/set_device_volume",
+            json!({ "device_id": "mock-device-id", "volume": 40.0 }),
+        )
+        .await;
+    assert_eq!(
+        audio_system.get_device_volume("mock-unplugged-id").unwrap(),
+        80.0
+    );
+
+    // O link vale nos dois sentidos
+    server
+        .post_json(
+            "/api/v1/set_device_volume",
+            json!({ "device_id": "mock-unplugged-id", "volume": 50.0 }),
+        )
+        .await;
+    assert_eq!(
+        audio_system.get_device_volume("mock-device-id").unwrap(),
+        25.0
+    );
+}
+
+#[tokio::test]
+async fn test_clamped_member_does_not_bounce_back() {
+    let server = TestServer::new().await;
+    save_link(&server, dual_speakers()).await;
+    let audio_system = server.audio_system();
+    let links = server.audio_module().volume_links();
+
+    server
+        .post_json(
+            "/api/v1/set_device_volume",
+            json!({ "device_id": "mock-device-id", "volume": 80.0 }),
+        )
+        .await;
+    assert_eq!(
+        audio_system.get_device_volume("mock-unplugged-id").unwrap(),
+        100.0
+    );
+
+    for _ in 0..3 {
+        assert_eq!(links.tick().await.unwrap(), 0);
+    }
+    assert_eq!(
+        audio_system.get_device_volume("mock-device-id").unwrap(),
+        80.0
+    );
+    assert_eq!(
+        audio_system.get_device_volume("mock-unplugged-id").unwrap(),
+        100.0
+    );
+}
+
+#[tokio::test]
+async fn test_external_changes_propagate_between_master_and_group() {
+    let server = TestServer::new().await;
+    let audio_system = server.audio_system();
+    let group_id = GroupId::new(MOCK_GROUP_ID.to_string());
+    audio_system
+        .set_group_volume(&group_id, "mock-device-id", 50.0, GroupVolumeMode::Uniform)
+        .unwrap();
+
+    save_link(
+        &server,
+        json!({
+            "name": "Game and overlay",
+            "members": [
+                { "target": { "type": "master" } },
+                {
+                    "target": { "type": "group", "device_id": "mock-device-id", "group_id": MOCK_GROUP_ID },
+                    "offset": -5.0
+                }
+            ]
+        }),
+    )
+    .await;
+    let links = server.audio_module().volume_links();
+    // A primeira leitura só registra os volumes atuais
+    links.tick().await.unwrap();
+
+    audio_system
+        .set_device_volume("mock-device-id", 65.0)
+        .unwrap();
+    links.tick().await.unwrap();
+    links.tick().await.unwrap();
+    let group = audio_system
+        .get_sessions_for_device("mock-device-id", false)
+        .unwrap()
+        .remove(0);
+    assert_eq!(group.member_volumes, vec![60.0, 60.0]);
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 65.0);
+
+    audio_system
+        .set_group_volume(&group_id, "mock-device-id", 30.0, GroupVolumeMode::Uniform)
+        .unwrap();
+    links.tick().await.unwrap();
+    links.tick().await.unwrap();
+    assert_eq!(audio_system.get_actual_volume().unwrap(), 35.0);
+}
+
+#[tokio::test]
+async fn test_volume_links_are_validated_listed_and_deleted() {
+    let server = TestServer::new().await;
+
+    let response = save_link(
+        &server,
+        json!({
+            "name": "Lonely",
+            "members": [{ "target": { "type": "master" } }]
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["message"],
+        "Link 'Lonely': At least two members are required"
+    );
+
+    save_link(&server, dual_speakers()).await;
+    let response = save_link(
+        &server,
+        json!({
+            "name": "Overlap",
+            "members": [
+                { "target": { "type": "device", "device_id": "mock-unplugged-id" } },
+                { "target": { "type": "application", "process_name": "mock-session" } }
+            ]
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["message"],
+        "Link 'Overlap': A target is already linked in 'Dual speakers'"
+    );
+
+    let json: Value = server
+        .get("/api/v1/volume_links")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json["headers"]["count"], 1);
+    assert_eq!(json["data"][0]["members"][0]["ratio"], 1.0);
+    assert_eq!(json["data"][0]["members"][1]["offset"], 0.0);
+
+    let delete = |name: &'static str| {
+        server.post_json("/api/v1/delete_volume_link", json!({ "name": name }))
+    };
+    assert_eq!(delete("Dual speakers").await.status(), StatusCode::OK);
+    let response = delete("Dual speakers").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["message"], "Volume link not found");
+}
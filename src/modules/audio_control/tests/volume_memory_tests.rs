@@ -0,0 +1,217 @@
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use super::mocks::MOCK_GROUP_ID;
+use super::test_server::{wait_until, TestServer};
+use crate::modules::audio_control::models::{SessionMember, SessionState};
+use crate::modules::audio_control::platform::audio_system_interface::AudioSessionControl;
+
+fn relaunched_session() -> SessionMember {
+    SessionMember {
+        session_id: "mock-session-3".to_string(),
+        pid: 1240,
+        process_name: "Mock-Session.exe".to_string(),
+        volume_level: 100.0,
+        muted: false,
+        state: SessionState::Active,
+    }
+}
+
+async fn set_group_volume(server: &TestServer, volume: f32) {
+    let response = server
+        .post_json(
+            "/api/v1/set_group_volume",
+            json!({
+                "device_id": "mock-device-id",
+                "group_id": MOCK_GROUP_ID,
+                "volume": volume
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+async fn session_volume(server: &TestServer, session_id: &str) -> Value {
+    let json: Value = server
+        .get("/api/v1/list_session/mock-device-id?expand=true")
+        .await
+        .json()
+        .await
+        .unwrap();
+    json["data"][0]["sessions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|member| member["session_id"] == session_id)
+        .map(|member| member["volume_level"].clone())
+        .unwrap_or(Value::Null)
+}
+
+async fn volume_memory(server: &TestServer) -> Value {
+    let json: Value = server
+        .get("/api/v1/volume_memory")
+        .await
+        .json()
+        .await
+        .unwrap();
+    json["data"].clone()
+}
+
+fn mute_sessions_outside_server(server: &TestServer) {
+    for session_id in ["mock-session-1", "mock-session-2"] {
+        server
+            .audio_system()
+            .set_session_mute("mock-device-id", session_id, true)
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_volume_changes_are_remembered_by_process_name() {
+    let server = TestServer::new().await;
+    let monitor = server.audio_module().session_monitor();
+    monitor.poll().await.unwrap();
+
+    // Mudanças pela API e pelo mixer chegam do mesmo jeito, pelo monitor
+    set_group_volume(&server, 40.0).await;
+    mute_sessions_outside_server(&server);
+    monitor.poll().await.unwrap();
+
+    let expected = json!([{
+        "process_name": "mock-session",
+        "enabled": true,
+        "volume": 40.0,
+        "muted": true
+    }]);
+    wait_until("volume change was not remembered", || async {
+        volume_memory(&server).await == expected
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_remembered_volume_is_restored_when_app_starts_again() {
+    let server = TestServer::new().await;
+    let monitor = server.audio_module().session_monitor();
+    monitor.poll().await.unwrap();
+    set_group_volume(&server, 30.0).await;
+    monitor.poll().await.unwrap();
+
+    server.audio_system().add_session(relaunched_session());
+    monitor.poll().await.unwrap();
+
+    // A restauração roda na tarefa em segundo plano
+    wait_until("remembered volume was not restored", || async {
+        session_volume(&server, "mock-session-3").await == 30.0
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_remembered_volumes_are_saved_after_a_quiet_period_or_session_end() {
+    let dir = std::env::temp_dir().join(format!("pc-controller-memory-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let file = dir.join("volume_memory.json");
+    let server = TestServer::with_config(|config| {
+        config.data_dir = Some(dir.clone());
+        config.volume_memory_write_delay = Duration::from_secs(60);
+    })
+    .await;
+    let monitor = server.audio_module().session_monitor();
+    monitor.poll().await.unwrap();
+
+    set_group_volume(&server, 40.0).await;
+    monitor.poll().await.unwrap();
+    wait_until("volume change was not observed", || async {
+        volume_memory(&server).await[0]["volume"] == 40.0
+    })
+    .await;
+    // Ainda dentro do intervalo sem mudanças: nada foi gravado
+    assert!(!file.exists());
+
+    // A aplicação fechou: grava na hora
+    server.audio_system().remove_session("mock-session-2");
+    monitor.poll().await.unwrap();
+    wait_until("remembered volume was not saved", || async {
+        std::fs::read_to_string(&file).is_ok_and(|saved| saved.contains("40.0"))
+    })
+    .await;
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_disabled_app_is_neither_recorded_nor_restored() {
+    let server = TestServer::new().await;
+    let monitor = server.audio_module().session_monitor();
+    monitor.poll().await.unwrap();
+
+    let response = server
+        .post_json(
+            "/api/v1/set_volume_memory",
+            json!({ "process_name": "Mock-Session.EXE", "enabled": false }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["data"],
+        json!({ "process_name": "mock-session", "enabled": false, "muted": false })
+    );
+
+    set_group_volume(&server, 30.0).await;
+    monitor.poll().await.unwrap();
+    server.audio_system().add_session(relaunched_session());
+    monitor.poll().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert!(volume_memory(&server).await[0].get("volume").is_none());
+    assert_eq!(session_volume(&server, "mock-session-3").await, 100.0);
+}
+
+#[tokio::test]
+async fn test_forget_volume_memory() {
+    let server = TestServer::new().await;
+    let monitor = server.audio_module().session_monitor();
+    monitor.poll().await.unwrap();
+    set_group_volume(&server, 40.0).await;
+    monitor.poll().await.unwrap();
+    wait_until("volume change was not remembered", || async {
+        volume_memory(&server).await[0]["volume"] == 40.0
+    })
+    .await;
+
+    let body = json!({ "process_name": "mock-session" });
+    let json: Value = server
+        .post_json("/api/v1/forget_volume_memory", body.clone())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json["data"], true);
+    assert_eq!(volume_memory(&server).await, json!([]));
+
+    let json: Value = server
+        .post_json("/api/v1/forget_volume_memory", body)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json["data"], false);
+}
+
+#[tokio::test]
+async fn test_set_volume_memory_rejects_empty_process_name() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .post_json(
+            "/api/v1/set_volume_memory",
+            json!({ "process_name": " ", "enabled": true }),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,138 @@
+use windows::{
+    core::{Error, Result, HSTRING},
+    Win32::{
+        Foundation::{E_FAIL, HGLOBAL},
+        Graphics::Imaging::{
+            CLSID_WICImagingFactory, GUID_ContainerFormatPng, IWICBitmapFrameEncode,
+            IWICImagingFactory, WICBitmapEncoderNoCache,
+        },
+        System::{
+            Com::{
+                CoCreateInstance,
+                StructuredStorage::{CreateStreamOnHGlobal, GetHGlobalFromStream},
+                CLSCTX_INPROC_SERVER, STATFLAG_NONAME, STATSTG,
+            },
+            Environment::ExpandEnvironmentStringsW,
+            Memory::{GlobalLock, GlobalUnlock},
+        },
+        UI::{
+            Shell::ExtractIconExW,
+            WindowsAndMessaging::{DestroyIcon, HICON},
+        },
+    },
+};
+
+/// Local de um ícone: arquivo (exe, dll ou ico) e índice do recurso
+///
+/// Índices negativos referenciam o id do recurso em vez da posição.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IconLocation {
+    pub path: String,
+    pub index: i32,
+}
+
+impl IconLocation {
+    pub fn new(path: impl Into<String>, index: i32) -> Self {
+        Self {
+            path: path.into(),
+            index,
+        }
+    }
+
+    /// Interpreta o formato `@arquivo,índice` usado pelas sessões de áudio
+    ///
+    /// Sem índice válido o caminho inteiro é usado com índice 0.
+    pub fn parse(location: &str) -> Option<Self> {
+        let location = location.trim().trim_start_matches('@');
+        if location.is_empty() {
+            return None;
+        }
+
+        let parsed = location.rsplit_once(',').and_then(|(path, index)| {
+            index
+                .trim()
+                .parse()
+                .ok()
+                .map(|index| Self::new(path.trim(), index))
+        });
+        Some(parsed.unwrap_or_else(|| Self::new(location, 0)))
+    }
+}
+
+/// Extrai o ícone grande de um arquivo e o codifica em PNG
+///
+/// Retorna `None` quando o arquivo não tem o ícone pedido.
+pub fn extract_icon_png(location: &IconLocation) -> Result<Option<Vec<u8>>> {
+    unsafe {
+        let path = HSTRING::from(expand_environment(&location.path));
+        let mut icon = HICON::default();
+        let extracted = ExtractIconExW(
+            &path,
+            location.index,
+            Some(&mut icon as *mut HICON),
+            None,
+            1,
+        );
+        if extracted == 0 || icon.is_invalid() {
+            return Ok(None);
+        }
+
+        let png = encode_icon_png(icon);
+        let _ = DestroyIcon(icon);
+        png.map(Some)
+    }
+}
+
+/// Converte um HICON em PNG usando o Windows Imaging Component
+unsafe fn encode_icon_png(icon: HICON) -> Result<Vec<u8>> {
+    let factory: IWICImagingFactory =
+        CoCreateInstance(&CLSID_WICImagingFactory, None, CLSCTX_INPROC_SERVER)?;
+    let bitmap = factory.CreateBitmapFromHICON(icon)?;
+
+    let stream = CreateStreamOnHGlobal(HGLOBAL::default(), true)?;
+    let encoder = factory.CreateEncoder(&GUID_ContainerFormatPng, None)?;
+    encoder.Initialize(&stream, WICBitmapEncoderNoCache)?;
+
+    let mut frame: Option<IWICBitmapFrameEncode> = None;
+    encoder.CreateNewFrame(&mut frame, std::ptr::null_mut())?;
+    let frame = frame.ok_or_else(|| Error::from(E_FAIL))?;
+    frame.Initialize(None)?;
+    frame.WriteSource(&bitmap, std::ptr::null())?;
+    frame.Commit()?;
+    encoder.Commit()?;
+
+    // O HGLOBAL pode ser maior que o conteúdo; o tamanho real vem do stream
+    let mut stat = STATSTG::default();
+    stream.Stat(&mut stat, STATFLAG_NONAME)?;
+    let size = stat.cbSize as usize;
+
+    let memory = GetHGlobalFromStream(&stream)?;
+    let data = GlobalLock(memory) as *const u8;
+    if data.is_null() {
+        return Err(Error::from_win32());
+    }
+    let bytes = std::slice::from_raw_parts(data, size).to_vec();
+    let _ = GlobalUnlock(memory);
+
+    Ok(bytes)
+}
+
+/// Expande variáveis como `%SystemRoot%` presentes no caminho
+fn expand_environment(path: &str) -> String {
+    unsafe {
+        let source = HSTRING::from(path);
+        let size = ExpandEnvironmentStringsW(&source, None);
+        if size == 0 {
+            return path.to_string();
+        }
+
+        let mut buffer = vec![0u16; size as usize];
+        let written = ExpandEnvironmentStringsW(&source, Some(&mut buffer));
+        if written == 0 || written > size {
+            return path.to_string();
+        }
+
+        // O tamanho retornado inclui o terminador nulo
+        String::from_utf16_lossy(&buffer[..written as usize - 1])
+    }
+}
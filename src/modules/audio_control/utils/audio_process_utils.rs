@@ -1,9 +1,11 @@
+use std::ffi::c_void;
 use std::path::Path;
 
 use windows::{
-    core::{Result, PWSTR},
+    core::{Result, HSTRING, PCWSTR, PWSTR},
     Win32::{
         Foundation::CloseHandle,
+        Storage::FileSystem::{GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW},
         System::Threading::{
             OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
             PROCESS_QUERY_LIMITED_INFORMATION,
@@ -11,11 +13,10 @@ use windows::{
     },
 };
 
-/// Obtém o nome amigável de um processo a partir do PID
-///
-/// Abre o processo, obtém o caminho completo do executável,
-/// e extrai apenas o nome do arquivo sem extensão.
-pub fn get_friendly_process_name(pid: u32) -> Result<String> {
+use crate::modules::audio_control::models::AppInfo;
+
+/// Obtém o caminho completo do executável de um processo
+pub fn get_process_path(pid: u32) -> Result<String> {
     unsafe {
         // Abre o processo com permissões para ler informações
         let process_handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)?;
@@ -35,12 +36,86 @@ pub fn get_friendly_process_name(pid: u32) -> Result<String> {
         query_result?;
 
         // Converte de UTF-16 para String (perda segura de dados inválidos)
-        let path = String::from_utf16_lossy(&buffer[..size as usize]);
+        Ok(String::from_utf16_lossy(&buffer[..size as usize]))
+    }
+}
+
+/// Monta as informações de exibição da aplicação dona de um processo
+///
+/// O nome exibido vem da descrição do executável (`FileDescription`),
+/// depois do nome do produto (`ProductName`) e por fim do nome do arquivo.
+pub fn get_app_info(pid: u32) -> AppInfo {
+    let Ok(path) = get_process_path(pid) else {
+        return AppInfo::unresolved(pid);
+    };
+
+    let process_name = extract_simple_name(&path);
+    let display_name = get_version_string(&path, "FileDescription")
+        .or_else(|| get_version_string(&path, "ProductName"))
+        .unwrap_or_else(|| process_name.clone());
 
-        Ok(extract_simple_name(&path))
+    AppInfo {
+        display_name,
+        process_name,
+        pid,
+        executable_path: Some(path),
+        is_system_sounds: false,
     }
 }
 
+/// Lê um valor textual do recurso de versão de um executável
+///
+/// Usa a primeira tradução declarada no arquivo; valores vazios são ignorados.
+fn get_version_string(path: &str, key: &str) -> Option<String> {
+    unsafe {
+        let file_name = HSTRING::from(path);
+        let size = GetFileVersionInfoSizeW(&file_name, None);
+        if size == 0 {
+            return None;
+        }
+
+        let mut data = vec![0u8; size as usize];
+        GetFileVersionInfoW(&file_name, None, size, data.as_mut_ptr() as *mut c_void).ok()?;
+
+        // Cada tradução é um par (idioma, página de código) de 16 bits
+        let translation = query_version_value(&data, r"\VarFileInfo\Translation")?;
+        if translation.1 < 4 {
+            return None;
+        }
+        let language = *(translation.0 as *const u16);
+        let code_page = *(translation.0 as *const u16).add(1);
+
+        let sub_block = format!(r"\StringFileInfo\{language:04x}{code_page:04x}\{key}");
+        let (value, length) = query_version_value(&data, &sub_block)?;
+        if length == 0 {
+            return None;
+        }
+
+        let chars = std::slice::from_raw_parts(value as *const u16, length as usize);
+        let text = String::from_utf16_lossy(chars)
+            .trim_end_matches('\0')
+            .trim()
+            .to_string();
+        (!text.is_empty()).then_some(text)
+    }
+}
+
+/// Consulta um bloco do recurso de versão, retornando o ponteiro e o tamanho
+unsafe fn query_version_value(data: &[u8], sub_block: &str) -> Option<(*mut c_void, u32)> {
+    let sub_block = HSTRING::from(sub_block);
+    let mut value: *mut c_void = std::ptr::null_mut();
+    let mut length = 0u32;
+
+    VerQueryValueW(
+        data.as_ptr() as *const c_void,
+        PCWSTR(sub_block.as_ptr()),
+        &mut value,
+        &mut length,
+    )
+    .as_bool()
+    .then_some((value, length))
+}
+
 /// Extrai apenas o nome do arquivo de um caminho completo
 fn extract_simple_name(path: &str) -> String {
     let path_obj = Path::new(path);
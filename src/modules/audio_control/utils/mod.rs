@@ -1,3 +1,5 @@
+pub mod app_icon_utils;
 pub mod audio_process_utils;
 
-pub use audio_process_utils::get_friendly_process_name;
+pub use app_icon_utils::{extract_icon_png, IconLocation};
+pub use audio_process_utils::{get_app_info, get_process_path};
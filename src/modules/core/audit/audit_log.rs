@@ -0,0 +1,349 @@
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard};
+use std::thread;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::modules::core::persistence::PersistenceError;
+use crate::modules::core::Clock;
+
+/// Entradas mantidas em memória para as consultas
+const IN_MEMORY_CAPACITY: usize = 1000;
+
+/// Quantidade padrão e máxima de entradas retornadas por consulta
+const DEFAULT_QUERY_LIMIT: usize = 100;
+const MAX_QUERY_LIMIT: usize = 1000;
+
+/// Chamada que alterou o estado do servidor
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    pub timestamp: NaiveDateTime,
+    /// Identidade declarada no header `X-Client-Id`
+    pub client: String,
+    /// Endereço IP de origem, quando o servidor o conhece
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_addr: Option<String>,
+    pub method: String,
+    pub route: String,
+    /// Corpo da requisição; texto quando não é JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+    pub status: u16,
+}
+
+/// Filtros da consulta ao log
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditQuery {
+    /// Entradas a partir deste horário, inclusive
+    pub since: Option<NaiveDateTime>,
+    /// Entradas até este horário, inclusive
+    pub until: Option<NaiveDateTime>,
+    pub client: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    fn matches(&self, entry: &AuditEntry) -> bool {
+        self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.until.is_none_or(|until| entry.timestamp <= until)
+            && self
+                .client
+                .as_deref()
+                .is_none_or(|client| entry.client == client.trim())
+    }
+
+    fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_QUERY_LIMIT)
+            .clamp(1, MAX_QUERY_LIMIT)
+    }
+}
+
+/// Quando o arquivo do log é rotacionado e quantos arquivos antigos ficam
+#[derive(Debug, Clone, Copy)]
+pub struct AuditRotation {
+    /// Tamanho a partir do qual o arquivo atual vira `audit.1.log`
+    pub max_file_bytes: u64,
+    /// Arquivos rotacionados mantidos; o mais antigo é apagado
+    pub max_files: usize,
+}
+
+impl Default for AuditRotation {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: 1024 * 1024,
+            max_files: 5,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct AuditFile {
+    path: PathBuf,
+    rotation: AuditRotation,
+    file: Option<File>,
+    size: u64,
+}
+
+/// Pedido para a thread que grava o arquivo
+enum WriterMessage {
+    Line(String),
+    /// Responde quando as linhas anteriores já foram gravadas
+    Flush(mpsc::Sender<()>),
+}
+
+/// Log de auditoria das chamadas que alteram estado, em todos os módulos
+///
+/// Em disco, cada entrada é uma linha JSON acrescentada ao fim do arquivo;
+/// nada é reescrito. Quando o arquivo passa do tamanho configurado ele é
+/// renomeado para `audit.1.log` (os anteriores sobem um número) e o mais
+/// antigo além de `max_files` é apagado. Sem caminho (`in_memory`) as
+/// entradas só ficam em memória, o que é usado nos testes.
+///
+/// A gravação roda em uma thread própria (`audit-writer`), então registrar
+/// uma entrada não faz I/O na thread de quem chama. As consultas leem as
+/// últimas `IN_MEMORY_CAPACITY` entradas, mantidas em memória e carregadas
+/// dos arquivos na abertura.
+#[derive(Debug)]
+pub struct AuditLog {
+    clock: Arc<dyn Clock>,
+    tail: Mutex<VecDeque<AuditEntry>>,
+    /// Canal da thread de gravação; ausente no log só em memória
+    writer: Option<mpsc::Sender<WriterMessage>>,
+}
+
+impl AuditLog {
+    pub fn in_memory(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            tail: Mutex::new(VecDeque::new()),
+            writer: None,
+        }
+    }
+
+    /// Abre o log no arquivo indicado; o arquivo é criado na primeira entrada
+    ///
+    /// As entradas mais recentes dos arquivos existentes são lidas aqui, uma
+    /// vez, para responder às consultas.
+    pub fn open(path: impl Into<PathBuf>, rotation: AuditRotation, clock: Arc<dyn Clock>) -> Self {
+        let file = AuditFile {
+            path: path.into(),
+            rotation,
+            file: None,
+            size: 0,
+        };
+        let tail = file.read_tail(IN_MEMORY_CAPACITY).unwrap_or_else(|error| {
+            tracing::warn!("Failed to read audit log: {}", error);
+            VecDeque::new()
+        });
+
+        let (sender, receiver) = mpsc::channel();
+        let spawned = thread::Builder::new()
+            .name("audit-writer".to_string())
+            .spawn(move || file.run(receiver));
+        if let Err(error) = spawned {
+            tracing::error!("Failed to start audit writer thread: {}", error);
+        }
+
+        Self {
+            clock,
+            tail: Mutex::new(tail),
+            writer: Some(sender),
+        }
+    }
+
+    pub fn now(&self) -> NaiveDateTime {
+        self.clock.now()
+    }
+
+    /// Guarda a entrada em memória e a envia para gravação, sem esperar
+    ///
+    /// Falhas de escrita no arquivo são registradas pela thread de gravação.
+    pub fn record(&self, entry: AuditEntry) -> Result<(), PersistenceError> {
+        if let Some(writer) = &self.writer {
+            let line =
+                serde_json::to_string(&entry).map_err(|source| PersistenceError::Serialize {
+                    path: "audit log".to_string(),
+                    source,
+                })?;
+            if writer.send(WriterMessage::Line(line)).is_err() {
+                tracing::error!("Audit writer thread is not running");
+            }
+        }
+
+        let mut tail = self.lock_tail();
+        tail.push_back(entry);
+        while tail.len() > IN_MEMORY_CAPACITY {
+            tail.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Entradas que atendem aos filtros, da mais recente para a mais antiga
+    pub fn query(&self, query: &AuditQuery) -> Vec<AuditEntry> {
+        self.lock_tail()
+            .iter()
+            .rev()
+            .filter(|entry| query.matches(entry))
+            .take(query.limit())
+            .cloned()
+            .collect()
+    }
+
+    /// Espera a thread de gravação terminar as entradas já registradas
+    pub fn flush(&self) {
+        let Some(writer) = &self.writer else {
+            return;
+        };
+        let (done, flushed) = mpsc::channel();
+        if writer.send(WriterMessage::Flush(done)).is_ok() {
+            let _ = flushed.recv();
+        }
+    }
+
+    fn lock_tail(&self) -> MutexGuard<'_, VecDeque<AuditEntry>> {
+        self.tail.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl AuditFile {
+    /// Grava as linhas recebidas até o log ser descartado
+    fn run(mut self, receiver: mpsc::Receiver<WriterMessage>) {
+        for message in receiver {
+            match message {
+                WriterMessage::Line(line) => {
+                    if let Err(error) = self.append(&line) {
+                        tracing::error!("Failed to write audit entry: {}", error);
+                    }
+                }
+                WriterMessage::Flush(done) => {
+                    let _ = done.send(());
+                }
+            }
+        }
+    }
+
+    fn append(&mut self, line: &str) -> Result<(), PersistenceError> {
+        let line_size = line.len() as u64 + 1;
+        if self.file.is_none() {
+            self.open_current()?;
+        }
+        if self.size > 0 && self.size + line_size > self.rotation.max_file_bytes {
+            self.rotate()?;
+            self.open_current()?;
+        }
+
+        let path = self.path.clone();
+        let file = self.file.as_mut().expect("audit file opened above");
+        writeln!(file, "{}", line).map_err(|source| io_error(&path, source))?;
+        self.size += line_size;
+        Ok(())
+    }
+
+    fn open_current(&mut self) -> Result<(), PersistenceError> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|source| io_error(parent, source))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|source| io_error(&self.path, source))?;
+        self.size = file
+            .metadata()
+            .map_err(|source| io_error(&self.path, source))?
+            .len();
+        self.file = Some(file);
+        Ok(())
+    }
+
+    /// Renomeia o arquivo atual e os já rotacionados, apagando o mais antigo
+    fn rotate(&mut self) -> Result<(), PersistenceError> {
+        self.file = None;
+        self.size = 0;
+
+        let max_files = self.rotation.max_files;
+        if max_files == 0 {
+            return remove_if_exists(&self.path);
+        }
+        remove_if_exists(&self.rotated_path(max_files))?;
+        for index in (1..max_files).rev() {
+            rename_if_exists(&self.rotated_path(index), &self.rotated_path(index + 1))?;
+        }
+        rename_if_exists(&self.path, &self.rotated_path(1))
+    }
+
+    /// Lê os arquivos do mais antigo para o atual, guardando as últimas
+    /// `capacity` entradas; linhas inválidas são ignoradas
+    fn read_tail(&self, capacity: usize) -> Result<VecDeque<AuditEntry>, PersistenceError> {
+        let mut paths: Vec<PathBuf> = (1..=self.rotation.max_files)
+            .rev()
+            .map(|index| self.rotated_path(index))
+            .collect();
+        paths.push(self.path.clone());
+
+        let mut entries = VecDeque::new();
+        for path in paths {
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                Err(error) if error.kind() == ErrorKind::NotFound => continue,
+                Err(source) => return Err(io_error(&path, source)),
+            };
+            for line in BufReader::new(file).lines() {
+                let line = line.map_err(|source| io_error(&path, source))?;
+                match serde_json::from_str(&line) {
+                    Ok(entry) => {
+                        entries.push_back(entry);
+                        if entries.len() > capacity {
+                            entries.pop_front();
+                        }
+                    }
+                    Err(error) => {
+                        tracing::warn!("Invalid audit entry in {}: {}", path.display(), error)
+                    }
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// `audit.log` vira `audit.1.log`, `audit.2.log`...
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let stem = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let name = match self.path.extension() {
+            Some(extension) => format!("{}.{}.{}", stem, index, extension.to_string_lossy()),
+            None => format!("{}.{}", stem, index),
+        };
+        self.path.with_file_name(name)
+    }
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), PersistenceError> {
+    match std::fs::remove_file(path) {
+        Err(error) if error.kind() != ErrorKind::NotFound => Err(io_error(path, error)),
+        _ => Ok(()),
+    }
+}
+
+fn rename_if_exists(from: &Path, to: &Path) -> Result<(), PersistenceError> {
+    match std::fs::rename(from, to) {
+        Err(error) if error.kind() != ErrorKind::NotFound => Err(io_error(from, error)),
+        _ => Ok(()),
+    }
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> PersistenceError {
+    PersistenceError::Io {
+        path: path.display().to_string(),
+        source,
+    }
+}
@@ -0,0 +1,111 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{rejection::QueryRejection, ConnectInfo, Query, Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use serde_json::Value;
+
+use crate::modules::core::audit::{AuditEntry, AuditLog, AuditQuery};
+use crate::modules::core::errors::error_codes;
+use crate::modules::core::response::{create_error_response, create_response};
+use crate::modules::core::ClientId;
+
+/// Mesmo limite que o extractor `Json` do axum aplica por padrão
+const MAX_AUDITED_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Tamanho máximo de um corpo que não é JSON guardado como texto
+const MAX_RAW_PARAMS_CHARS: usize = 1024;
+
+/// Registra no log de auditoria toda requisição que pode alterar estado
+///
+/// `GET`, `HEAD` e `OPTIONS` passam direto. O corpo é lido antes do
+/// handler para ser guardado junto com o status da resposta; a entrada é
+/// registrada depois que o handler termina, sem esperar o disco, e uma
+/// falha ao gravar não afeta a resposta.
+pub async fn audit_requests(
+    State(audit): State<Arc<AuditLog>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    ) {
+        return next.run(request).await;
+    }
+
+    let timestamp = audit.now();
+    let (parts, body) = request.into_parts();
+    let mut entry = AuditEntry {
+        timestamp,
+        client: ClientId::from_headers(&parts.headers).to_string(),
+        remote_addr: parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(address)| address.ip().to_string()),
+        method: parts.method.to_string(),
+        route: parts.uri.path().to_string(),
+        params: None,
+        status: 0,
+    };
+
+    let response = match to_bytes(body, MAX_AUDITED_BODY_BYTES).await {
+        Ok(bytes) => {
+            entry.params = params_from_body(&bytes);
+            next.run(Request::from_parts(parts, Body::from(bytes)))
+                .await
+        }
+        Err(_) => create_error_response(
+            error_codes::PAYLOAD_TOO_LARGE,
+            "Request body too large",
+            None,
+        ),
+    };
+
+    entry.status = response.status().as_u16();
+    if let Err(error) = audit.record(entry) {
+        tracing::error!("Failed to write audit entry: {}", error);
+    }
+    response
+}
+
+/// Consulta o log de auditoria, da entrada mais recente para a mais antiga
+pub async fn handle_audit_query(
+    State(audit): State<Arc<AuditLog>>,
+    query: Result<Query<AuditQuery>, QueryRejection>,
+) -> Response {
+    let Query(query) = match query {
+        Ok(query) => query,
+        Err(rejection) => {
+            return create_error_response(
+                error_codes::BAD_REQUEST,
+                &format!("Invalid query parameters: {}", rejection.body_text()),
+                None,
+            )
+        }
+    };
+
+    let entries = audit.query(&query);
+    let size = entries.len();
+    create_response(entries, Some(size))
+}
+
+fn params_from_body(bytes: &[u8]) -> Option<Value> {
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return None;
+    }
+    let params = serde_json::from_slice(bytes).unwrap_or_else(|_| {
+        Value::String(
+            String::from_utf8_lossy(bytes)
+                .chars()
+                .take(MAX_RAW_PARAMS_CHARS)
+                .collect(),
+        )
+    });
+    Some(params)
+}
@@ -0,0 +1,5 @@
+pub mod audit_log;
+pub mod audit_middleware;
+
+pub use audit_log::{AuditEntry, AuditLog, AuditQuery, AuditRotation};
+pub use audit_middleware::{audit_requests, handle_audit_query};
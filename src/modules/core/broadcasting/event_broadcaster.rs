@@ -1,13 +1,62 @@
-use crate::modules::core::models::ServerEvent;
+use crate::modules::core::models::{SequencedEvent, ServerEvent};
+use crate::modules::core::utils::get_timestamp;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
 use tokio::sync::broadcast;
 
+/// Eventos guardados por padrão para repetir a clientes que reconectam
+pub const DEFAULT_REPLAY_CAPACITY: usize = 512;
+
+/// Receiver dos eventos publicados, já numerados
+pub type EventReceiver = broadcast::Receiver<SequencedEvent>;
+
+/// Últimos eventos publicados, numerados em sequência
+///
+/// Eventos transitórios (`ServerEvent::is_transient`) recebem id, mas não são
+/// guardados: o próximo já substitui o anterior.
+#[derive(Debug)]
+struct ReplayBuffer {
+    capacity: usize,
+    /// Id do último evento publicado; zero antes do primeiro
+    last_id: u64,
+    /// Id do último evento que saiu do buffer (ou nem entrou); zero se nenhum
+    dropped_id: u64,
+    events: VecDeque<SequencedEvent>,
+}
+
+/// Eventos que um cliente perdeu desde o último que recebeu
+#[derive(Debug, Clone)]
+pub enum Replay {
+    /// Todos os eventos perdidos, do mais antigo ao mais novo
+    Events(Vec<SequencedEvent>),
+    /// Os eventos perdidos já saíram do buffer, ou o id não é conhecido
+    /// (por exemplo, de antes de o servidor reiniciar)
+    Resync { last_event_id: u64 },
+}
+
+/// Inscrição de um cliente que retoma de onde parou
+pub struct EventSubscription {
+    /// Eventos perdidos até o momento da inscrição
+    pub missed: Replay,
+    /// Último evento publicado antes da inscrição
+    pub last_event_id: u64,
+    /// Eventos publicados depois da inscrição, sem lacunas nem repetições
+    /// em relação a `missed`
+    pub events: EventReceiver,
+}
+
 /// Sistema de broadcast para enviar eventos para múltiplos clientes WebSocket
 ///
 /// Usa o `tokio::sync::broadcast` para permitir que múltiplos clientes
-/// se inscrevam e recebam eventos em tempo real.
+/// se inscrevam e recebam eventos em tempo real. Cada evento publicado
+/// recebe um id sequencial e um timestamp; os últimos ficam guardados para
+/// repetir a clientes que reconectam. Serviços e clientes WebSocket recebem
+/// pelo mesmo canal.
 #[derive(Clone)]
 pub struct Broadcaster {
-    tx: broadcast::Sender<ServerEvent>,
+    tx: broadcast::Sender<SequencedEvent>,
+    /// Também serializa as publicações, para que ids e ordem de envio batam
+    replay: Arc<Mutex<ReplayBuffer>>,
 }
 
 impl Broadcaster {
@@ -16,35 +65,126 @@ impl Broadcaster {
     /// # Arguments
     /// * `capacity` - Número máximo de eventos em buffer antes de descartar
     pub fn new(capacity: usize) -> Self {
+        Self::with_replay(capacity, DEFAULT_REPLAY_CAPACITY)
+    }
+
+    /// Cria um Broadcaster que guarda os últimos `replay_capacity` eventos
+    ///
+    /// # Arguments
+    /// * `capacity` - Número máximo de eventos em buffer antes de descartar
+    /// * `replay_capacity` - Eventos disponíveis para `subscribe_since`
+    pub fn with_replay(capacity: usize, replay_capacity: usize) -> Self {
         assert!(capacity > 0, "Broadcaster capacity must be greater than 0");
         let (tx, _) = broadcast::channel(capacity);
-        Self { tx }
+        Self {
+            tx,
+            replay: Arc::new(Mutex::new(ReplayBuffer {
+                capacity: replay_capacity,
+                last_id: 0,
+                dropped_id: 0,
+                events: VecDeque::with_capacity(replay_capacity),
+            })),
+        }
     }
     /// Inscreve um novo cliente para receber eventos
     ///
     /// Retorna um receiver que pode ser usado para receber eventos.
-    pub fn subscribe(&self) -> broadcast::Receiver<ServerEvent> {
+    pub fn subscribe(&self) -> EventReceiver {
         self.tx.subscribe()
     }
 
+    /// Inscreve um cliente que já recebeu os eventos até `last_event_id`
+    ///
+    /// Sem `last_event_id` o cliente só recebe os eventos seguintes.
+    pub fn subscribe_since(&self, last_event_id: Option<u64>) -> EventSubscription {
+        let replay = self.lock_replay();
+        let events = self.tx.subscribe();
+        let missed = match last_event_id {
+            Some(last_event_id) => replay.since(last_event_id),
+            None => Replay::Events(Vec::new()),
+        };
+        EventSubscription {
+            missed,
+            last_event_id: replay.last_id,
+            events,
+        }
+    }
+
+    /// Eventos publicados depois de `last_event_id`
+    pub fn replay_since(&self, last_event_id: u64) -> Replay {
+        self.lock_replay().since(last_event_id)
+    }
+
     /// Envia um evento para todos os clientes inscritos
     ///
     /// # Arguments
     /// * `event` - Evento a ser broadcasted
     ///
     /// # Returns
-    /// Número de receivers que receberam o evento
+    /// Número de receivers que receberam o evento. Sem receivers o evento
+    /// volta no erro (em `Box`, já que alguns eventos são grandes), mas
+    /// continua guardado para repetição.
     pub fn broadcast(
         &self,
         event: ServerEvent,
-    ) -> Result<usize, broadcast::error::SendError<ServerEvent>> {
-        self.tx.send(event)
+    ) -> Result<usize, Box<broadcast::error::SendError<SequencedEvent>>> {
+        let mut replay = self.lock_replay();
+        let sequenced = replay.push(event);
+        self.tx.send(sequenced).map_err(Box::new)
     }
 
     /// Retorna o número atual de receivers ativos
     pub fn receiver_count(&self) -> usize {
         self.tx.receiver_count()
     }
+
+    fn lock_replay(&self) -> MutexGuard<'_, ReplayBuffer> {
+        self.replay.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ReplayBuffer {
+    fn push(&mut self, event: ServerEvent) -> SequencedEvent {
+        self.last_id += 1;
+        let sequenced = SequencedEvent {
+            id: self.last_id,
+            timestamp: get_timestamp(),
+            event,
+        };
+
+        if sequenced.event.is_transient() {
+            return sequenced;
+        }
+        if self.capacity == 0 {
+            self.dropped_id = sequenced.id;
+            return sequenced;
+        }
+        if self.events.len() == self.capacity {
+            if let Some(dropped) = self.events.pop_front() {
+                self.dropped_id = dropped.id;
+            }
+        }
+        self.events.push_back(sequenced.clone());
+        sequenced
+    }
+
+    fn since(&self, last_event_id: u64) -> Replay {
+        // Quem perdeu um evento que já saiu do buffer precisa reler tudo;
+        // transitórios perdidos não são repetidos
+        if last_event_id > self.last_id || last_event_id < self.dropped_id {
+            return Replay::Resync {
+                last_event_id: self.last_id,
+            };
+        }
+
+        Replay::Events(
+            self.events
+                .iter()
+                .filter(|event| event.id > last_event_id)
+                .cloned()
+                .collect(),
+        )
+    }
 }
 
 /// Implementação padrão com capacidade de 100 eventos
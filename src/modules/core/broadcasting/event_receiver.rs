@@ -0,0 +1,36 @@
+use crate::modules::core::broadcasting::EventReceiver;
+use crate::modules::core::models::ServerEvent;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Resultado de uma leitura de `recv_event`
+#[derive(Debug)]
+pub enum Received {
+    Event(ServerEvent),
+    /// O receiver ficou para trás e eventos foram descartados
+    Lagged,
+}
+
+/// Aguarda o próximo evento de um receiver do broadcaster
+///
+/// Eventos descartados por atraso são registrados em log em nome de
+/// `listener`. Retorna `None` quando o broadcaster é encerrado.
+pub async fn recv_event(events: &mut EventReceiver, listener: &str) -> Option<Received> {
+    match events.recv().await {
+        Ok(sequenced) => Some(Received::Event(sequenced.event)),
+        Err(RecvError::Lagged(skipped)) => {
+            tracing::warn!("{} lagged, {} events skipped", listener, skipped);
+            Some(Received::Lagged)
+        }
+        Err(RecvError::Closed) => None,
+    }
+}
+
+/// Como `recv_event`, mas segue para o evento seguinte quando há atraso
+pub async fn next_event(events: &mut EventReceiver, listener: &str) -> Option<ServerEvent> {
+    loop {
+        match recv_event(events, listener).await? {
+            Received::Event(event) => return Some(event),
+            Received::Lagged => continue,
+        }
+    }
+}
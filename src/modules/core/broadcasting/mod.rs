@@ -1,3 +1,7 @@
 pub mod event_broadcaster;
+pub mod event_receiver;
 
-pub use event_broadcaster::Broadcaster;
+pub use event_broadcaster::{
+    Broadcaster, EventReceiver, EventSubscription, Replay, DEFAULT_REPLAY_CAPACITY,
+};
+pub use event_receiver::{next_event, recv_event, Received};
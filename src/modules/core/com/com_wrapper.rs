@@ -1,3 +1,6 @@
+use std::any::{Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::marker::PhantomData;
 
 use windows::{
@@ -5,6 +8,13 @@ use windows::{
     Win32::System::Com::{CoInitializeEx, CoUninitialize, COINIT_MULTITHREADED},
 };
 
+thread_local! {
+    /// Contextos COM vivos na thread atual
+    static ACTIVE_CONTEXTS: Cell<usize> = const { Cell::new(0) };
+    /// Objetos COM reaproveitados enquanto a thread mantém o COM inicializado
+    static CACHED_OBJECTS: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
 pub struct ComContext {
     // Prevent Send + Sync since COM init is per-thread
     _marker: PhantomData<*const ()>,
@@ -19,10 +29,42 @@ impl ComContext {
             // COINIT_MULTITHREADED permite uso multi-thread das APIs COM
             CoInitializeEx(None, COINIT_MULTITHREADED).ok()?;
         }
+        ACTIVE_CONTEXTS.with(|active| active.set(active.get() + 1));
         Ok(ComContext {
             _marker: PhantomData,
         })
     }
+
+    /// Retorna o objeto COM do tipo `T` já criado nesta thread, ou o cria
+    ///
+    /// O objeto só é guardado enquanto houver um `ComContext` vivo na
+    /// thread, e é liberado junto com o último deles. Em threads que
+    /// inicializam o COM a cada chamada isso equivale a criar sempre; na
+    /// thread do backend, que mantém um contexto aberto, o objeto é criado
+    /// uma única vez.
+    pub fn cached<T: Clone + 'static>(create: impl FnOnce() -> Result<T>) -> Result<T> {
+        if ACTIVE_CONTEXTS.with(Cell::get) == 0 {
+            return create();
+        }
+
+        let key = TypeId::of::<T>();
+        let cached = CACHED_OBJECTS.with(|objects| {
+            objects
+                .borrow()
+                .get(&key)
+                .and_then(|object| object.downcast_ref::<T>())
+                .cloned()
+        });
+        if let Some(object) = cached {
+            return Ok(object);
+        }
+
+        let object = create()?;
+        CACHED_OBJECTS.with(|objects| {
+            objects.borrow_mut().insert(key, Box::new(object.clone()));
+        });
+        Ok(object)
+    }
 }
 
 impl Drop for ComContext {
@@ -31,6 +73,16 @@ impl Drop for ComContext {
     /// Deve ser chamado após completar todas as operações de áudio
     /// para limpar recursos alocados pelo COM.
     fn drop(&mut self) {
+        let remaining = ACTIVE_CONTEXTS.with(|active| {
+            let remaining = active.get().saturating_sub(1);
+            active.set(remaining);
+            remaining
+        });
+        if remaining == 0 {
+            // Os objetos precisam ser liberados antes do COM ser finalizado
+            let objects = CACHED_OBJECTS.with(|objects| std::mem::take(&mut *objects.borrow_mut()));
+            drop(objects);
+        }
         unsafe { CoUninitialize() };
     }
 }
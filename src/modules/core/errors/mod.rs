@@ -11,7 +11,10 @@ pub struct ErrorResponse {
 pub mod error_codes {
     pub const BAD_REQUEST: u16 = 400;
     pub const NOT_FOUND: u16 = 404;
+    pub const CONFLICT: u16 = 409;
+    pub const PAYLOAD_TOO_LARGE: u16 = 413;
     pub const INTERNAL_ERROR: u16 = 500;
+    pub const GATEWAY_TIMEOUT: u16 = 504;
 }
 
 impl ErrorResponse {
@@ -0,0 +1,143 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::sync::oneshot;
+
+/// Comando entregue às threads do backend
+type Job<B> = Box<dyn FnOnce(&B) + Send>;
+
+#[derive(Debug, Error)]
+pub enum BackendError {
+    #[error("Backend call timed out after {} ms", .0.as_millis())]
+    Timeout(Duration),
+
+    #[error("Backend worker is unavailable")]
+    Unavailable,
+}
+
+/// Quantas threads atendem o backend e quanto tempo cada chamada pode esperar
+#[derive(Debug, Clone, Copy)]
+pub struct BackendExecutorConfig {
+    pub threads: usize,
+    /// Tempo máximo entre enviar o comando e receber a resposta
+    pub timeout: Duration,
+}
+
+impl Default for BackendExecutorConfig {
+    fn default() -> Self {
+        Self {
+            threads: 1,
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Executa as chamadas a um backend síncrono em threads dedicadas
+///
+/// As threads são criadas uma vez, recebem os comandos por um canal e
+/// guardam o estado criado por `thread_init` até o executor ser
+/// descartado; no Windows isso mantém o COM inicializado e os objetos COM
+/// em cache. Quem chama só aguarda a resposta, sem ocupar as threads do
+/// runtime. Uma chamada que passa de `timeout` retorna
+/// `BackendError::Timeout`, mas não é interrompida: ela segue até o fim e
+/// só o resultado é descartado. Comandos cujo chamador já desistiu enquanto
+/// estavam na fila nem chegam ao backend.
+pub struct BackendExecutor<B: ?Sized> {
+    sender: mpsc::Sender<Job<B>>,
+    timeout: Duration,
+}
+
+/// Os clones enviam comandos para as mesmas threads
+impl<B: ?Sized> Clone for BackendExecutor<B> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            timeout: self.timeout,
+        }
+    }
+}
+
+impl<B: ?Sized + Send + Sync + 'static> BackendExecutor<B> {
+    pub fn spawn(name: &str, backend: Arc<B>, config: BackendExecutorConfig) -> Self {
+        Self::spawn_with_init(name, backend, config, || ())
+    }
+
+    /// Como `spawn`, mantendo o retorno de `thread_init` vivo em cada thread
+    pub fn spawn_with_init<S, I>(
+        name: &str,
+        backend: Arc<B>,
+        config: BackendExecutorConfig,
+        thread_init: I,
+    ) -> Self
+    where
+        I: Fn() -> S + Send + Sync + 'static,
+    {
+        let (sender, receiver) = mpsc::channel::<Job<B>>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let thread_init = Arc::new(thread_init);
+
+        for index in 0..config.threads.max(1) {
+            let backend = Arc::clone(&backend);
+            let receiver = Arc::clone(&receiver);
+            let thread_init = Arc::clone(&thread_init);
+            let spawned = thread::Builder::new()
+                .name(format!("{}-{}", name, index))
+                .spawn(move || {
+                    let _state = thread_init();
+                    loop {
+                        // O canal fecha quando o executor é descartado
+                        let job = match receiver.lock().unwrap_or_else(|e| e.into_inner()).recv() {
+                            Ok(job) => job,
+                            Err(_) => break,
+                        };
+                        if catch_unwind(AssertUnwindSafe(|| job(&backend))).is_err() {
+                            tracing::error!(
+                                "Backend call panicked on {:?}",
+                                thread::current().name()
+                            );
+                        }
+                    }
+                });
+            if let Err(error) = spawned {
+                tracing::error!(
+                    "Failed to start backend thread {}-{}: {}",
+                    name,
+                    index,
+                    error
+                );
+            }
+        }
+
+        Self {
+            sender,
+            timeout: config.timeout,
+        }
+    }
+
+    /// Roda `operation` em uma thread do backend e aguarda o resultado
+    pub async fn call<R, F>(&self, operation: F) -> Result<R, BackendError>
+    where
+        F: FnOnce(&B) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (reply, response) = oneshot::channel();
+        let job: Job<B> = Box::new(move |backend| {
+            if reply.is_closed() {
+                return;
+            }
+            let _ = reply.send(operation(backend));
+        });
+        self.sender
+            .send(job)
+            .map_err(|_| BackendError::Unavailable)?;
+
+        match tokio::time::timeout(self.timeout, response).await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(_)) => Err(BackendError::Unavailable),
+            Err(_) => Err(BackendError::Timeout(self.timeout)),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod backend_executor;
+
+pub use backend_executor::{BackendError, BackendExecutor, BackendExecutorConfig};
@@ -0,0 +1,118 @@
+use axum::extract::ws::{Message, WebSocket};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::modules::core::broadcasting::{Broadcaster, Replay};
+use crate::modules::core::models::{EventResync, SequencedEvent};
+
+/// Parâmetros de conexão ao WebSocket de eventos
+#[derive(Debug, Default, Deserialize)]
+pub struct EventStreamQuery {
+    /// Último evento recebido antes de a conexão cair
+    pub last_event_id: Option<u64>,
+}
+
+/// Encaminha todos os eventos do broadcaster para um cliente WebSocket
+///
+/// Com `last_event_id`, os eventos perdidos desde então são enviados antes
+/// dos novos; se já não estiverem guardados, o cliente recebe um `resync`.
+/// O mesmo vale quando o cliente fica para trás e o buffer do broadcaster
+/// descarta eventos. A conexão é encerrada quando o cliente fecha o socket.
+pub async fn handle_event_socket(
+    socket: WebSocket,
+    broadcaster: Broadcaster,
+    last_event_id: Option<u64>,
+) {
+    let subscription = broadcaster.subscribe_since(last_event_id);
+    let mut events = subscription.events;
+    let (mut sender, mut receiver) = socket.split();
+    // Eventos até este id já foram entregues (ou cobertos por um resync)
+    let mut delivered = last_event_id.unwrap_or(subscription.last_event_id);
+
+    if send_replay(&mut sender, subscription.missed, &mut delivered)
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Event stream client lagged, {} events skipped", skipped);
+                        let missed = broadcaster.replay_since(delivered);
+                        if send_replay(&mut sender, missed, &mut delivered).await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+
+                // Já entregue pela repetição
+                if event.id <= delivered {
+                    continue;
+                }
+                if send_event(&mut sender, &event, &mut delivered).await.is_err() {
+                    break;
+                }
+            }
+            message = receiver.next() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            }
+        }
+    }
+}
+
+async fn send_replay<S>(sender: &mut S, replay: Replay, delivered: &mut u64) -> Result<(), ()>
+where
+    S: SinkExt<Message> + Unpin,
+{
+    match replay {
+        Replay::Events(events) => {
+            for event in events {
+                send_event(sender, &event, delivered).await?;
+            }
+            Ok(())
+        }
+        Replay::Resync { last_event_id } => {
+            *delivered = last_event_id;
+            send_message(sender, &EventResync { last_event_id }).await
+        }
+    }
+}
+
+async fn send_event<S>(
+    sender: &mut S,
+    event: &SequencedEvent,
+    delivered: &mut u64,
+) -> Result<(), ()>
+where
+    S: SinkExt<Message> + Unpin,
+{
+    *delivered = event.id;
+    send_message(sender, event).await
+}
+
+async fn send_message<S, T>(sender: &mut S, message: &T) -> Result<(), ()>
+where
+    S: SinkExt<Message> + Unpin,
+    T: Serialize,
+{
+    let json = match serde_json::to_string(message) {
+        Ok(json) => json,
+        Err(error) => {
+            tracing::error!("Failed to serialize event: {:?}", error);
+            return Ok(());
+        }
+    };
+    sender
+        .send(Message::Text(json.into()))
+        .await
+        .map_err(|_| ())
+}
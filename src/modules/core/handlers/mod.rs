@@ -1,3 +1,5 @@
+pub mod event_stream;
 pub mod message_handler;
 
+pub use event_stream::{handle_event_socket, EventStreamQuery};
 pub use message_handler::handle_message;
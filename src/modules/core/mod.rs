@@ -1,8 +1,11 @@
+pub mod audit;
 pub mod broadcasting;
 pub mod com;
 pub mod errors;
+pub mod executor;
 pub mod handlers;
 pub mod models;
+pub mod persistence;
 pub mod registry;
 pub mod response;
 #[cfg(test)]
@@ -12,7 +15,7 @@ pub mod utils;
 
 pub use broadcasting::Broadcaster;
 pub use handlers::handle_message;
-pub use models::{ModuleType, ServerEvent};
+pub use models::{ClientId, ModuleType, ServerEvent};
 pub use registry::ModuleRegistry;
 pub use response::{create_error_response, create_response};
-pub use utils::get_timestamp;
+pub use utils::{get_timestamp, get_timestamp_millis, Clock, CronSchedule, SystemClock};
@@ -0,0 +1,66 @@
+use std::convert::Infallible;
+use std::fmt::Display;
+
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderMap},
+};
+
+/// Header com o nome que o cliente usa para se identificar
+pub const CLIENT_ID_HEADER: &str = "x-client-id";
+
+/// Cliente usado quando o header está ausente ou vazio
+pub const ANONYMOUS_CLIENT: &str = "anonymous";
+
+/// Tamanho máximo do nome do cliente; o resto é descartado
+const MAX_CLIENT_ID_LEN: usize = 64;
+
+/// Identidade declarada pelo cliente no header `X-Client-Id`
+///
+/// Não é autenticação: serve para separar o histórico de cada controle
+/// (celular, painel, scripts) e identificar as chamadas no log de
+/// auditoria. Clientes sem o header compartilham a identidade `anonymous`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClientId(String);
+
+impl ClientId {
+    pub fn new(id: &str) -> Self {
+        let id: String = id
+            .trim()
+            .chars()
+            .filter(|c| !c.is_control())
+            .take(MAX_CLIENT_ID_LEN)
+            .collect();
+        if id.is_empty() {
+            Self(ANONYMOUS_CLIENT.to_string())
+        } else {
+            Self(id)
+        }
+    }
+
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let id = headers
+            .get(CLIENT_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        Self::new(id)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for ClientId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientId {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
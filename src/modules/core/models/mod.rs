@@ -1,7 +1,9 @@
 pub mod api_response;
+pub mod client_id;
 pub mod module_request;
 pub mod server_events;
 
 pub use api_response::ApiResponse;
+pub use client_id::ClientId;
 pub use module_request::{ModuleRequest, ModuleType};
-pub use server_events::ServerEvent;
+pub use server_events::{EventResync, SequencedEvent, ServerEvent};
@@ -1,7 +1,7 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// Evento do servidor para notificações proativas
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "event_type", rename_all = "snake_case")]
 pub enum ServerEvent {
     VolumeChanged {
@@ -15,12 +15,87 @@ pub enum ServerEvent {
     DeviceDisconnected {
         device_id: String,
     },
+    /// O dispositivo padrão de saída mudou, pela API ou fora do servidor
+    DefaultDeviceChanged {
+        device_id: String,
+    },
     SessionStarted {
         session_id: String,
         display_name: String,
+        /// Nome do executável sem extensão
+        process_name: String,
+        device_id: String,
+        group_id: String,
+        pid: u32,
+        /// Se a sessão já estava tocando áudio quando foi detectada
+        #[serde(default)]
+        active: bool,
     },
     SessionEnded {
         session_id: String,
+        /// Nome do executável sem extensão
+        process_name: String,
+        device_id: String,
+        #[serde(default)]
+        pid: u32,
+    },
+    /// O volume ou o mute de uma sessão mudou, pela API ou fora do servidor
+    SessionVolumeChanged {
+        session_id: String,
+        /// Nome do executável sem extensão
+        process_name: String,
+        device_id: String,
+        volume: f32,
+        muted: bool,
+    },
+    /// Uma sessão começou ou parou de tocar áudio
+    SessionActivityChanged {
+        session_id: String,
+        /// Nome do executável sem extensão
+        process_name: String,
+        device_id: String,
+        active: bool,
+    },
+    /// O áudio de uma aplicação passou a sair por outro dispositivo
+    SessionRouted {
+        group_id: String,
+        process_name: String,
+        from_device_id: String,
+        to_device_id: String,
+    },
+    /// Uma tarefa agendada rodou e todas as suas ações foram aplicadas
+    ScheduledTaskExecuted {
+        task_id: u64,
+        name: String,
+    },
+    /// Uma tarefa agendada rodou, mas alguma ação falhou
+    ScheduledTaskFailed {
+        task_id: u64,
+        name: String,
+        error: String,
+    },
+    /// Tempo restante do timer de sono; publicado ao iniciar, a cada minuto
+    /// e quando o fade começa
+    SleepTimerUpdated {
+        remaining_secs: u64,
+        fading: bool,
+    },
+    /// Aplicações de comunicação ficaram ativas (os demais grupos estão
+    /// sendo abaixados) ou deixaram de estar (os grupos estão voltando)
+    DuckingChanged {
+        active: bool,
+    },
+    /// O timer de sono terminou (alvos silenciados) ou foi cancelado
+    /// (volumes restaurados)
+    SleepTimerEnded {
+        cancelled: bool,
+    },
+    /// O modo foco foi ligado ou desligado (manualmente ou pelo timer)
+    FocusModeChanged {
+        active: bool,
+        /// Tempo até o modo foco desligar sozinho, se tiver timer
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        remaining_secs: Option<u64>,
     },
     Notification {
         title: String,
@@ -32,4 +107,37 @@ impl ServerEvent {
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(self)
     }
+
+    /// Eventos de progresso publicados com frequência, em que cada um
+    /// substitui o anterior; não são guardados para repetição
+    ///
+    /// `DuckingChanged` não entra aqui: marca o início e o fim do ducking e
+    /// o cliente que perdeu um deles ficaria com o estado errado.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, ServerEvent::SleepTimerUpdated { .. })
+    }
+}
+
+/// Evento como é enviado aos clientes do WebSocket de eventos
+///
+/// `id` cresce de um em um a cada evento publicado, para que um cliente que
+/// reconecta peça só o que perdeu (`last_event_id`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequencedEvent {
+    pub id: u64,
+    /// Segundos Unix em que o evento foi publicado
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub event: ServerEvent,
+}
+
+/// Aviso de que os eventos perdidos pelo cliente não estão mais disponíveis
+///
+/// O cliente deve reler o estado completo; os eventos seguintes continuam a
+/// partir de `last_event_id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event_type", rename = "resync")]
+pub struct EventResync {
+    /// Último evento publicado antes do aviso
+    pub last_event_id: u64,
 }
@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+/// Variável de ambiente que sobrescreve o diretório de dados
+pub const DATA_DIR_ENV: &str = "PC_CONTROLLER_DATA_DIR";
+
+/// Diretório padrão onde os dados persistentes do servidor são gravados
+///
+/// Usa `PC_CONTROLLER_DATA_DIR` se definido, senão `%APPDATA%\pc-controller`,
+/// e por fim a pasta `data` relativa ao diretório atual.
+pub fn default_data_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(DATA_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+
+    std::env::var_os("APPDATA")
+        .map(|app_data| PathBuf::from(app_data).join("pc-controller"))
+        .unwrap_or_else(|| PathBuf::from("data"))
+}
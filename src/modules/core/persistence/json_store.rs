@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PersistenceError {
+    #[error("Failed to write {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Failed to serialize data for {path}: {source}")]
+    Serialize {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// Valor persistido em um arquivo JSON, mantido em memória
+///
+/// Toda alteração feita por `update` é gravada no disco antes de retornar.
+/// Sem caminho (`in_memory`) o valor vive apenas enquanto o processo existir,
+/// o que é usado nos testes.
+pub struct JsonStore<T> {
+    path: Option<PathBuf>,
+    data: RwLock<T>,
+}
+
+impl<T> JsonStore<T>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    /// Cria um store que não grava em disco
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            data: RwLock::new(T::default()),
+        }
+    }
+
+    /// Abre o store a partir de um arquivo JSON
+    ///
+    /// Arquivo ausente ou inválido resulta no valor padrão; no segundo caso o
+    /// erro é registrado e o arquivo será sobrescrito na próxima gravação.
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let data = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|error| {
+                tracing::error!("Invalid data in {}: {}", path.display(), error);
+                T::default()
+            }),
+            Err(_) => T::default(),
+        };
+
+        Self {
+            path: Some(path),
+            data: RwLock::new(data),
+        }
+    }
+
+    /// Lê o valor atual
+    pub fn read<R>(&self, reader: impl FnOnce(&T) -> R) -> R {
+        let data = self.data.read().unwrap_or_else(|e| e.into_inner());
+        reader(&data)
+    }
+
+    /// Altera o valor e grava o resultado no disco
+    pub fn update<R>(&self, updater: impl FnOnce(&mut T) -> R) -> Result<R, PersistenceError> {
+        let mut data = self.data.write().unwrap_or_else(|e| e.into_inner());
+        let result = updater(&mut data);
+        if let Some(path) = &self.path {
+            write_json(path, &*data)?;
+        }
+        Ok(result)
+    }
+}
+
+/// Grava em um arquivo temporário e renomeia, para nunca deixar JSON parcial
+fn write_json<T: Serialize>(path: &Path, data: &T) -> Result<(), PersistenceError> {
+    let display = path.display().to_string();
+    let io_error = |source| PersistenceError::Io {
+        path: display.clone(),
+        source,
+    };
+
+    let json =
+        serde_json::to_string_pretty(data).map_err(|source| PersistenceError::Serialize {
+            path: display.clone(),
+            source,
+        })?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(io_error)?;
+    }
+
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, json).map_err(io_error)?;
+    std::fs::rename(&temp_path, path).map_err(io_error)
+}
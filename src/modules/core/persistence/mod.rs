@@ -0,0 +1,5 @@
+pub mod data_dir;
+pub mod json_store;
+
+pub use data_dir::default_data_dir;
+pub use json_store::{JsonStore, PersistenceError};
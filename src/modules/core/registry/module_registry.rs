@@ -22,6 +22,12 @@ impl ModuleRegistry {
         self.modules.contains_key(&module_name.to_lowercase())
     }
 
+    pub fn start_background_tasks(&self) {
+        for module in self.modules.values() {
+            Arc::clone(module).start_background_tasks();
+        }
+    }
+
     pub fn http_routes(&self) -> Router {
         let mut router = Router::new();
 
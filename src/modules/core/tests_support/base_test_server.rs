@@ -1,9 +1,12 @@
 use serde_json::Value;
 use std::future::IntoFuture;
+use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::modules::app_router::app_with_registry;
-use crate::modules::core::ModuleRegistry;
+use crate::modules::core::audit::AuditLog;
+use crate::modules::core::{ModuleRegistry, SystemClock};
 
 pub struct BaseTestServer {
     base_url: String,
@@ -12,6 +15,14 @@ pub struct BaseTestServer {
 
 impl BaseTestServer {
     pub async fn new_with_registry<F>(configure_registry: F) -> Self
+    where
+        F: FnOnce(&mut ModuleRegistry),
+    {
+        let audit = Arc::new(AuditLog::in_memory(Arc::new(SystemClock)));
+        Self::new_with_audit(configure_registry, audit).await
+    }
+
+    pub async fn new_with_audit<F>(configure_registry: F, audit: Arc<AuditLog>) -> Self
     where
         F: FnOnce(&mut ModuleRegistry),
     {
@@ -22,7 +33,9 @@ impl BaseTestServer {
         let listener = tokio::net::TcpListener::bind(address).await.unwrap();
 
         let addr = listener.local_addr().unwrap();
-        tokio::spawn(axum::serve(listener, app_with_registry(registry)).into_future());
+        let app =
+            app_with_registry(registry, audit).into_make_service_with_connect_info::<SocketAddr>();
+        tokio::spawn(axum::serve(listener, app).into_future());
         tokio::time::sleep(Duration::from_millis(25)).await;
 
         Self {
@@ -31,6 +44,10 @@ impl BaseTestServer {
         }
     }
 
+    pub fn ws_url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url.replacen("http", "ws", 1), path)
+    }
+
     pub async fn get(&self, path: &str) -> reqwest::Response {
         self.client
             .get(format!("{}{}", self.base_url, path))
@@ -39,6 +56,15 @@ impl BaseTestServer {
             .unwrap()
     }
 
+    pub async fn get_with_header(&self, path: &str, name: &str, value: &str) -> reqwest::Response {
+        self.client
+            .get(format!("{}{}", self.base_url, path))
+            .header(name, value)
+            .send()
+            .await
+            .unwrap()
+    }
+
     pub async fn post_json(&self, path: &str, body: Value) -> reqwest::Response {
         self.client
             .post(format!("{}{}", self.base_url, path))
@@ -48,6 +74,22 @@ impl BaseTestServer {
             .unwrap()
     }
 
+    pub async fn post_json_with_header(
+        &self,
+        path: &str,
+        body: Value,
+        name: &str,
+        value: &str,
+    ) -> reqwest::Response {
+        self.client
+            .post(format!("{}{}", self.base_url, path))
+            .header(name, value)
+            .json(&body)
+            .send()
+            .await
+            .unwrap()
+    }
+
     pub async fn post_raw(&self, path: &str, body: &str) -> reqwest::Response {
         self.client
             .post(format!("{}{}", self.base_url, path))
@@ -0,0 +1,42 @@
+use std::sync::Mutex;
+
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+
+use crate::modules::core::utils::Clock;
+
+/// Relógio controlado pelos testes
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<NaiveDateTime>,
+}
+
+impl ManualClock {
+    pub fn new(now: NaiveDateTime) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Relógio parado em uma data fixa, ao meio-dia
+    pub fn at_noon() -> Self {
+        Self::new(
+            NaiveDate::from_ymd_opt(2025, 1, 17)
+                .and_then(|date| date.and_hms_opt(12, 0, 0))
+                .expect("valid fixed date"),
+        )
+    }
+
+    pub fn set(&self, now: NaiveDateTime) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    pub fn advance(&self, delta: TimeDelta) {
+        *self.now.lock().unwrap() += delta;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> NaiveDateTime {
+        *self.now.lock().unwrap()
+    }
+}
@@ -1,3 +1,5 @@
 pub mod base_test_server;
+pub mod manual_clock;
 
 pub use base_test_server::BaseTestServer;
+pub use manual_clock::ManualClock;
@@ -6,4 +6,9 @@ pub type ModuleResponse = Result<Response, anyhow::Error>;
 
 pub trait ModuleHandler: Send + Sync {
     fn routes(self: Arc<Self>) -> Router;
+
+    /// Inicia tarefas em segundo plano do módulo (monitores, automações)
+    ///
+    /// Chamado uma vez ao montar o router, dentro do runtime do tokio.
+    fn start_background_tasks(self: Arc<Self>) {}
 }
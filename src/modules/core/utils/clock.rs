@@ -0,0 +1,20 @@
+use std::fmt::Debug;
+
+use chrono::{Local, NaiveDateTime};
+
+/// Fonte do horário local usada pelas automações
+///
+/// Injetável para que os testes controlem o horário sem esperar o relógio.
+pub trait Clock: Send + Sync + Debug {
+    fn now(&self) -> NaiveDateTime;
+}
+
+/// Relógio do sistema, no fuso horário local
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDateTime, TimeDelta, Timelike};
+
+/// Limite de busca da próxima ocorrência, suficiente para 29 de fevereiro
+const MAX_SEARCH_DAYS: i64 = 366 * 8;
+
+const MONTH_NAMES: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const DAY_NAMES: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Expressão cron de cinco campos: minuto, hora, dia do mês, mês e dia da
+/// semana
+///
+/// Cada campo aceita `*`, valores, intervalos (`1-5`), listas (`1,15`) e
+/// passos (`*/15`, `0-30/10`). Meses e dias da semana também aceitam nomes
+/// em inglês (`jan`, `mon-fri`); domingo é `0` ou `7`. Como no cron
+/// tradicional, quando dia do mês e dia da semana são restritos basta um
+/// deles coincidir.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl CronSchedule {
+    /// Primeiro minuto depois de `after` que satisfaz a expressão
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + TimeDelta::minutes(1);
+        let limit = start + TimeDelta::days(MAX_SEARCH_DAYS);
+        let mut candidate = start;
+
+        while candidate < limit {
+            if !self.day_matches(candidate) {
+                candidate = candidate.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !contains(self.hours, candidate.hour()) {
+                candidate = candidate.with_minute(0)? + TimeDelta::hours(1);
+                continue;
+            }
+            if !contains(self.minutes, candidate.minute()) {
+                candidate += TimeDelta::minutes(1);
+                continue;
+            }
+            return Some(candidate);
+        }
+        None
+    }
+
+    fn day_matches(&self, time: NaiveDateTime) -> bool {
+        if !contains(self.months, time.month()) {
+            return false;
+        }
+        let day_of_month = contains(self.days_of_month, time.day());
+        let day_of_week = contains(self.days_of_week, time.weekday().num_days_from_sunday());
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => day_of_week,
+            (false, true) => day_of_month,
+            (false, false) => day_of_month || day_of_week,
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = CronParseError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(CronParseError(format!(
+                "Expected 5 fields, got {}",
+                fields.len()
+            )));
+        };
+
+        let mut days_of_week = parse_field(day_of_week, 0, 7, DAY_NAMES, 0)?;
+        // 7 também é domingo
+        if contains(days_of_week, 7) {
+            days_of_week |= 1;
+        }
+        let days_of_month = parse_field(day_of_month, 1, 31, &[], 0)?;
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[], 0)?,
+            hours: parse_field(hour, 0, 23, &[], 0)?,
+            days_of_month,
+            months: parse_field(month, 1, 12, MONTH_NAMES, 1)?,
+            days_of_week,
+            // Restrito é o campo que deixa algum dia de fora, seja qual for a
+            // forma escrita (`*`, `*/1`, `1-31`, `0-6`...)
+            any_day_of_month: covers(days_of_month, 1, 31),
+            any_day_of_week: covers(days_of_week, 0, 6),
+        })
+    }
+}
+
+/// Expressão cron inválida
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronParseError(String);
+
+impl fmt::Display for CronParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid cron expression: {}", self.0)
+    }
+}
+
+impl std::error::Error for CronParseError {}
+
+fn contains(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// Se a máscara aceita todos os valores de `min` a `max`
+fn covers(mask: u64, min: u32, max: u32) -> bool {
+    (min..=max).all(|value| contains(mask, value))
+}
+
+/// Converte um campo em uma máscara de bits com os valores aceitos
+///
+/// `names[i]` corresponde ao valor `first_name + i`.
+fn parse_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    first_name: u32,
+) -> Result<u64, CronParseError> {
+    let value = |text: &str| -> Result<u32, CronParseError> {
+        let lower = text.to_ascii_lowercase();
+        let parsed = match names.iter().position(|name| *name == lower) {
+            Some(index) => index as u32 + first_name,
+            None => text
+                .parse()
+                .map_err(|_| CronParseError(format!("'{text}' is not a valid value")))?,
+        };
+        if (min..=max).contains(&parsed) {
+            Ok(parsed)
+        } else {
+            Err(CronParseError(format!("'{text}' is outside {min}-{max}")))
+        }
+    };
+
+    let mut mask = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| CronParseError(format!("'{step}' is not a valid step")))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // Com passo, um valor isolado vai até o fim do intervalo
+                None if step > 1 => (value(range)?, max),
+                None => {
+                    let single = value(range)?;
+                    (single, single)
+                }
+            },
+        };
+        if start > end {
+            return Err(CronParseError(format!("'{range}' is an empty range")));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
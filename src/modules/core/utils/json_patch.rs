@@ -0,0 +1,59 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Operação JSON Patch (RFC 6902)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+/// Operações que transformam `from` em `to`
+///
+/// Objetos são comparados chave a chave; arrays e valores diferentes são
+/// substituídos por inteiro.
+pub fn diff(from: &Value, to: &Value) -> Vec<PatchOperation> {
+    let mut operations = Vec::new();
+    diff_into(&mut operations, String::new(), from, to);
+    operations
+}
+
+fn diff_into(operations: &mut Vec<PatchOperation>, path: String, from: &Value, to: &Value) {
+    match (from, to) {
+        (Value::Object(from), Value::Object(to)) => diff_objects(operations, &path, from, to),
+        _ if from == to => {}
+        _ => operations.push(PatchOperation::Replace {
+            path,
+            value: to.clone(),
+        }),
+    }
+}
+
+fn diff_objects(
+    operations: &mut Vec<PatchOperation>,
+    path: &str,
+    from: &Map<String, Value>,
+    to: &Map<String, Value>,
+) {
+    for key in from.keys().filter(|key| !to.contains_key(*key)) {
+        operations.push(PatchOperation::Remove {
+            path: child_path(path, key),
+        });
+    }
+    for (key, value) in to {
+        match from.get(key) {
+            Some(previous) => diff_into(operations, child_path(path, key), previous, value),
+            None => operations.push(PatchOperation::Add {
+                path: child_path(path, key),
+                value: value.clone(),
+            }),
+        }
+    }
+}
+
+/// Acrescenta uma chave ao JSON Pointer, escapando `~` e `/`
+fn child_path(path: &str, key: &str) -> String {
+    format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"))
+}
@@ -1,2 +1,8 @@
+pub mod clock;
+pub mod cron;
+pub mod json_patch;
 pub mod timestamp_utils;
-pub use timestamp_utils::get_timestamp;
+pub use clock::{Clock, SystemClock};
+pub use cron::{CronParseError, CronSchedule};
+pub use json_patch::PatchOperation;
+pub use timestamp_utils::{get_timestamp, get_timestamp_millis};
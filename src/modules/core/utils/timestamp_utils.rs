@@ -7,3 +7,11 @@ pub fn get_timestamp() -> u64 {
         .expect("System clock is set before UNIX_EPOCH")
         .as_secs()
 }
+
+/// Retorna o timestamp atual em milissegundos Unix
+pub fn get_timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("System clock is set before UNIX_EPOCH")
+        .as_millis() as u64
+}